@@ -0,0 +1,204 @@
+use oot_explorer_game_data::cutscene::{
+    ActorCue, Cutscene, CutsceneCommand, CutsceneCommandType, DestinationCue, FadeOutSeqCue,
+    LightSettingCue, MiscCue, RumbleCue, SeqCue, TextCue, TimeCue, TransitionCue,
+    CUTSCENE_COMMAND_TYPE_DESC, TEXT_TYPE_DESC,
+};
+use oot_explorer_read::{FromVrom, Layout, ReadError, Slice, VromProxy};
+use oot_explorer_reflect::TypeDescriptor;
+use oot_explorer_vrom::Vrom;
+
+/// Prints a cutscene script, one command per paragraph and one cue or camera point per line.
+pub fn dump(vrom: Vrom<'_>, cutscene: Cutscene) {
+    println!(
+        "(0x{:08x}) cutscene: {} commands, {} frames",
+        cutscene.addr().0,
+        cutscene.command_count(vrom),
+        cutscene.frame_count(vrom),
+    );
+    for result in cutscene.commands(vrom) {
+        let (addr, command) = match result {
+            Ok(command) => command,
+            Err(e) => {
+                println!("    {}", e);
+                break;
+            }
+        };
+        print!("    (0x{:08x}) ", addr.0);
+        if let Err(e) = dump_command(vrom, command) {
+            println!("        {}", e);
+        }
+    }
+}
+
+fn dump_command(vrom: Vrom<'_>, command: CutsceneCommand) -> Result<(), ReadError> {
+    match command {
+        CutsceneCommand::Camera {
+            command_type,
+            start_frame,
+            end_frame,
+            points,
+        } => {
+            println!(
+                "{}: frames {}..{}",
+                command_name(command_type),
+                start_frame,
+                end_frame,
+            );
+            for point in points.iter(vrom) {
+                let point = point?;
+                let pos = point.pos(vrom);
+                println!(
+                    "        ({}, {}, {}) roll {}, view angle {}, {} frames{}",
+                    pos.x,
+                    pos.y,
+                    pos.z,
+                    point.roll(vrom),
+                    point.view_angle(vrom),
+                    point.frame(vrom),
+                    if point.continue_flag(vrom) == -1 {
+                        ", last"
+                    } else {
+                        ""
+                    },
+                );
+            }
+        }
+        CutsceneCommand::Misc(cues) => dump_cues(vrom, "MISC", cues, |cue| {
+            format!("misc type {}", cue.misc_type(vrom))
+        })?,
+        CutsceneCommand::LightSetting(cues) => dump_cues(vrom, "LIGHT_SETTING", cues, |cue| {
+            format!(
+                "light setting {}",
+                i32::from(cue.setting_plus_one(vrom)) - 1
+            )
+        })?,
+        CutsceneCommand::Rumble(cues) => dump_cues(vrom, "RUMBLE_CONTROLLER", cues, |cue| {
+            format!(
+                "strength {}, duration {}, decrease rate {}",
+                cue.source_strength(vrom),
+                cue.duration(vrom),
+                cue.decrease_rate(vrom),
+            )
+        })?,
+        CutsceneCommand::ActorCues { command_type, cues } => {
+            dump_cues(vrom, &command_name(command_type), cues, |cue| {
+                let rot = cue.rot(vrom);
+                format!(
+                    "action {}, {:?} -> {:?}, rot ({}, {}, {})",
+                    cue.action(vrom),
+                    cue.start_pos(vrom),
+                    cue.end_pos(vrom),
+                    rot.x,
+                    rot.y,
+                    rot.z,
+                )
+            })?
+        }
+        CutsceneCommand::Text(cues) => dump_cues(vrom, "TEXT", cues, |cue| {
+            let text_type = cue.text_type(vrom);
+            let type_name = value_name(TEXT_TYPE_DESC, text_type.to_u32())
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("type {}", text_type.0));
+            match cue.text_id(vrom) {
+                0xffff => "no text".to_string(),
+                text_id => format!(
+                    "text 0x{:04x} ({}), alternatives 0x{:04x} 0x{:04x}",
+                    text_id,
+                    type_name,
+                    cue.alt_text_id_1(vrom),
+                    cue.alt_text_id_2(vrom),
+                ),
+            }
+        })?,
+        CutsceneCommand::Transition(cues) => dump_cues(vrom, "TRANSITION", cues, |cue| {
+            format!("transition type {}", cue.transition_type(vrom))
+        })?,
+        CutsceneCommand::StartSeq(cues) => dump_cues(vrom, "START_SEQ", cues, |cue| {
+            format!("sequence {}", i32::from(cue.seq_id_plus_one(vrom)) - 1)
+        })?,
+        CutsceneCommand::StopSeq(cues) => dump_cues(vrom, "STOP_SEQ", cues, |cue| {
+            format!("sequence {}", i32::from(cue.seq_id_plus_one(vrom)) - 1)
+        })?,
+        CutsceneCommand::FadeOutSeq(cues) => dump_cues(vrom, "FADE_OUT_SEQ", cues, |cue| {
+            format!("sequence player {}", cue.seq_player(vrom))
+        })?,
+        CutsceneCommand::Time(cues) => dump_cues(vrom, "TIME", cues, |cue| {
+            format!("{:02}:{:02}", cue.hour(vrom), cue.minute(vrom))
+        })?,
+        CutsceneCommand::Destination(cues) => dump_cues(vrom, "DESTINATION", cues, |cue| {
+            format!("destination 0x{:x}", cue.destination(vrom))
+        })?,
+        CutsceneCommand::End => println!("END"),
+    }
+    Ok(())
+}
+
+/// The name of a command type, or a name made from its value for actor cue commands that aren't
+/// named.
+fn command_name(command_type: CutsceneCommandType) -> String {
+    match value_name(CUTSCENE_COMMAND_TYPE_DESC, command_type.to_u32()) {
+        Some(name) => name.to_string(),
+        None => format!("ACTOR_CUE_0x{:x}", command_type.0),
+    }
+}
+
+fn value_name(desc: TypeDescriptor, value: u32) -> Option<&'static str> {
+    match desc {
+        TypeDescriptor::Enum(desc) => desc
+            .values
+            .iter()
+            .find(|&&(x, _)| x == value)
+            .map(|&(_, name)| name),
+        _ => None,
+    }
+}
+
+/// Prints a command's cues, each as its frame range and what `describe` says about it.
+fn dump_cues<T, F>(vrom: Vrom<'_>, name: &str, cues: Slice<T>, describe: F) -> Result<(), ReadError>
+where
+    T: FromVrom + Layout + Copy + CueFrames,
+    F: Fn(T) -> String,
+{
+    println!("{}: {} cues", name, cues.len());
+    for cue in cues.iter(vrom) {
+        let cue = cue?;
+        let (start_frame, end_frame) = cue.frames(vrom);
+        println!(
+            "        frames {}..{}: {}",
+            start_frame,
+            end_frame,
+            describe(cue),
+        );
+    }
+    Ok(())
+}
+
+/// Cues that apply over a range of frames.
+trait CueFrames {
+    fn frames(self, vrom: Vrom<'_>) -> (u16, u16);
+}
+
+macro_rules! cue_frames {
+    ($($cue:ident),* $(,)?) => {
+        $(
+            impl CueFrames for $cue {
+                fn frames(self, vrom: Vrom<'_>) -> (u16, u16) {
+                    (self.start_frame(vrom), self.end_frame(vrom))
+                }
+            }
+        )*
+    };
+}
+
+cue_frames!(
+    MiscCue,
+    LightSettingCue,
+    RumbleCue,
+    ActorCue,
+    TextCue,
+    TransitionCue,
+    SeqCue,
+    FadeOutSeqCue,
+    TimeCue,
+    DestinationCue,
+);
@@ -1,16 +1,17 @@
 use oot_explorer_game_data::gbi::DisplayList;
 use oot_explorer_game_data::header_room::{MeshHeader, RoomHeaderVariant};
 use oot_explorer_game_data::header_scene::SceneHeaderVariant;
-use oot_explorer_game_data::mesh::{Background, JfifMeshVariant, MeshEntry, MeshVariant};
+use oot_explorer_game_data::mesh::{Background, JfifMeshVariant, MeshVariant};
 use oot_explorer_game_data::room::{Room, ROOM_DESC};
 use oot_explorer_game_data::scene::{Scene, SCENE_DESC};
 use oot_explorer_game_data::versions::oot_ntsc_10;
 use oot_explorer_gl::display_list_interpreter::{DisplayListInterpreter, DisplayListOpacity};
 use oot_explorer_gl::shader_state::TextureDescriptor;
-use oot_explorer_read::VromProxy;
+use oot_explorer_read::{ReadError, VromProxy};
+use oot_explorer_reflect::{diff, export, Query, TypeDescriptor, Value};
 use oot_explorer_rom::Rom;
 use oot_explorer_segment::{Segment, SegmentTable};
-use oot_explorer_vrom::{decompress, FileIndex, FileTable, OwnedVrom, Vrom};
+use oot_explorer_vrom::{decompress, FileIndex, FileTable, OwnedVrom, Vrom, VromAddr};
 use std::collections::hash_map::DefaultHasher;
 use std::convert::TryInto;
 use std::fs::File;
@@ -19,7 +20,11 @@ use std::io::{BufWriter, Write};
 use std::path::PathBuf;
 use std::sync::Arc;
 
+use crate::search_filter::SearchFilter;
+
+mod cutscene_text;
 mod reflect_text;
+mod search_filter;
 
 struct Context {
     file_table: FileTable,
@@ -27,6 +32,12 @@ struct Context {
 }
 
 fn main() {
+    // Exporting type definitions doesn't need the game data.
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("export") {
+        return run_export(&args[1..]);
+    }
+
     // Load and decompress the game data. Put the results in an Arc to share with worker threads.
     let (file_table, vrom) = decompress(
         Rom(&std::fs::read("Legend of Zelda, The - Ocarina of Time (U) (V1.0) [!].z64").unwrap()),
@@ -35,13 +46,29 @@ fn main() {
     .unwrap();
     let ctx = Arc::new(Context { file_table, vrom });
 
+    // With arguments, search or print a queried value instead of scanning everything.
+    match args.first().map(String::as_str) {
+        Some("search") => return run_search(&ctx, &args[1..]),
+        Some("diff") => return run_diff(&ctx, &args[1..]),
+        Some("scan") => return run_scan(&ctx, parse_setup(&args[1..], "scan [--setup <index>]")),
+        Some("cutscene") => return run_cutscene(&ctx, &args[1..]),
+        Some(query) => return run_query(&ctx, query),
+        None => (),
+    }
+
+    run_scan(&ctx, 0);
+}
+
+/// Dumps every scene and room as text and JSON, and every texture they use as a PNG. `setup`
+/// selects which of each scene's alternate setups to examine.
+fn run_scan(ctx: &Arc<Context>, setup: u32) {
     // A channel for the main thread to send work to the worker threads.
     let (sender, receiver) = crossbeam::channel::bounded(0);
 
     // Spawn worker threads to dump textures.
     let mut join_handles = vec![];
     for _ in 0..8 {
-        let ctx = Arc::clone(&ctx);
+        let ctx = Arc::clone(ctx);
         let receiver = receiver.clone();
         join_handles.push(std::thread::spawn(move || {
             while let Ok(texture) = receiver.recv() {
@@ -69,6 +96,7 @@ fn main() {
             &mut dlist_interp,
             scene_index,
             scene,
+            setup,
         );
         dlist_interp.clear_batches();
     }
@@ -92,6 +120,152 @@ fn main() {
     }
 }
 
+/// Parses an optional `--setup <index>` argument, exiting with `usage` if there's anything else.
+fn parse_setup(args: &[String], usage: &str) -> u32 {
+    match args {
+        [] => 0,
+        [flag, setup] if flag == "--setup" => setup.parse().unwrap_or_else(|e| {
+            eprintln!("{}: {}", setup, e);
+            std::process::exit(1);
+        }),
+        _ => {
+            eprintln!("usage: {}", usage);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Prints the cutscene scripts that a scene setup's headers point to.
+fn run_cutscene(ctx: &Context, args: &[String]) {
+    const USAGE: &str = "cutscene <scene index> [--setup <index>]";
+    let (scene_index, setup) = match args.split_first() {
+        Some((scene_index, rest)) => (scene_index, parse_setup(rest, USAGE)),
+        None => {
+            eprintln!("usage: {}", USAGE);
+            std::process::exit(1);
+        }
+    };
+    let scene_index: u32 = scene_index.parse().unwrap_or_else(|e| {
+        eprintln!("{}: {}", scene_index, e);
+        std::process::exit(1);
+    });
+
+    let vrom = ctx.vrom.borrow();
+    let scene = oot_ntsc_10::get_scene_table(&ctx.file_table)
+        .unwrap()
+        .get(vrom, scene_index)
+        .unwrap()
+        .scene(vrom)
+        .unwrap()
+        .into_inner();
+    let segment_table = SegmentTable::default().with(Segment::SCENE, scene.addr());
+    let scene = scene.setup(vrom, &segment_table, setup).unwrap();
+
+    let mut found = false;
+    for header in scene.headers(vrom) {
+        if let SceneHeaderVariant::Cutscene(header) = header.unwrap().variant(vrom) {
+            cutscene_text::dump(vrom, header.cutscene(vrom, &segment_table).unwrap());
+            found = true;
+        }
+    }
+    if !found {
+        println!("scene {} setup {} has no cutscene", scene_index, setup);
+    }
+}
+
+fn run_query(ctx: &Context, query: &str) {
+    let (addr, value) = read_query(ctx, query);
+    println!("{}: 0x{:08x}", query, addr.0);
+    println!("{}", serde_json::to_string_pretty(&value).unwrap());
+}
+
+fn run_diff(ctx: &Context, args: &[String]) {
+    let (old, new, json) = match args {
+        [old, new] => (old, new, false),
+        [old, new, flag] if flag == "--json" => (old, new, true),
+        _ => {
+            eprintln!("usage: diff <query> <query> [--json]");
+            std::process::exit(1);
+        }
+    };
+    let differences = diff(&read_query(ctx, old).1, &read_query(ctx, new).1);
+    if json {
+        println!("{}", serde_json::to_string_pretty(&differences).unwrap());
+    } else {
+        for difference in &differences {
+            println!("{}", difference);
+        }
+    }
+}
+
+/// Writes a C header and a Kaitai Struct definition for every reflected type.
+fn run_export(args: &[String]) {
+    let dir = match args {
+        [dir] => PathBuf::from(dir),
+        _ => {
+            eprintln!("usage: export <dir>");
+            std::process::exit(1);
+        }
+    };
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("oot.h"),
+        export::c_header(oot_ntsc_10::ROOT_DESCS, "OOT_EXPLORER_H"),
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("oot.ksy"),
+        export::kaitai(oot_ntsc_10::ROOT_DESCS, "oot"),
+    )
+    .unwrap();
+}
+
+/// Resolves a query and reads its value, exiting on failure.
+fn read_query(ctx: &Context, query: &str) -> (VromAddr, Value) {
+    let vrom = ctx.vrom.borrow();
+    let resolved = match Query::parse(query)
+        .and_then(|query| oot_ntsc_10::resolve_query(&ctx.file_table, vrom, &query))
+    {
+        Ok(resolved) => resolved,
+        Err(e) => {
+            eprintln!("{}: {}", query, e);
+            std::process::exit(1);
+        }
+    };
+    (
+        resolved.target.addr(),
+        resolved.target.value(vrom, &resolved.segment_table),
+    )
+}
+
+fn run_search(ctx: &Context, args: &[String]) {
+    let fail = |message: String| -> ! {
+        eprintln!("{}", message);
+        std::process::exit(1);
+    };
+
+    let (type_name, filters) = args
+        .split_first()
+        .unwrap_or_else(|| fail("usage: search <type> [field=value | field&mask]...".to_string()));
+    let desc = oot_ntsc_10::find_type(type_name)
+        .unwrap_or_else(|| fail(format!("unknown type: {}", type_name)));
+    let filters: Vec<_> = filters
+        .iter()
+        .map(|filter| SearchFilter::parse(filter).unwrap_or_else(|e| fail(e)))
+        .collect();
+
+    let results = oot_ntsc_10::search(&ctx.file_table, ctx.vrom.borrow(), desc, |value| {
+        filters.iter().all(|filter| filter.matches(value))
+    });
+    for (scene_index, e) in &results.errors {
+        eprintln!("scene 0x{:02x}: {}", scene_index, e);
+    }
+    for hit in &results.hits {
+        println!("0x{:08x} {}", hit.addr.0, hit.query);
+    }
+    println!("{} matches", results.hits.len());
+}
+
 fn examine_scene(
     file_table: &FileTable,
     vrom: Vrom<'_>,
@@ -99,11 +273,31 @@ fn examine_scene(
     dlist_interp: &mut DisplayListInterpreter,
     scene_index: usize,
     scene: Scene,
+    setup: u32,
 ) {
     let segment_table = segment_table.with(Segment::SCENE, scene.addr());
+    let scene = scene.setup(vrom, &segment_table, setup).unwrap();
+    let file_prefix = match setup {
+        0 => format!("scene_0x{:02x}", scene_index),
+        _ => format!("scene_0x{:02x}_setup_{}", scene_index, setup),
+    };
 
-    reflect_text::dump(vrom, &segment_table, SCENE_DESC, scene.addr(), 0);
+    reflect_text::dump(
+        vrom,
+        file_table,
+        &segment_table,
+        SCENE_DESC,
+        scene.addr(),
+        0,
+    );
     println!();
+    dump_json(
+        vrom,
+        &segment_table,
+        SCENE_DESC,
+        scene.addr(),
+        &format!("{}.json", file_prefix),
+    );
 
     for result in scene.headers(vrom) {
         let header = result.unwrap();
@@ -124,6 +318,8 @@ fn examine_scene(
                         scene_index,
                         room_index,
                         room,
+                        setup,
+                        &file_prefix,
                     );
                 }
             }
@@ -132,6 +328,7 @@ fn examine_scene(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn examine_room(
     file_table: &FileTable,
     vrom: Vrom<'_>,
@@ -140,11 +337,21 @@ fn examine_room(
     scene_index: usize,
     room_index: usize,
     room: Room,
+    setup: u32,
+    file_prefix: &str,
 ) {
     let segment_table = segment_table.with(Segment::ROOM, room.addr());
+    let room = room.setup(vrom, &segment_table, setup).unwrap();
 
-    reflect_text::dump(vrom, &segment_table, ROOM_DESC, room.addr(), 0);
+    reflect_text::dump(vrom, file_table, &segment_table, ROOM_DESC, room.addr(), 0);
     println!();
+    dump_json(
+        vrom,
+        &segment_table,
+        ROOM_DESC,
+        room.addr(),
+        &format!("{}_room_{}.json", file_prefix, room_index),
+    );
 
     for result in room.headers(vrom) {
         let header = result.unwrap();
@@ -209,12 +416,12 @@ fn enumerate_meshes<F, G>(
     match header.mesh(vrom, segment_table).unwrap().variant(vrom) {
         MeshVariant::Simple(mesh) => {
             for result in mesh.entries(vrom, segment_table).unwrap().iter(vrom) {
+                let entry = result.unwrap();
                 enumerate_mesh_entry(
-                    vrom,
-                    segment_table,
                     scene_index,
                     room_index,
-                    result.unwrap(),
+                    entry.opaque_display_list(vrom, segment_table),
+                    entry.translucent_display_list(vrom, segment_table),
                     &mut f,
                 );
             }
@@ -222,23 +429,23 @@ fn enumerate_meshes<F, G>(
 
         MeshVariant::Jfif(jfif) => match jfif.variant(vrom) {
             JfifMeshVariant::Single(single) => {
+                let entry = single.mesh_entry(vrom, segment_table).unwrap();
                 enumerate_mesh_entry(
-                    vrom,
-                    segment_table,
                     scene_index,
                     room_index,
-                    single.mesh_entry(vrom, segment_table).unwrap(),
+                    entry.opaque_display_list(vrom, segment_table),
+                    entry.translucent_display_list(vrom, segment_table),
                     &mut f,
                 );
                 g(single.background(vrom));
             }
             JfifMeshVariant::Multiple(multiple) => {
+                let entry = multiple.mesh_entry(vrom, segment_table).unwrap();
                 enumerate_mesh_entry(
-                    vrom,
-                    segment_table,
                     scene_index,
                     room_index,
-                    multiple.mesh_entry(vrom, segment_table).unwrap(),
+                    entry.opaque_display_list(vrom, segment_table),
+                    entry.translucent_display_list(vrom, segment_table),
                     &mut f,
                 );
                 for result in multiple
@@ -253,12 +460,12 @@ fn enumerate_meshes<F, G>(
 
         MeshVariant::Clipped(mesh) => {
             for result in mesh.entries(vrom, segment_table).unwrap().iter(vrom) {
+                let entry = result.unwrap();
                 enumerate_mesh_entry(
-                    vrom,
-                    segment_table,
                     scene_index,
                     room_index,
-                    result.unwrap(),
+                    entry.opaque_display_list(vrom, segment_table),
+                    entry.translucent_display_list(vrom, segment_table),
                     &mut f,
                 );
             }
@@ -267,16 +474,15 @@ fn enumerate_meshes<F, G>(
 }
 
 fn enumerate_mesh_entry<F>(
-    vrom: Vrom<'_>,
-    segment_table: &SegmentTable,
     scene_index: usize,
     room_index: usize,
-    entry: impl MeshEntry + Copy,
+    opaque_display_list: Result<Option<DisplayList>, ReadError>,
+    translucent_display_list: Result<Option<DisplayList>, ReadError>,
     mut f: F,
 ) where
     F: FnMut(DisplayListOpacity, DisplayList),
 {
-    match entry.opaque_display_list(vrom, segment_table) {
+    match opaque_display_list {
         Ok(Some(dlist)) => f(DisplayListOpacity::Opaque, dlist),
         Ok(None) => (),
         Err(e) => {
@@ -286,7 +492,7 @@ fn enumerate_mesh_entry<F>(
             )
         }
     }
-    match entry.translucent_display_list(vrom, segment_table) {
+    match translucent_display_list {
         Ok(Some(dlist)) => f(DisplayListOpacity::Translucent, dlist),
         Ok(None) => (),
         Err(e) => {
@@ -298,6 +504,21 @@ fn enumerate_mesh_entry<F>(
     }
 }
 
+fn dump_json(
+    vrom: Vrom<'_>,
+    segment_table: &SegmentTable,
+    desc: TypeDescriptor,
+    addr: VromAddr,
+    file_name: &str,
+) {
+    let file = {
+        let mut path = PathBuf::from("./json");
+        path.push(file_name);
+        BufWriter::new(File::create(path).unwrap())
+    };
+    serde_json::to_writer(file, &Value::read(vrom, segment_table, desc, addr)).unwrap();
+}
+
 fn dump_texture(vrom: Vrom<'_>, texture: &TextureDescriptor) {
     let src = texture.source.src().unwrap();
 
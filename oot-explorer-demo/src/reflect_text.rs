@@ -1,13 +1,15 @@
-use oot_explorer_read::{FromVrom, ReadError};
+use oot_explorer_game_data::versions::oot_ntsc_10;
+use oot_explorer_read::{FromVrom, Qu0_16, Qu10_2, Qu1_11, ReadError, Vec3f, Vec3s};
 use oot_explorer_reflect::{
     BitfieldDescriptor, EnumDescriptor, FieldDescriptor, PointerDescriptor, PrimitiveType,
     StructDescriptor, StructFieldLocation, TypeDescriptor, UnionDescriptor,
 };
 use oot_explorer_segment::{SegmentAddr, SegmentTable};
-use oot_explorer_vrom::{Vrom, VromAddr};
+use oot_explorer_vrom::{FileTable, Vrom, VromAddr};
 
 pub fn dump(
     vrom: Vrom<'_>,
+    file_table: &FileTable,
     segment_table: &SegmentTable,
     desc: TypeDescriptor,
     addr: VromAddr,
@@ -15,16 +17,16 @@ pub fn dump(
 ) {
     match desc {
         TypeDescriptor::Struct(desc) => {
-            dump_struct(vrom, segment_table, desc, addr, indent_level);
+            dump_struct(vrom, file_table, segment_table, desc, addr, indent_level);
         }
         TypeDescriptor::Union(desc) => {
-            dump_union(vrom, segment_table, desc, addr, indent_level);
+            dump_union(vrom, file_table, segment_table, desc, addr, indent_level);
         }
         TypeDescriptor::Enum(desc) => dump_enum(vrom, desc, addr),
         TypeDescriptor::Bitfield(desc) => dump_bitfield(vrom, desc, addr),
         TypeDescriptor::Primitive(desc) => dump_primitive(vrom, desc, addr),
         TypeDescriptor::Pointer(desc) => {
-            dump_pointer(vrom, segment_table, desc, addr, indent_level)
+            dump_pointer(vrom, file_table, segment_table, desc, addr, indent_level)
         }
     }
 }
@@ -47,9 +49,10 @@ fn dump_bitfield(vrom: Vrom<'_>, desc: &'static BitfieldDescriptor, addr: VromAd
         }
 
         let value = (value >> field.shift) & field.mask;
-        // TODO: How do we dump a value that doesn't exist in VROM? Does we need a dump_value that
-        // can only forward to enum and primitive?
-        print!("{}", value);
+        match field.desc.values.binary_search_by_key(&value, |&(x, _)| x) {
+            Ok(index) => print!("{}={}", field.desc.name, field.desc.values[index].1),
+            Err(_) => print!("{}={}", field.desc.name, value),
+        }
     }
 }
 
@@ -81,6 +84,13 @@ fn dump_primitive(vrom: Vrom<'_>, desc: PrimitiveType, addr: VromAddr) -> () {
             PrimitiveType::I32 => {
                 print!("{}", i32::from_vrom(vrom, addr)?)
             }
+            PrimitiveType::U64 => print!("{}", u64::from_vrom(vrom, addr)?),
+            PrimitiveType::F32 => print!("{}", f32::from_vrom(vrom, addr)?),
+            PrimitiveType::Qu10_2 => print!("{}", Qu10_2::from_vrom(vrom, addr)?),
+            PrimitiveType::Qu1_11 => print!("{}", Qu1_11::from_vrom(vrom, addr)?),
+            PrimitiveType::Qu0_16 => print!("{}", Qu0_16::from_vrom(vrom, addr)?),
+            PrimitiveType::Vec3s => print!("{}", Vec3s::from_vrom(vrom, addr)?),
+            PrimitiveType::Vec3f => print!("{}", Vec3f::from_vrom(vrom, addr)?),
             PrimitiveType::VromAddr => {
                 print!("{:?}", VromAddr::from_vrom(vrom, addr)?)
             }
@@ -98,33 +108,38 @@ fn dump_primitive(vrom: Vrom<'_>, desc: PrimitiveType, addr: VromAddr) -> () {
 
 fn dump_pointer(
     vrom: Vrom<'_>,
+    file_table: &FileTable,
     segment_table: &SegmentTable,
     desc: &'static PointerDescriptor,
     addr: VromAddr,
     indent_level: usize,
 ) {
-    let segment_addr = match SegmentAddr::from_vrom(vrom, addr) {
-        Ok(segment_addr) => segment_addr,
-        Err(e) => {
-            print!("{}", e);
+    let vrom_addr = match desc.target_at(vrom, segment_table, addr) {
+        Ok(Some(vrom_addr)) => vrom_addr,
+        Ok(None) => {
+            print!("NULL");
             return;
         }
-    };
-
-    let vrom_addr = match segment_table.resolve(segment_addr) {
-        Ok(vrom_addr) => vrom_addr,
-        Err(e) => {
-            print!("{}", e);
+        Err(item) => {
+            print!("({})", item);
             return;
         }
     };
 
     print!("&");
-    dump(vrom, segment_table, desc.target, vrom_addr, indent_level)
+    dump(
+        vrom,
+        file_table,
+        segment_table,
+        desc.target(),
+        vrom_addr,
+        indent_level,
+    )
 }
 
 fn dump_struct(
     vrom: Vrom<'_>,
+    file_table: &FileTable,
     segment_table: &SegmentTable,
     desc: &'static StructDescriptor,
     addr: VromAddr,
@@ -134,10 +149,20 @@ fn dump_struct(
         .take(4 * indent_level)
         .collect::<String>();
 
-    println!("{} {{", desc.name);
+    match oot_ntsc_10::summarize(file_table, vrom, TypeDescriptor::Struct(desc), addr) {
+        Some(summary) => println!("{} {{ // {}", desc.name, summary),
+        None => println!("{} {{", desc.name),
+    }
 
     for field in desc.fields {
-        dump_field(vrom, segment_table, field, addr, indent_level + 1);
+        dump_field(
+            vrom,
+            file_table,
+            segment_table,
+            field,
+            addr,
+            indent_level + 1,
+        );
     }
 
     print!("{}}}", indent);
@@ -145,6 +170,7 @@ fn dump_struct(
 
 fn dump_union(
     vrom: Vrom<'_>,
+    file_table: &FileTable,
     segment_table: &SegmentTable,
     desc: &'static UnionDescriptor,
     addr: VromAddr,
@@ -155,12 +181,20 @@ fn dump_union(
         .collect::<String>();
 
     println!("{} {{", desc.name);
-    dump_union_body(vrom, segment_table, desc, addr, indent_level + 1);
+    dump_union_body(
+        vrom,
+        file_table,
+        segment_table,
+        desc,
+        addr,
+        indent_level + 1,
+    );
     print!("{}}}", indent);
 }
 
 fn dump_union_body(
     vrom: Vrom<'_>,
+    file_table: &FileTable,
     segment_table: &SegmentTable,
     desc: &'static UnionDescriptor,
     addr: VromAddr,
@@ -170,7 +204,7 @@ fn dump_union_body(
         .take(4 * indent_level)
         .collect::<String>();
 
-    let discriminant_addr = addr + desc.discriminant_offset;
+    let discriminant_addr = desc.discriminant_addr(addr);
     match desc
         .discriminant_desc
         .read_as_u32(vrom, discriminant_addr)
@@ -185,6 +219,7 @@ fn dump_union_body(
             );
             dump(
                 vrom,
+                file_table,
                 segment_table,
                 desc.discriminant_desc,
                 discriminant_addr,
@@ -192,27 +227,22 @@ fn dump_union_body(
             );
             println!(" (0x{:x})", discriminant);
 
-            match desc
-                .variants
-                .binary_search_by_key(&discriminant, |&(x, _)| x)
-            {
-                Ok(index) => match desc.variants[index].1 {
-                    TypeDescriptor::Struct(desc) => {
-                        for field in desc.fields {
-                            dump_field(vrom, segment_table, field, addr, indent_level);
-                        }
+            match desc.variant_at(vrom, addr) {
+                Ok(TypeDescriptor::Struct(desc)) => {
+                    for field in desc.fields {
+                        dump_field(vrom, file_table, segment_table, field, addr, indent_level);
                     }
-                    TypeDescriptor::Union(desc) => {
-                        dump_union_body(vrom, segment_table, desc, addr, indent_level);
-                    }
-                    _ => unimplemented!(
-                        "variant `{}` of union `{}` is not a struct or union",
-                        desc.variants[index].1.name(),
-                        desc.name,
-                    ),
-                },
-                Err(_) => {
-                    println!("{}(unknown variant)", indent);
+                }
+                Ok(TypeDescriptor::Union(desc)) => {
+                    dump_union_body(vrom, file_table, segment_table, desc, addr, indent_level);
+                }
+                Ok(variant) => unimplemented!(
+                    "variant `{}` of union `{}` is not a struct or union",
+                    variant.name(),
+                    desc.name,
+                ),
+                Err(item) => {
+                    println!("{}({})", indent, item);
                 }
             }
         }
@@ -224,6 +254,7 @@ fn dump_union_body(
 
 fn dump_field(
     vrom: Vrom<'_>,
+    file_table: &FileTable,
     segment_table: &SegmentTable,
     field: &'static FieldDescriptor,
     addr: VromAddr,
@@ -243,23 +274,33 @@ fn dump_field(
                 field.name,
                 field.desc.name(),
             );
-            dump(vrom, segment_table, field.desc, addr, indent_level);
+            dump(
+                vrom,
+                file_table,
+                segment_table,
+                field.desc,
+                addr,
+                indent_level,
+            );
             println!();
         }
-        StructFieldLocation::Slice {
-            count_offset,
-            count_desc,
-            ptr_offset,
-        } => {
-            let count_addr = addr + count_offset;
-            print!(
-                "{}(0x{:08x}) {}_count: {} = ",
-                indent,
-                count_addr.0,
-                field.name,
-                count_desc.name(),
-            );
-            let count = match count_desc.read_as_u32(vrom, count_addr) {
+        StructFieldLocation::Slice { ptr_offset, .. }
+        | StructFieldLocation::InferredSlice { ptr_offset, .. } => {
+            match field.location {
+                StructFieldLocation::Slice {
+                    count_offset,
+                    count_desc,
+                    ..
+                } => print!(
+                    "{}(0x{:08x}) {}_count: {} = ",
+                    indent,
+                    (addr + count_offset).0,
+                    field.name,
+                    count_desc.name(),
+                ),
+                _ => print!("{}(inferred) {}_count = ", indent, field.name),
+            }
+            let count = match field.location.slice_count(vrom, segment_table, addr) {
                 Ok(count) => {
                     println!("{}", count);
                     Some(count)
@@ -302,7 +343,14 @@ fn dump_field(
 
                         for _ in 0..count {
                             print!("{}    (0x{:08x}) ", indent, vrom_addr.0);
-                            dump(vrom, segment_table, field.desc, vrom_addr, indent_level + 1);
+                            dump(
+                                vrom,
+                                file_table,
+                                segment_table,
+                                field.desc,
+                                vrom_addr,
+                                indent_level + 1,
+                            );
                             println!();
 
                             vrom_addr += match field.desc.size() {
@@ -330,34 +378,51 @@ fn dump_field(
                 }
             }
         }
-        StructFieldLocation::InlineDelimitedList { offset } => {
-            let mut addr = addr + offset;
+        StructFieldLocation::InlineDelimitedList { offset }
+        | StructFieldLocation::InlineSlice { offset, .. } => {
+            if let StructFieldLocation::InlineSlice {
+                count_offset,
+                count_desc,
+                ..
+            } = field.location
+            {
+                print!(
+                    "{}(0x{:08x}) {}_count: {} = ",
+                    indent,
+                    (addr + count_offset).0,
+                    field.name,
+                    count_desc.name(),
+                );
+                match field.location.slice_count(vrom, segment_table, addr) {
+                    Ok(count) => println!("{}", count),
+                    Err(e) => println!("{}", e),
+                }
+            }
+
             println!(
                 "{}(0x{:08x}) {}: [{}; N] = [",
                 indent,
-                addr.0,
+                (addr + offset).0,
                 field.name,
                 field.desc.name(),
             );
 
-            loop {
-                print!("{}    (0x{:08x}) ", indent, addr.0);
-                dump(vrom, segment_table, field.desc, addr, indent_level + 1);
-                println!();
-
-                if (field
-                    .desc
-                    .is_end()
-                    .expect("inline delimited list element has no is_end"))(
-                    vrom, addr
-                ) {
-                    break;
+            match field.location.inline_elements(vrom, field.desc, addr) {
+                Ok((element_addrs, _)) => {
+                    for element_addr in element_addrs {
+                        print!("{}    (0x{:08x}) ", indent, element_addr.0);
+                        dump(
+                            vrom,
+                            file_table,
+                            segment_table,
+                            field.desc,
+                            element_addr,
+                            indent_level + 1,
+                        );
+                        println!();
+                    }
                 }
-
-                addr += field
-                    .desc
-                    .size()
-                    .expect("inline delimited list element has no size");
+                Err(e) => println!("{}    {}", indent, e),
             }
 
             println!("{}]", indent)
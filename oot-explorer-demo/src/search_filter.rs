@@ -0,0 +1,57 @@
+use oot_explorer_reflect::{Value, ValueKind};
+
+/// A condition on one field of a search hit, parsed from the command line.
+///
+/// `field=value` matches a field equal to an integer (decimal or `0x` hex) or enum value name.
+/// `field&mask` matches a field with any of the bits in `mask` set.
+pub struct SearchFilter {
+    field: String,
+    op: Op,
+}
+
+enum Op {
+    Equals(String),
+    AnyBits(i64),
+}
+
+impl SearchFilter {
+    pub fn parse(s: &str) -> Result<SearchFilter, String> {
+        if let Some(index) = s.find('=') {
+            Ok(SearchFilter {
+                field: s[..index].to_string(),
+                op: Op::Equals(s[index + 1..].to_string()),
+            })
+        } else if let Some(index) = s.find('&') {
+            Ok(SearchFilter {
+                field: s[..index].to_string(),
+                op: Op::AnyBits(
+                    parse_int(&s[index + 1..]).ok_or_else(|| format!("bad mask: {}", s))?,
+                ),
+            })
+        } else {
+            Err(format!("expected field=value or field&mask: {}", s))
+        }
+    }
+
+    pub fn matches(&self, value: &Value) -> bool {
+        let field = match value.field(&self.field) {
+            Some(field) => field,
+            None => return false,
+        };
+        match &self.op {
+            Op::Equals(expected) => match (&field.kind, parse_int(expected)) {
+                (_, Some(expected)) => field.as_i64() == Some(expected),
+                (ValueKind::Enum { name, .. }, None) => *name == Some(expected.as_str()),
+                _ => false,
+            },
+            Op::AnyBits(mask) => matches!(field.as_i64(), Some(x) if x & mask != 0),
+        }
+    }
+}
+
+fn parse_int(s: &str) -> Option<i64> {
+    match s.strip_prefix("0x") {
+        Some(hex) => i64::from_str_radix(hex, 16).ok(),
+        None => s.parse().ok(),
+    }
+}
@@ -0,0 +1,151 @@
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Error, Expr, Ident, LitInt, Result, Token};
+
+/// Arguments to `#[layout(size = .., align_bits = ..)]`.
+pub struct LayoutArgs {
+    pub size: LitInt,
+    pub align_bits: LitInt,
+}
+
+impl Parse for LayoutArgs {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut size = None;
+        let mut align_bits = None;
+        for arg in Punctuated::<NamedArg, Token![,]>::parse_terminated(input)? {
+            let slot = match arg.name.to_string().as_str() {
+                "size" => &mut size,
+                "align_bits" => &mut align_bits,
+                _ => {
+                    return Err(Error::new(
+                        arg.name.span(),
+                        "expected `size` or `align_bits`",
+                    ))
+                }
+            };
+            if slot.is_some() {
+                return Err(Error::new(arg.name.span(), "duplicate layout argument"));
+            }
+            *slot = Some(arg.value);
+        }
+        Ok(LayoutArgs {
+            size: size.ok_or_else(|| input.error("missing `size = ..`"))?,
+            align_bits: align_bits.ok_or_else(|| input.error("missing `align_bits = ..`"))?,
+        })
+    }
+}
+
+/// Arguments to `#[newtype]` or `#[newtype(names = ..)]`.
+#[derive(Default)]
+pub struct NewtypeArgs {
+    pub names: Option<Expr>,
+}
+
+impl Parse for NewtypeArgs {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut args = NewtypeArgs::default();
+        if input.is_empty() {
+            return Ok(args);
+        }
+        let name: Ident = input.parse()?;
+        if name != "names" {
+            return Err(Error::new(name.span(), "expected `names`"));
+        }
+        input.parse::<Token![=]>()?;
+        args.names = Some(input.parse()?);
+        Ok(args)
+    }
+}
+
+struct NamedArg {
+    name: Ident,
+    value: LitInt,
+}
+
+impl Parse for NamedArg {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let value = input.parse()?;
+        Ok(NamedArg { name, value })
+    }
+}
+
+/// A primitive value at a fixed offset, written `u8 @ 1`.
+#[derive(Clone)]
+pub struct TypedOffset {
+    pub type_: Ident,
+    pub offset: LitInt,
+}
+
+impl Parse for TypedOffset {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let type_ = input.parse()?;
+        input.parse::<Token![@]>()?;
+        let offset = input.parse()?;
+        Ok(TypedOffset { type_, offset })
+    }
+}
+
+/// Helper attributes recognized on items, removed from the list of attributes passed through to
+/// the generated type.
+#[derive(Default)]
+pub struct ItemAttrs {
+    pub is_end: Option<Expr>,
+    pub discriminant: Option<TypedOffset>,
+    pub end_discriminant: Option<Expr>,
+    pub passthrough: Vec<Attribute>,
+}
+
+impl ItemAttrs {
+    pub fn parse(attrs: Vec<Attribute>) -> Result<Self> {
+        let mut result = ItemAttrs::default();
+        for attr in attrs {
+            if attr.path.is_ident("is_end") {
+                set_once(&mut result.is_end, &attr, attr.parse_args()?)?;
+            } else if attr.path.is_ident("discriminant") {
+                set_once(&mut result.discriminant, &attr, attr.parse_args()?)?;
+            } else if attr.path.is_ident("end_discriminant") {
+                set_once(&mut result.end_discriminant, &attr, attr.parse_args()?)?;
+            } else {
+                result.passthrough.push(attr);
+            }
+        }
+        Ok(result)
+    }
+}
+
+/// Helper attributes recognized on struct fields.
+#[derive(Default)]
+pub struct FieldAttrs {
+    pub at: Option<LitInt>,
+    pub count: Option<TypedOffset>,
+    pub infer_len: Option<Expr>,
+    pub passthrough: Vec<Attribute>,
+}
+
+impl FieldAttrs {
+    pub fn parse(attrs: Vec<Attribute>) -> Result<Self> {
+        let mut result = FieldAttrs::default();
+        for attr in attrs {
+            if attr.path.is_ident("at") {
+                set_once(&mut result.at, &attr, attr.parse_args()?)?;
+            } else if attr.path.is_ident("count") {
+                set_once(&mut result.count, &attr, attr.parse_args()?)?;
+            } else if attr.path.is_ident("infer_len") {
+                set_once(&mut result.infer_len, &attr, attr.parse_args()?)?;
+            } else {
+                result.passthrough.push(attr);
+            }
+        }
+        Ok(result)
+    }
+}
+
+fn set_once<T>(slot: &mut Option<T>, attr: &Attribute, value: T) -> Result<()> {
+    if slot.is_some() {
+        return Err(Error::new_spanned(attr, "duplicate attribute"));
+    }
+    *slot = Some(value);
+    Ok(())
+}
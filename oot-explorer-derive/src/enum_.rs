@@ -0,0 +1,92 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Fields, Ident, Result};
+
+use crate::names::{desc_ident, primitive_type};
+
+pub fn expand(underlying: Ident, input: DeriveInput) -> Result<TokenStream> {
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "interface types cannot be generic",
+        ));
+    }
+    let primitive = primitive_type(&underlying)?;
+    let variants = match input.data {
+        Data::Enum(data) => data.variants,
+        _ => {
+            return Err(Error::new(
+                input.ident.span(),
+                "`#[primitive_enum]` only applies to enums",
+            ))
+        }
+    };
+
+    let mut value_names = vec![];
+    let mut value_exprs = vec![];
+    let mut value_attrs = vec![];
+    for variant in variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(Error::new_spanned(
+                variant.fields,
+                "primitive enum values cannot have fields",
+            ));
+        }
+        let span = variant.ident.span();
+        let (_, value) = variant.discriminant.ok_or_else(|| {
+            Error::new(
+                span,
+                "primitive enum values need an explicit value, e.g. `NAME = 0x01`",
+            )
+        })?;
+        value_names.push(variant.ident);
+        value_exprs.push(value);
+        value_attrs.push(variant.attrs);
+    }
+
+    let name = &input.ident;
+    let name_string = name.to_string();
+    let vis = &input.vis;
+    let attrs = &input.attrs;
+    let desc = desc_ident(name);
+    let value_name_strings = value_names.iter().map(Ident::to_string);
+
+    Ok(quote! {
+        #vis const #desc: ::oot_explorer_reflect::TypeDescriptor =
+            ::oot_explorer_reflect::TypeDescriptor::Enum(&::oot_explorer_reflect::EnumDescriptor {
+                name: #name_string,
+                underlying: #primitive,
+                values: &[#((#value_exprs, #value_name_strings),)*],
+            });
+
+        #(#attrs)*
+        #[derive(Clone, Copy, Eq, PartialEq)]
+        #vis struct #name(pub #underlying);
+
+        impl #name {
+            #(
+                #(#value_attrs)*
+                pub const #value_names: #name = #name(#value_exprs);
+            )*
+
+            pub const fn to_u32(self) -> u32 {
+                self.0 as u32
+            }
+        }
+
+        impl ::oot_explorer_read::FromVrom for #name {
+            fn from_vrom(
+                vrom: ::oot_explorer_vrom::Vrom<'_>,
+                addr: ::oot_explorer_vrom::VromAddr,
+            ) -> ::std::result::Result<Self, ::oot_explorer_read::ReadError> {
+                ::oot_explorer_read::aligned_data::<Self>(vrom, addr)?;
+                Ok(Self(<#underlying as ::oot_explorer_read::FromVrom>::from_vrom(vrom, addr)?))
+            }
+        }
+
+        impl ::oot_explorer_read::Layout for #name {
+            const SIZE: u32 = <#underlying as ::oot_explorer_read::Layout>::SIZE;
+            const ALIGN_BITS: u32 = <#underlying as ::oot_explorer_read::Layout>::ALIGN_BITS;
+        }
+    })
+}
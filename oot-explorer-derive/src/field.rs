@@ -0,0 +1,384 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{
+    Attribute, Error, Expr, Field, GenericArgument, Ident, LitInt, Path, PathArguments, Result,
+    Type, TypePtr, TypeSlice,
+};
+
+use crate::args::{FieldAttrs, TypedOffset};
+use crate::names::{desc_path, primitive_type, type_path};
+
+/// A parsed struct field.
+pub struct StructField {
+    name: Ident,
+    attrs: Vec<Attribute>,
+    offset: LitInt,
+    kind: FieldKind,
+}
+
+enum FieldKind {
+    /// `T`: a value stored in place.
+    Simple { type_: Type },
+    /// `*const T`: a segmented pointer to a value.
+    Pointer { target: Type },
+    /// `Option<*const T>`: a segmented pointer to a value, or NULL.
+    NullablePointer { target: Type },
+    /// `*const [T]`: a segmented pointer to a slice with a separately stored count. As
+    /// `Option<*const [T]>`, the pointer may be NULL.
+    Slice {
+        element: Type,
+        count: TypedOffset,
+        nullable: bool,
+    },
+    /// `*const [T]`: a segmented pointer to a slice whose count is inferred by a function. As
+    /// `Option<*const [T]>`, the pointer may be NULL.
+    InferredSlice {
+        element: Type,
+        infer_len: Box<Expr>,
+        nullable: bool,
+    },
+    /// `[T]`: an inline list terminated by a sentinel value.
+    InlineDelimitedList { element: Type },
+}
+
+impl StructField {
+    pub fn parse(field: Field) -> Result<Self> {
+        let span = field.span();
+        let name = field
+            .ident
+            .ok_or_else(|| Error::new(span, "interface fields must be named"))?;
+        let attrs = FieldAttrs::parse(field.attrs)?;
+        let offset = attrs
+            .at
+            .ok_or_else(|| Error::new(name.span(), "missing `#[at(offset)]` attribute on field"))?;
+
+        let kind = match field.ty {
+            Type::Ptr(ptr) => match *ptr_elem(ptr)? {
+                Type::Slice(slice) => slice_kind(slice, &attrs.count, &attrs.infer_len, false)?,
+                target => FieldKind::Pointer { target },
+            },
+            Type::Slice(slice) => FieldKind::InlineDelimitedList {
+                element: *slice.elem,
+            },
+            type_ => match option_ptr(&type_) {
+                Some(ptr) => match *ptr_elem(ptr.clone())? {
+                    Type::Slice(slice) => slice_kind(slice, &attrs.count, &attrs.infer_len, true)?,
+                    target => FieldKind::NullablePointer { target },
+                },
+                None => FieldKind::Simple { type_ },
+            },
+        };
+        if let (Some(count), false) = (&attrs.count, matches!(kind, FieldKind::Slice { .. })) {
+            return Err(Error::new(
+                count.type_.span(),
+                "`#[count]` only applies to fields of type `*const [T]`",
+            ));
+        }
+        if let (Some(infer_len), false) = (
+            &attrs.infer_len,
+            matches!(kind, FieldKind::InferredSlice { .. }),
+        ) {
+            return Err(Error::new_spanned(
+                infer_len,
+                "`#[infer_len]` only applies to fields of type `*const [T]`",
+            ));
+        }
+
+        Ok(StructField {
+            name,
+            attrs: attrs.passthrough,
+            offset,
+            kind,
+        })
+    }
+
+    /// Emits a `FieldDescriptor` literal.
+    pub fn descriptor(&self) -> Result<TokenStream> {
+        let name = self.name.to_string();
+        let offset = &self.offset;
+        let (location, desc) = match &self.kind {
+            FieldKind::Simple { type_ } => (
+                quote!(::oot_explorer_reflect::StructFieldLocation::Simple { offset: #offset }),
+                path_tokens(desc_path(type_)?),
+            ),
+            FieldKind::Pointer { target } => (
+                quote!(::oot_explorer_reflect::StructFieldLocation::Simple { offset: #offset }),
+                pointer_descriptor(target, false)?,
+            ),
+            FieldKind::NullablePointer { target } => (
+                quote!(::oot_explorer_reflect::StructFieldLocation::Simple { offset: #offset }),
+                pointer_descriptor(target, true)?,
+            ),
+            FieldKind::Slice { element, count, .. } => {
+                let count_offset = &count.offset;
+                let count_desc = primitive_type(&count.type_)?;
+                (
+                    quote!(::oot_explorer_reflect::StructFieldLocation::Slice {
+                        count_offset: #count_offset,
+                        count_desc: #count_desc,
+                        count_span: None,
+                        ptr_offset: #offset,
+                    }),
+                    path_tokens(desc_path(element)?),
+                )
+            }
+            FieldKind::InferredSlice {
+                element, infer_len, ..
+            } => (
+                quote!(::oot_explorer_reflect::StructFieldLocation::InferredSlice {
+                    ptr_offset: #offset,
+                    infer_len: #infer_len,
+                }),
+                path_tokens(desc_path(element)?),
+            ),
+            FieldKind::InlineDelimitedList { element } => (
+                quote!(::oot_explorer_reflect::StructFieldLocation::InlineDelimitedList {
+                    offset: #offset,
+                }),
+                path_tokens(desc_path(element)?),
+            ),
+        };
+        Ok(quote! {
+            ::oot_explorer_reflect::FieldDescriptor {
+                name: #name,
+                location: #location,
+                desc: #desc,
+            }
+        })
+    }
+
+    /// Emits an accessor method.
+    pub fn accessor(&self) -> Result<TokenStream> {
+        let name = &self.name;
+        let attrs = &self.attrs;
+        let offset = &self.offset;
+        Ok(match &self.kind {
+            FieldKind::Simple { type_ } => {
+                let read = read_simple_field(type_, offset);
+                quote! {
+                    #(#attrs)*
+                    pub fn #name(self, vrom: ::oot_explorer_vrom::Vrom<'_>) -> #type_ {
+                        #read
+                    }
+                }
+            }
+            FieldKind::Pointer { target } => {
+                let read_ptr = read_simple_field(&segment_addr_type(), offset);
+                quote! {
+                    #(#attrs)*
+                    pub fn #name(
+                        self,
+                        vrom: ::oot_explorer_vrom::Vrom<'_>,
+                        segment_table: &::oot_explorer_segment::SegmentTable,
+                    ) -> ::std::result::Result<#target, ::oot_explorer_segment::SegmentError> {
+                        let vrom_addr = segment_table.resolve(#read_ptr)?;
+                        // Unwrap because struct size and alignment have already been checked.
+                        Ok(<#target as ::oot_explorer_read::FromVrom>::from_vrom(vrom, vrom_addr)
+                            .unwrap())
+                    }
+                }
+            }
+            FieldKind::NullablePointer { target } => {
+                let read_ptr = read_simple_field(&segment_addr_type(), offset);
+                quote! {
+                    #(#attrs)*
+                    pub fn #name(
+                        self,
+                        vrom: ::oot_explorer_vrom::Vrom<'_>,
+                        segment_table: &::oot_explorer_segment::SegmentTable,
+                    ) -> ::std::result::Result<
+                        ::std::option::Option<#target>,
+                        ::oot_explorer_read::ReadError,
+                    > {
+                        #read_ptr
+                            .non_null()
+                            .map(|segment_addr| {
+                                <#target as ::oot_explorer_read::FromVrom>::from_vrom(
+                                    vrom,
+                                    segment_table.resolve(segment_addr)?,
+                                )
+                            })
+                            .transpose()
+                    }
+                }
+            }
+            FieldKind::Slice {
+                element,
+                count,
+                nullable,
+            } => {
+                let read_ptr = read_simple_field(&segment_addr_type(), offset);
+                let count_type = &count.type_;
+                let read_count = read_simple_field(&syn::parse_quote!(#count_type), &count.offset);
+                let slice = quote!(::oot_explorer_read::Slice<#element>);
+                let make_slice = quote!(::oot_explorer_read::Slice::new(vrom_addr, count as u32));
+                let (return_type, body) =
+                    slice_getter_body(&slice, &read_ptr, &make_slice, *nullable);
+                quote! {
+                    #(#attrs)*
+                    pub fn #name(
+                        self,
+                        vrom: ::oot_explorer_vrom::Vrom<'_>,
+                        segment_table: &::oot_explorer_segment::SegmentTable,
+                    ) -> ::std::result::Result<#return_type, ::oot_explorer_segment::SegmentError> {
+                        let count = #read_count;
+                        #body
+                    }
+                }
+            }
+            FieldKind::InferredSlice {
+                element, nullable, ..
+            } => {
+                let read_ptr = read_simple_field(&segment_addr_type(), offset);
+                let slice = quote!(::oot_explorer_read::Slice<#element>);
+                let make_slice = quote!(::oot_explorer_read::Slice::with_inferred_len(
+                    vrom_addr, len
+                ));
+                let (return_type, body) =
+                    slice_getter_body(&slice, &read_ptr, &make_slice, *nullable);
+                quote! {
+                    #(#attrs)*
+                    pub fn #name(
+                        self,
+                        vrom: ::oot_explorer_vrom::Vrom<'_>,
+                        segment_table: &::oot_explorer_segment::SegmentTable,
+                        len: impl ::oot_explorer_read::InferLen,
+                    ) -> ::std::result::Result<#return_type, ::oot_explorer_segment::SegmentError> {
+                        #body
+                    }
+                }
+            }
+            FieldKind::InlineDelimitedList { element } => {
+                quote! {
+                    #(#attrs)*
+                    pub fn #name(
+                        self,
+                        vrom: ::oot_explorer_vrom::Vrom<'_>,
+                    ) -> ::oot_explorer_read::SentinelIter<'_, #element> {
+                        ::oot_explorer_read::SentinelIter::new(vrom, self.addr + #offset)
+                    }
+                }
+            }
+        })
+    }
+}
+
+/// Classifies a slice pointer field by how its count is found.
+fn slice_kind(
+    slice: TypeSlice,
+    count: &Option<TypedOffset>,
+    infer_len: &Option<Expr>,
+    nullable: bool,
+) -> Result<FieldKind> {
+    match (count, infer_len) {
+        (Some(count), None) => Ok(FieldKind::Slice {
+            element: *slice.elem,
+            count: count.clone(),
+            nullable,
+        }),
+        (None, Some(infer_len)) => Ok(FieldKind::InferredSlice {
+            element: *slice.elem,
+            infer_len: Box::new(infer_len.clone()),
+            nullable,
+        }),
+        _ => Err(Error::new_spanned(
+            &slice,
+            "slice pointers need either a `#[count(type @ offset)]` or an `#[infer_len(path)]` \
+             attribute",
+        )),
+    }
+}
+
+/// Emits the return type and body of a slice getter, which builds `make_slice` from `vrom_addr`.
+/// Nullable slices are wrapped in an `Option` that is `None` for a NULL pointer.
+fn slice_getter_body(
+    slice: &TokenStream,
+    read_ptr: &TokenStream,
+    make_slice: &TokenStream,
+    nullable: bool,
+) -> (TokenStream, TokenStream) {
+    if nullable {
+        (
+            quote!(::std::option::Option<#slice>),
+            quote! {
+                match #read_ptr.non_null() {
+                    Some(segment_addr) => {
+                        let vrom_addr = segment_table.resolve(segment_addr)?;
+                        Ok(Some(#make_slice))
+                    }
+                    None => Ok(None),
+                }
+            },
+        )
+    } else {
+        (
+            slice.clone(),
+            quote! {
+                let vrom_addr = segment_table.resolve(#read_ptr)?;
+                Ok(#make_slice)
+            },
+        )
+    }
+}
+
+/// Emits an expression that reads a value at a fixed offset from `self.addr`.
+pub fn read_simple_field(type_: &Type, offset: &LitInt) -> TokenStream {
+    quote! {
+        // Unwrap because struct size and alignment have already been checked.
+        <#type_ as ::oot_explorer_read::FromVrom>::from_vrom(vrom, self.addr + #offset).unwrap()
+    }
+}
+
+/// Emits a `TypeDescriptor::Pointer` expression targeting the given type.
+pub fn pointer_descriptor(target: &Type, nullable: bool) -> Result<TokenStream> {
+    let target_path = type_path(target)?;
+    let target_name = format!("{}*", target_path.segments.last().unwrap().ident);
+    let target_desc = desc_path(target)?;
+    Ok(quote! {
+        ::oot_explorer_reflect::TypeDescriptor::Pointer(&::oot_explorer_reflect::PointerDescriptor {
+            name: #target_name,
+            target: || #target_desc,
+            nullable: #nullable,
+        })
+    })
+}
+
+/// Returns the pointee of a `*const` pointer type.
+fn ptr_elem(ptr: TypePtr) -> Result<Box<Type>> {
+    match ptr.mutability {
+        Some(mutability) => Err(Error::new_spanned(
+            mutability,
+            "segmented pointers are read-only; use `*const`",
+        )),
+        None => Ok(ptr.elem),
+    }
+}
+
+/// Matches `Option<*const T>`.
+fn option_ptr(type_: &Type) -> Option<&TypePtr> {
+    let path = match type_ {
+        Type::Path(type_path) if type_path.qself.is_none() => &type_path.path,
+        _ => return None,
+    };
+    let last = path.segments.last()?;
+    if last.ident != "Option" {
+        return None;
+    }
+    let args = match &last.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => &args.args,
+        _ => return None,
+    };
+    match args.first()? {
+        GenericArgument::Type(Type::Ptr(ptr)) => Some(ptr),
+        _ => None,
+    }
+}
+
+fn segment_addr_type() -> Type {
+    syn::parse_quote!(::oot_explorer_segment::SegmentAddr)
+}
+
+fn path_tokens(path: Path) -> TokenStream {
+    quote!(#path)
+}
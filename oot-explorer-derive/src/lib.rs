@@ -0,0 +1,121 @@
+//! Attribute macros that generate VROM proxy types and their reflection descriptors.
+//!
+//! Every annotated item expands to a `Copy` proxy type that wraps a `VromAddr`, implementations of
+//! `FromVrom` and `Layout` (plus `VromProxy` for structs and unions), and a `*_DESC`
+//! `TypeDescriptor` constant for use by the reflection crates.
+//!
+//! Descriptors for referenced types are found by naming convention: a field of type `Foo` refers
+//! to a constant `FOO_DESC`, which must be in scope at the expansion site.
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, Data, DeriveInput, Error};
+
+mod args;
+mod enum_;
+mod field;
+mod names;
+mod newtype;
+mod struct_;
+mod union;
+
+/// Declares a struct or union that is read from VROM.
+///
+/// # Structs
+///
+/// Each field is annotated with its byte offset and becomes an accessor method. The field's type
+/// selects the kind of accessor:
+///
+/// - `T` reads a value in place.
+/// - `*const T` follows a segmented pointer.
+/// - `Option<*const T>` follows a segmented pointer that may be NULL.
+/// - `*const [T]` follows a segmented pointer to a slice whose length is read from another
+///   offset, given by `#[count(u8 @ 1)]`. If the length isn't stored, `#[infer_len(path)]` names
+///   an `InferLenFn` that reflection uses to infer it instead, and the accessor takes an
+///   `impl InferLen` argument.
+/// - `[T]` iterates an inline list terminated by a sentinel value.
+///
+/// ```ignore
+/// #[layout(size = 8, align_bits = 2)]
+/// pub struct RoomListHeader {
+///     #[at(1)]
+///     room_count: u8,
+///     #[at(4)]
+///     #[count(u8 @ 1)]
+///     room_list: *const [RoomListEntry],
+/// }
+/// ```
+///
+/// # Unions
+///
+/// Unions are declared as enums with one single-field tuple variant per discriminant value. The
+/// discriminant's type and offset are given by `#[discriminant(Type @ offset)]`. A `*Variant` enum
+/// is generated alongside the proxy type.
+///
+/// ```ignore
+/// #[layout(size = 0x10, align_bits = 2)]
+/// #[discriminant(MeshType @ 0)]
+/// pub enum Mesh {
+///     Simple(SimpleMesh) = MeshType::SIMPLE,
+///     Jfif(JfifMesh) = MeshType::JFIF,
+///     Clipped(ClippedMesh) = MeshType::CLIPPED,
+/// }
+/// ```
+///
+/// Either form accepts `#[is_end(path)]` to name a function that detects the end of an inline list
+/// of this type. Unions whose list ends at one variant, whatever its contents, also name that
+/// variant's discriminant with `#[end_discriminant(value)]`.
+#[proc_macro_attribute]
+pub fn layout(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(attr as args::LayoutArgs);
+    let input = parse_macro_input!(item as DeriveInput);
+    let result = match &input.data {
+        Data::Struct(_) => struct_::expand(args, input),
+        Data::Enum(_) => union::expand(args, input),
+        Data::Union(data) => Err(Error::new_spanned(
+            data.union_token,
+            "declare unions as an enum with one tuple variant per discriminant",
+        )),
+    };
+    result.unwrap_or_else(Error::into_compile_error).into()
+}
+
+/// Declares a newtype over a primitive integer with named values.
+///
+/// Unlike a Rust enum, the generated type can hold any value of the underlying type, so data with
+/// unknown values can still be read.
+///
+/// ```ignore
+/// #[primitive_enum(u8)]
+/// pub enum MeshType {
+///     SIMPLE = 0x00,
+///     JFIF = 0x01,
+///     CLIPPED = 0x02,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn primitive_enum(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let underlying = parse_macro_input!(attr as syn::Ident);
+    let input = parse_macro_input!(item as DeriveInput);
+    enum_::expand(underlying, input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Generates `FromVrom`, `Layout`, and a descriptor for a newtype over a primitive integer.
+///
+/// The descriptor describes the newtype as an enum. `names = path` names its values by a table of
+/// strings indexed by value, such as a table of actor names.
+///
+/// ```ignore
+/// #[newtype(names = ACTOR_NAMES)]
+/// #[derive(Clone, Copy, Eq, PartialEq)]
+/// pub struct ActorId(pub u16);
+/// ```
+#[proc_macro_attribute]
+pub fn newtype(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(attr as args::NewtypeArgs);
+    let input = parse_macro_input!(item as DeriveInput);
+    newtype::expand(args, input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
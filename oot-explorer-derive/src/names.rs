@@ -0,0 +1,84 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Error, Ident, Path, PathArguments, Result, Type};
+
+/// Converts a type name to the name of its descriptor constant, e.g. `SceneHeader` to
+/// `SCENE_HEADER_DESC`.
+pub fn desc_ident(ident: &Ident) -> Ident {
+    let mut name = String::new();
+    let mut prev = '_';
+    for ch in ident.to_string().chars() {
+        if ch.is_uppercase() && prev != '_' {
+            name.push('_');
+        }
+        name.extend(ch.to_uppercase());
+        prev = ch;
+    }
+    name.push_str("_DESC");
+    Ident::new(&name, ident.span())
+}
+
+/// Converts a path to a type into a path to its descriptor constant, preserving any leading
+/// module path.
+pub fn desc_path(type_: &Type) -> Result<Path> {
+    let mut path = type_path(type_)?.clone();
+    let last = path.segments.last_mut().unwrap();
+    last.ident = desc_ident(&last.ident);
+    last.arguments = PathArguments::None;
+    Ok(path)
+}
+
+/// Returns the path of a plain named type.
+pub fn type_path(type_: &Type) -> Result<&Path> {
+    match type_ {
+        Type::Path(type_path) if type_path.qself.is_none() => Ok(&type_path.path),
+        Type::Group(group) => type_path(&group.elem),
+        Type::Paren(paren) => type_path(&paren.elem),
+        _ => Err(Error::new_spanned(type_, "expected a named type")),
+    }
+}
+
+/// Maps a primitive integer type to its `PrimitiveType` variant.
+pub fn primitive_type(ident: &Ident) -> Result<TokenStream> {
+    let variant = match ident.to_string().as_str() {
+        "u8" => quote!(U8),
+        "i8" => quote!(I8),
+        "u16" => quote!(U16),
+        "i16" => quote!(I16),
+        "u32" => quote!(U32),
+        "i32" => quote!(I32),
+        _ => {
+            return Err(Error::new(
+                ident.span(),
+                "expected one of `u8`, `i8`, `u16`, `i16`, `u32`, `i32`",
+            ))
+        }
+    };
+    Ok(quote!(::oot_explorer_reflect::PrimitiveType::#variant))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proc_macro2::Span;
+
+    fn desc_name(name: &str) -> String {
+        desc_ident(&Ident::new(name, Span::call_site())).to_string()
+    }
+
+    #[test]
+    fn primitive_desc_names() {
+        assert_eq!(desc_name("u8"), "U8_DESC");
+        assert_eq!(desc_name("bool"), "BOOL_DESC");
+        assert_eq!(desc_name("f32"), "F32_DESC");
+        assert_eq!(desc_name("Qu10_2"), "QU10_2_DESC");
+        assert_eq!(desc_name("Vec3s"), "VEC3S_DESC");
+    }
+
+    #[test]
+    fn camel_case_desc_names() {
+        assert_eq!(desc_name("SceneHeader"), "SCENE_HEADER_DESC");
+        assert_eq!(desc_name("VromAddr"), "VROM_ADDR_DESC");
+        assert_eq!(desc_name("Lighting"), "LIGHTING_DESC");
+    }
+}
@@ -0,0 +1,71 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Fields, Result};
+
+use crate::args::NewtypeArgs;
+use crate::names::{desc_ident, primitive_type, type_path};
+
+pub fn expand(args: NewtypeArgs, input: DeriveInput) -> Result<TokenStream> {
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "interface types cannot be generic",
+        ));
+    }
+    let field = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed[0],
+            _ => {
+                return Err(Error::new(
+                    input.ident.span(),
+                    "newtypes must have exactly one unnamed field",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                input.ident.span(),
+                "`#[newtype]` only applies to structs",
+            ))
+        }
+    };
+    let underlying = type_path(&field.ty)?
+        .get_ident()
+        .ok_or_else(|| Error::new_spanned(&field.ty, "expected a primitive integer type"))?;
+    let primitive = primitive_type(underlying)?;
+
+    let name = &input.ident;
+    let name_string = name.to_string();
+    let vis = &input.vis;
+    let desc = desc_ident(name);
+    let values = match &args.names {
+        Some(names) => quote!(&::oot_explorer_reflect::indexed_names(#names)),
+        None => quote!(&[]),
+    };
+
+    Ok(quote! {
+        #vis const #desc: ::oot_explorer_reflect::TypeDescriptor =
+            ::oot_explorer_reflect::TypeDescriptor::Enum(&::oot_explorer_reflect::EnumDescriptor {
+                name: #name_string,
+                underlying: #primitive,
+                values: #values,
+            });
+
+        #input
+
+        impl ::oot_explorer_read::FromVrom for #name {
+            fn from_vrom(
+                vrom: ::oot_explorer_vrom::Vrom<'_>,
+                addr: ::oot_explorer_vrom::VromAddr,
+            ) -> ::std::result::Result<Self, ::oot_explorer_read::ReadError> {
+                ::oot_explorer_read::aligned_data::<Self>(vrom, addr)?;
+                Ok(Self(<#underlying as ::oot_explorer_read::FromVrom>::from_vrom(vrom, addr)?))
+            }
+        }
+
+        impl ::oot_explorer_read::Layout for #name {
+            const SIZE: u32 = <#underlying as ::oot_explorer_read::Layout>::SIZE;
+            const ALIGN_BITS: u32 = <#underlying as ::oot_explorer_read::Layout>::ALIGN_BITS;
+        }
+    })
+}
@@ -0,0 +1,108 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Fields, Result};
+
+use crate::args::{ItemAttrs, LayoutArgs};
+use crate::field::StructField;
+use crate::names::desc_ident;
+
+pub fn expand(args: LayoutArgs, input: DeriveInput) -> Result<TokenStream> {
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "interface types cannot be generic",
+        ));
+    }
+    let attrs = ItemAttrs::parse(input.attrs)?;
+    if let Some(discriminant) = &attrs.discriminant {
+        return Err(Error::new(
+            discriminant.type_.span(),
+            "`#[discriminant]` only applies to unions",
+        ));
+    }
+    if let Some(end_discriminant) = &attrs.end_discriminant {
+        return Err(Error::new_spanned(
+            end_discriminant,
+            "`#[end_discriminant]` only applies to unions",
+        ));
+    }
+
+    let fields = match input.data {
+        Data::Struct(data) => match data.fields {
+            Fields::Named(fields) => fields.named,
+            Fields::Unit => Default::default(),
+            Fields::Unnamed(fields) => {
+                return Err(Error::new_spanned(fields, "interface fields must be named"))
+            }
+        },
+        _ => unreachable!(),
+    };
+    let fields = fields
+        .into_iter()
+        .map(StructField::parse)
+        .collect::<Result<Vec<_>>>()?;
+
+    let name = &input.ident;
+    let name_string = name.to_string();
+    let vis = &input.vis;
+    let passthrough = &attrs.passthrough;
+    let desc = desc_ident(name);
+    let size = &args.size;
+    let align_bits = &args.align_bits;
+    let is_end = match &attrs.is_end {
+        Some(is_end) => quote!(Some(#is_end)),
+        None => quote!(None),
+    };
+    let field_descriptors = fields
+        .iter()
+        .map(StructField::descriptor)
+        .collect::<Result<Vec<_>>>()?;
+    let accessors = fields
+        .iter()
+        .map(StructField::accessor)
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(quote! {
+        #vis const #desc: ::oot_explorer_reflect::TypeDescriptor =
+            ::oot_explorer_reflect::TypeDescriptor::Struct(
+                &::oot_explorer_reflect::StructDescriptor {
+                    name: #name_string,
+                    size: Some(#size),
+                    is_end: #is_end,
+                    fields: &[#(#field_descriptors,)*],
+                },
+            );
+
+        #(#passthrough)*
+        #[derive(Clone, Copy)]
+        #vis struct #name {
+            addr: ::oot_explorer_vrom::VromAddr,
+        }
+
+        #[allow(dead_code)]
+        impl #name {
+            #(#accessors)*
+        }
+
+        impl ::oot_explorer_read::FromVrom for #name {
+            fn from_vrom(
+                vrom: ::oot_explorer_vrom::Vrom<'_>,
+                addr: ::oot_explorer_vrom::VromAddr,
+            ) -> ::std::result::Result<Self, ::oot_explorer_read::ReadError> {
+                ::oot_explorer_read::aligned_data::<Self>(vrom, addr)?;
+                Ok(Self { addr })
+            }
+        }
+
+        impl ::oot_explorer_read::VromProxy for #name {
+            fn addr(&self) -> ::oot_explorer_vrom::VromAddr {
+                self.addr
+            }
+        }
+
+        impl ::oot_explorer_read::Layout for #name {
+            const SIZE: u32 = #size;
+            const ALIGN_BITS: u32 = #align_bits;
+        }
+    })
+}
@@ -0,0 +1,157 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Error, Fields, Result};
+
+use crate::args::{ItemAttrs, LayoutArgs};
+use crate::field::read_simple_field;
+use crate::names::{desc_ident, desc_path};
+
+pub fn expand(args: LayoutArgs, input: DeriveInput) -> Result<TokenStream> {
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "interface types cannot be generic",
+        ));
+    }
+    let attrs = ItemAttrs::parse(input.attrs)?;
+    let span = input.ident.span();
+    let discriminant = attrs.discriminant.clone().ok_or_else(|| {
+        Error::new(
+            span,
+            "unions need a `#[discriminant(type @ offset)]` attribute",
+        )
+    })?;
+
+    let variants = match input.data {
+        Data::Enum(data) => data.variants,
+        _ => unreachable!(),
+    };
+    let mut variant_names = vec![];
+    let mut variant_types = vec![];
+    let mut variant_values = vec![];
+    let mut variant_attrs = vec![];
+    for variant in variants {
+        let type_ = match variant.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                fields.unnamed.into_iter().next().unwrap().ty
+            }
+            _ => {
+                return Err(Error::new_spanned(
+                    variant.ident,
+                    "union variants must have exactly one unnamed field",
+                ))
+            }
+        };
+        let span = variant.ident.span();
+        let (_, value) = variant.discriminant.ok_or_else(|| {
+            Error::new(
+                span,
+                "union variants need a discriminant value, e.g. `Variant(Type) = VALUE`",
+            )
+        })?;
+        variant_names.push(variant.ident);
+        variant_types.push(type_);
+        variant_values.push(value);
+        variant_attrs.push(variant.attrs);
+    }
+
+    let name = &input.ident;
+    let name_string = name.to_string();
+    let vis = &input.vis;
+    let passthrough = &attrs.passthrough;
+    let desc = desc_ident(name);
+    let variant_enum = format_ident!("{}Variant", name);
+    let size = &args.size;
+    let align_bits = &args.align_bits;
+    let is_end = match &attrs.is_end {
+        Some(is_end) => quote!(Some(#is_end)),
+        None => quote!(None),
+    };
+    let end_discriminant = match &attrs.end_discriminant {
+        Some(value) => quote!(Some(#value.to_u32())),
+        None => quote!(None),
+    };
+    let discriminant_type = &discriminant.type_;
+    let discriminant_offset = &discriminant.offset;
+    let discriminant_desc = desc_ident(discriminant_type);
+    let read_discriminant =
+        read_simple_field(&syn::parse_quote!(#discriminant_type), discriminant_offset);
+    let variant_descs = variant_types
+        .iter()
+        .map(desc_path)
+        .collect::<Result<Vec<_>>>()?;
+    let unexpected_discriminant = format!("unexpected union discriminant for {}: {{}}", name);
+
+    Ok(quote! {
+        #vis const #desc: ::oot_explorer_reflect::TypeDescriptor =
+            ::oot_explorer_reflect::TypeDescriptor::Union(
+                &::oot_explorer_reflect::UnionDescriptor {
+                    name: #name_string,
+                    size: Some(#size),
+                    is_end: #is_end,
+                    end_discriminant: #end_discriminant,
+                    discriminant_offset: #discriminant_offset,
+                    discriminant_desc: #discriminant_desc,
+                    variants: &[#((#variant_values.to_u32(), #variant_descs),)*],
+                    fallback: None,
+                },
+            );
+
+        #(#passthrough)*
+        #[derive(Clone, Copy)]
+        #vis struct #name {
+            addr: ::oot_explorer_vrom::VromAddr,
+        }
+
+        impl #name {
+            pub fn discriminant(self, vrom: ::oot_explorer_vrom::Vrom<'_>) -> #discriminant_type {
+                #read_discriminant
+            }
+
+            pub fn variant(self, vrom: ::oot_explorer_vrom::Vrom<'_>) -> #variant_enum {
+                match self.discriminant(vrom) {
+                    #(
+                        #variant_values => #variant_enum::#variant_names(
+                            // Unwrap because struct size and alignment have already been checked.
+                            <#variant_types as ::oot_explorer_read::FromVrom>::from_vrom(
+                                vrom,
+                                self.addr,
+                            )
+                            .unwrap(),
+                        ),
+                    )*
+                    discriminant => panic!(#unexpected_discriminant, discriminant.to_u32()),
+                }
+            }
+        }
+
+        impl ::oot_explorer_read::FromVrom for #name {
+            fn from_vrom(
+                vrom: ::oot_explorer_vrom::Vrom<'_>,
+                addr: ::oot_explorer_vrom::VromAddr,
+            ) -> ::std::result::Result<Self, ::oot_explorer_read::ReadError> {
+                ::oot_explorer_read::aligned_data::<Self>(vrom, addr)?;
+                Ok(Self { addr })
+            }
+        }
+
+        impl ::oot_explorer_read::VromProxy for #name {
+            fn addr(&self) -> ::oot_explorer_vrom::VromAddr {
+                self.addr
+            }
+        }
+
+        impl ::oot_explorer_read::Layout for #name {
+            const SIZE: u32 = #size;
+            const ALIGN_BITS: u32 = #align_bits;
+        }
+
+        #[derive(Clone, Copy)]
+        #vis enum #variant_enum {
+            #(
+                #(#variant_attrs)*
+                #variant_names(#variant_types),
+            )*
+        }
+    })
+}
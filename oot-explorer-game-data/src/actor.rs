@@ -0,0 +1,161 @@
+use std::ops::Range;
+
+use crate::object::{ObjectId, OBJECT_ID_DESC};
+use crate::versions::oot_ntsc_10::ACTOR_NAMES;
+use oot_explorer_derive::{layout, newtype, primitive_enum};
+use oot_explorer_reflect::{U32_DESC, VROM_ADDR_DESC};
+use oot_explorer_vrom::{Vrom, VromAddr};
+
+mod params;
+pub use params::{
+    actor_params_desc, describe_params, ActorParams, TransitionActorParams, ACTOR_PARAMS_DESC,
+    TRANSITION_ACTOR_PARAMS_DESC,
+};
+
+/// An index into the actor overlay table.
+#[newtype(names = ACTOR_NAMES)]
+#[derive(Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
+pub struct ActorId(pub u16);
+
+/// An entry in the actor overlay table, which says where an actor's code lives.
+#[layout(size = 0x20, align_bits = 2)]
+pub struct ActorOverlay {
+    #[at(0)]
+    vrom_start: VromAddr,
+    #[at(4)]
+    vrom_end: VromAddr,
+    #[at(8)]
+    vram_start: u32,
+    #[at(0xc)]
+    vram_end: u32,
+    /// Where the overlay is loaded at runtime. Always zero in ROM.
+    #[at(0x10)]
+    loaded_ram_addr: u32,
+    /// The VRAM address of the actor's [`ActorInit`], or zero for unused entries.
+    #[at(0x14)]
+    init_info: u32,
+    /// The VRAM address of the actor's name. Only debug builds fill this in.
+    #[at(0x18)]
+    name: u32,
+    #[at(0x1c)]
+    alloc_type: ActorAllocType,
+}
+
+impl ActorOverlay {
+    /// The VROM range of the actor's overlay file, or `None` if the actor's code is part of `code`
+    /// or the entry is unused.
+    pub fn overlay_range(self, vrom: Vrom<'_>) -> Option<Range<VromAddr>> {
+        let start = self.vrom_start(vrom);
+        if start == VromAddr(0) {
+            None
+        } else {
+            Some(start..self.vrom_end(vrom))
+        }
+    }
+
+    /// Maps a VRAM address in the actor's overlay to VROM. Returns `None` if the actor has no
+    /// overlay file or the address is outside it.
+    pub fn vram_to_vrom(self, vrom: Vrom<'_>, vram: u32) -> Option<VromAddr> {
+        let range = self.overlay_range(vrom)?;
+        let vram_start = self.vram_start(vrom);
+        if vram < vram_start || vram >= self.vram_end(vrom) {
+            return None;
+        }
+        let addr = range.start + (vram - vram_start);
+        if addr < range.end {
+            Some(addr)
+        } else {
+            None
+        }
+    }
+}
+
+/// The actor's `ActorInit`, which the game reads when spawning it.
+#[layout(size = 0x20, align_bits = 2)]
+pub struct ActorInit {
+    #[at(0)]
+    id: ActorId,
+    #[at(2)]
+    category: ActorCategory,
+    #[at(4)]
+    flags: u32,
+    /// The object that must be loaded before the actor can spawn.
+    #[at(8)]
+    object_id: ObjectId,
+    /// The size of the actor's instance struct, in bytes.
+    #[at(0xc)]
+    instance_size: u32,
+    #[at(0x10)]
+    init_fn: u32,
+    #[at(0x14)]
+    destroy_fn: u32,
+    #[at(0x18)]
+    update_fn: u32,
+    #[at(0x1c)]
+    draw_fn: u32,
+}
+
+/// How the game allocates memory for an actor's overlay. `NORMAL` overlays are loaded with the
+/// first instance and freed with the last, `ABSOLUTE` overlays share one fixed region, and
+/// `PERMANENT` overlays are never freed.
+#[primitive_enum(u16)]
+pub enum ActorAllocType {
+    NORMAL = 0,
+    ABSOLUTE = 1,
+    PERMANENT = 2,
+}
+
+/// The list that the game keeps an actor in, which decides when it updates and how it interacts.
+#[primitive_enum(u8)]
+pub enum ActorCategory {
+    SWITCH = 0,
+    BG = 1,
+    PLAYER = 2,
+    EXPLOSIVE = 3,
+    NPC = 4,
+    ENEMY = 5,
+    PROP = 6,
+    ITEMACTION = 7,
+    MISC = 8,
+    BOSS = 9,
+    DOOR = 10,
+    CHEST = 11,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oot_explorer_read::FromVrom;
+
+    #[test]
+    fn maps_overlay_addresses() {
+        let mut data = vec![0; 0x40];
+        let words: [u32; 8] = [
+            0x0000_0040,
+            0x0000_0100,
+            0x8080_0000,
+            0x8080_00c0,
+            0,
+            0x8080_0080,
+            0,
+            0x0002_0000,
+        ];
+        for (i, word) in words.iter().enumerate() {
+            data[i * 4..i * 4 + 4].copy_from_slice(&word.to_be_bytes());
+        }
+        let vrom = Vrom(&data);
+        let overlay = ActorOverlay::from_vrom(vrom, VromAddr(0)).unwrap();
+
+        assert_eq!(
+            overlay.overlay_range(vrom),
+            Some(VromAddr(0x40)..VromAddr(0x100)),
+        );
+        assert!(overlay.alloc_type(vrom) == ActorAllocType::PERMANENT);
+        assert_eq!(
+            overlay.vram_to_vrom(vrom, overlay.init_info(vrom)),
+            Some(VromAddr(0xc0)),
+        );
+        assert_eq!(overlay.vram_to_vrom(vrom, 0x8080_00c0), None);
+        assert_eq!(overlay.vram_to_vrom(vrom, 0x807f_fffc), None);
+    }
+}
@@ -0,0 +1,511 @@
+//! Decoders for actors' 16-bit init params, whose meaning depends on the actor.
+//!
+//! Each decoder is a [`BitfieldDescriptor`] over the params, so its spans carry the field names and
+//! any known values.
+
+use oot_explorer_read::{FromVrom, Layout, ReadError};
+use oot_explorer_reflect::{
+    BitfieldDescriptor, BitfieldSpan, EnumDescriptor, PrimitiveType, TypeDescriptor,
+    UnionDescriptor, U16_DESC,
+};
+use oot_explorer_vrom::{Vrom, VromAddr};
+
+use super::{ActorId, ACTOR_ID_DESC};
+
+/// An actor's init params, which are decoded according to the actor number stored before them.
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct ActorParams(pub u16);
+
+impl FromVrom for ActorParams {
+    fn from_vrom(vrom: Vrom<'_>, addr: VromAddr) -> Result<Self, ReadError> {
+        Ok(Self(<u16 as FromVrom>::from_vrom(vrom, addr)?))
+    }
+}
+
+impl Layout for ActorParams {
+    const SIZE: u32 = 2;
+}
+
+/// A transition actor's init params. Only the distance back to the actor number differs from
+/// [`ActorParams`].
+pub type TransitionActorParams = ActorParams;
+
+/// Params are a union over the actor number, with the raw value for actors without a decoder.
+macro_rules! params_union {
+    ($name:literal, $discriminant_offset:literal) => {
+        TypeDescriptor::Union(&UnionDescriptor {
+            name: $name,
+            size: Some(2),
+            is_end: None,
+            end_discriminant: None,
+            discriminant_offset: $discriminant_offset,
+            discriminant_desc: ACTOR_ID_DESC,
+            variants: ACTOR_PARAMS,
+            fallback: Some(U16_DESC),
+        })
+    };
+}
+
+/// The params at the end of an [`Actor`](crate::header_common::Actor).
+pub const ACTOR_PARAMS_DESC: TypeDescriptor = params_union!("ActorParams", -0xe);
+
+/// The params at the end of a [`TransitionActor`](crate::header_scene::TransitionActor).
+pub const TRANSITION_ACTOR_PARAMS_DESC: TypeDescriptor =
+    params_union!("TransitionActorParams", -0xa);
+
+/// Looks up the decoder for an actor's params, or returns `None` if the actor has none.
+pub fn actor_params_desc(actor_id: ActorId) -> Option<&'static BitfieldDescriptor> {
+    let index = ACTOR_PARAMS
+        .binary_search_by_key(&u32::from(actor_id.0), |&(id, _)| id)
+        .ok()?;
+    match ACTOR_PARAMS[index].1 {
+        TypeDescriptor::Bitfield(desc) => Some(desc),
+        _ => None,
+    }
+}
+
+/// Decodes an actor's params into named fields, like `"type=SMALL | contents=GI_MAP | flag=3"`.
+/// Returns `None` if the actor has no decoder.
+pub fn describe_params(actor_id: ActorId, params: u16) -> Option<String> {
+    let desc = actor_params_desc(actor_id)?;
+    let spans: Vec<String> = desc
+        .fields
+        .iter()
+        .map(|span| {
+            let value = (u32::from(params) >> span.shift) & span.mask;
+            match span.desc.values.binary_search_by_key(&value, |&(x, _)| x) {
+                Ok(index) => format!("{}={}", span.desc.name, span.desc.values[index].1),
+                Err(_) => format!("{}={}", span.desc.name, value),
+            }
+        })
+        .collect();
+    Some(spans.join(" | "))
+}
+
+/// Decoders by actor ID, sorted by ID.
+const ACTOR_PARAMS: &[(u32, TypeDescriptor)] = &[
+    (0x0009, TypeDescriptor::Bitfield(&EN_DOOR)),
+    (0x000a, TypeDescriptor::Bitfield(&EN_BOX)),
+    (0x0015, TypeDescriptor::Bitfield(&EN_ITEM00)),
+    (0x002e, TypeDescriptor::Bitfield(&DOOR_SHUTTER)),
+    (0x005d, TypeDescriptor::Bitfield(&DOOR_WARP1)),
+    (0x009b, TypeDescriptor::Bitfield(&DOOR_ANA)),
+    (0x0110, TypeDescriptor::Bitfield(&OBJ_KIBAKO)),
+    (0x0111, TypeDescriptor::Bitfield(&OBJ_TSUBO)),
+    (0x0125, TypeDescriptor::Bitfield(&EN_KUSA)),
+    (0x0127, TypeDescriptor::Bitfield(&OBJ_BOMBIWA)),
+    (0x012a, TypeDescriptor::Bitfield(&OBJ_SWITCH)),
+    (0x0141, TypeDescriptor::Bitfield(&EN_KANBAN)),
+    (0x014d, TypeDescriptor::Bitfield(&EN_OWL)),
+    (0x0163, TypeDescriptor::Bitfield(&EN_KO)),
+];
+
+/// Bitfield spans are named after their descriptors.
+macro_rules! span {
+    ($name:literal, $shift:literal, $mask:literal, $values:expr) => {
+        BitfieldSpan {
+            shift: $shift,
+            mask: $mask,
+            desc: &EnumDescriptor {
+                name: $name,
+                underlying: PrimitiveType::U16,
+                values: $values,
+            },
+        }
+    };
+}
+
+macro_rules! params {
+    ($name:literal, [$($span:expr),* $(,)?]) => {
+        BitfieldDescriptor {
+            name: $name,
+            underlying: PrimitiveType::U16,
+            fields: &[$($span),*],
+        }
+    };
+}
+
+const EN_DOOR: BitfieldDescriptor = params!(
+    "En_Door",
+    [
+        span!("type", 7, 0x7, DOOR_TYPES),
+        span!("switch_flag", 0, 0x3f, &[]),
+    ]
+);
+
+const EN_BOX: BitfieldDescriptor = params!(
+    "En_Box",
+    [
+        span!("type", 12, 0xf, CHEST_TYPES),
+        span!("contents", 5, 0x7f, GET_ITEM_IDS),
+        span!("treasure_flag", 0, 0x1f, &[]),
+    ]
+);
+
+const EN_ITEM00: BitfieldDescriptor = params!(
+    "En_Item00",
+    [
+        span!("collectible_flag", 8, 0x3f, &[]),
+        span!("type", 0, 0xff, ITEM00_TYPES),
+    ]
+);
+
+const DOOR_SHUTTER: BitfieldDescriptor = params!(
+    "Door_Shutter",
+    [
+        span!("type", 6, 0xf, SHUTTER_TYPES),
+        span!("switch_flag", 0, 0x3f, &[]),
+    ]
+);
+
+const DOOR_WARP1: BitfieldDescriptor =
+    params!("Door_Warp1", [span!("type", 0, 0xffff, WARP_TYPES)]);
+
+const DOOR_ANA: BitfieldDescriptor = params!(
+    "Door_Ana",
+    [
+        span!("type", 8, 0x3, GROTTO_TYPES),
+        span!("grotto", 0, 0xff, &[]),
+    ]
+);
+
+const OBJ_KIBAKO: BitfieldDescriptor = params!(
+    "Obj_Kibako",
+    [
+        span!("collectible_flag", 8, 0x3f, &[]),
+        span!("drop", 0, 0x1f, ITEM00_TYPES),
+    ]
+);
+
+const OBJ_TSUBO: BitfieldDescriptor = params!(
+    "Obj_Tsubo",
+    [
+        span!("collectible_flag", 9, 0x3f, &[]),
+        span!("field_object", 8, 0x1, &[]),
+        span!("drop", 0, 0x1f, ITEM00_TYPES),
+    ]
+);
+
+const EN_KUSA: BitfieldDescriptor = params!(
+    "En_Kusa",
+    [
+        span!("drop_table", 8, 0xf, &[]),
+        span!("bugs", 4, 0x1, &[]),
+        span!("type", 0, 0x3, &[]),
+    ]
+);
+
+const OBJ_BOMBIWA: BitfieldDescriptor =
+    params!("Obj_Bombiwa", [span!("switch_flag", 0, 0x3f, &[])]);
+
+const OBJ_SWITCH: BitfieldDescriptor = params!(
+    "Obj_Switch",
+    [
+        span!("switch_flag", 8, 0x3f, &[]),
+        span!("frozen", 7, 0x1, &[]),
+        span!("subtype", 4, 0x7, &[]),
+        span!("type", 0, 0x7, SWITCH_TYPES),
+    ]
+);
+
+const EN_KANBAN: BitfieldDescriptor = params!("En_Kanban", [span!("message", 0, 0xff, &[])]);
+
+const EN_OWL: BitfieldDescriptor = params!(
+    "En_Owl",
+    [
+        span!("type", 6, 0x3f, &[]),
+        span!("switch_flag", 0, 0x3f, &[]),
+    ]
+);
+
+const EN_KO: BitfieldDescriptor = params!("En_Ko", [span!("type", 0, 0xff, &[])]);
+
+const DOOR_TYPES: &[(u32, &str)] = &[
+    (0, "ROOM_LOAD"),
+    (1, "LOCKED"),
+    (2, "ROOM_LOAD_2"),
+    (3, "CHECKABLE"),
+    (4, "AJAR"),
+    (5, "EVENING"),
+    (6, "ROOM_LOAD_6"),
+    (7, "ROOM_LOAD_7"),
+];
+
+const CHEST_TYPES: &[(u32, &str)] = &[
+    (0, "BIG"),
+    (1, "BIG_ROOM_CLEAR"),
+    (5, "SMALL"),
+    (7, "SMALL_ROOM_CLEAR"),
+    (11, "BIG_SWITCH_FLAG"),
+];
+
+const SHUTTER_TYPES: &[(u32, &str)] = &[
+    (0, "SHUTTER"),
+    (1, "FRONT_CLEAR"),
+    (2, "FRONT_SWITCH"),
+    (3, "BACK_LOCKED"),
+    (4, "PG_BARS"),
+    (5, "BOSS"),
+    (6, "GOHMA_BLOCK"),
+    (7, "FRONT_SWITCH_BACK_CLEAR"),
+    (11, "KEY_LOCKED"),
+];
+
+const WARP_TYPES: &[(u32, &str)] = &[
+    (0, "DUNGEON_CHILD"),
+    (1, "DUNGEON_ADULT"),
+    (2, "CLEAR_FLAG"),
+    (3, "SAGES"),
+    (4, "PURPLE_CRYSTAL"),
+    (5, "YELLOW"),
+    (6, "BLUE_RUTO"),
+    (7, "DESTINATION"),
+    (0xffff, "BLUE_CRYSTAL"),
+];
+
+const GROTTO_TYPES: &[(u32, &str)] = &[(0, "VISIBLE"), (1, "HIDDEN_STORMS"), (2, "HIDDEN_BOMB")];
+
+const SWITCH_TYPES: &[(u32, &str)] = &[
+    (0, "FLOOR"),
+    (1, "FLOOR_RUSTY"),
+    (2, "EYE"),
+    (3, "CRYSTAL"),
+    (4, "CRYSTAL_TARGETABLE"),
+];
+
+/// Collectibles that `En_Item00` spawns as, and that pots and crates drop.
+const ITEM00_TYPES: &[(u32, &str)] = &[
+    (0x00, "RUPEE_GREEN"),
+    (0x01, "RUPEE_BLUE"),
+    (0x02, "RUPEE_RED"),
+    (0x03, "RECOVERY_HEART"),
+    (0x04, "BOMBS_A"),
+    (0x05, "ARROWS_SINGLE"),
+    (0x06, "HEART_PIECE"),
+    (0x07, "HEART_CONTAINER"),
+    (0x08, "ARROWS_SMALL"),
+    (0x09, "ARROWS_MEDIUM"),
+    (0x0a, "ARROWS_LARGE"),
+    (0x0b, "BOMBS_B"),
+    (0x0c, "NUTS"),
+    (0x0d, "STICK"),
+    (0x0e, "MAGIC_LARGE"),
+    (0x0f, "MAGIC_SMALL"),
+    (0x10, "SEEDS"),
+    (0x11, "SMALL_KEY"),
+    (0x12, "FLEXIBLE"),
+    (0x13, "RUPEE_ORANGE"),
+    (0x14, "RUPEE_PURPLE"),
+    (0x15, "SHIELD_DEKU"),
+    (0x16, "SHIELD_HYLIAN"),
+    (0x17, "TUNIC_ZORA"),
+    (0x18, "TUNIC_GORON"),
+    (0x19, "BOMBS_SPECIAL"),
+];
+
+/// Items that chests give.
+const GET_ITEM_IDS: &[(u32, &str)] = &[
+    (0x00, "GI_NONE"),
+    (0x01, "GI_BOMBS_5"),
+    (0x02, "GI_NUTS_5"),
+    (0x03, "GI_BOMBCHUS_10"),
+    (0x04, "GI_BOW"),
+    (0x05, "GI_SLINGSHOT"),
+    (0x06, "GI_BOOMERANG"),
+    (0x07, "GI_STICKS_1"),
+    (0x08, "GI_HOOKSHOT"),
+    (0x09, "GI_LONGSHOT"),
+    (0x0a, "GI_LENS"),
+    (0x0b, "GI_LETTER_ZELDA"),
+    (0x0c, "GI_OCARINA_OOT"),
+    (0x0d, "GI_HAMMER"),
+    (0x0e, "GI_COJIRO"),
+    (0x0f, "GI_BOTTLE"),
+    (0x10, "GI_POTION_RED"),
+    (0x11, "GI_POTION_GREEN"),
+    (0x12, "GI_POTION_BLUE"),
+    (0x13, "GI_FAIRY"),
+    (0x14, "GI_MILK_BOTTLE"),
+    (0x15, "GI_LETTER_RUTO"),
+    (0x16, "GI_BEAN"),
+    (0x17, "GI_MASK_SKULL"),
+    (0x18, "GI_MASK_SPOOKY"),
+    (0x19, "GI_CHICKEN"),
+    (0x1a, "GI_MASK_KEATON"),
+    (0x1b, "GI_MASK_BUNNY"),
+    (0x1c, "GI_MASK_TRUTH"),
+    (0x1d, "GI_POCKET_EGG"),
+    (0x1e, "GI_POCKET_CHICKEN"),
+    (0x1f, "GI_ODD_MUSHROOM"),
+    (0x20, "GI_ODD_POTION"),
+    (0x21, "GI_SAW"),
+    (0x22, "GI_SWORD_BROKEN"),
+    (0x23, "GI_PRESCRIPTION"),
+    (0x24, "GI_FROG"),
+    (0x25, "GI_EYEDROPS"),
+    (0x26, "GI_CLAIM_CHECK"),
+    (0x27, "GI_SWORD_KOKIRI"),
+    (0x28, "GI_SWORD_KNIFE"),
+    (0x29, "GI_SHIELD_DEKU"),
+    (0x2a, "GI_SHIELD_HYLIAN"),
+    (0x2b, "GI_SHIELD_MIRROR"),
+    (0x2c, "GI_TUNIC_GORON"),
+    (0x2d, "GI_TUNIC_ZORA"),
+    (0x2e, "GI_BOOTS_IRON"),
+    (0x2f, "GI_BOOTS_HOVER"),
+    (0x30, "GI_QUIVER_40"),
+    (0x31, "GI_QUIVER_50"),
+    (0x32, "GI_BOMB_BAG_20"),
+    (0x33, "GI_BOMB_BAG_30"),
+    (0x34, "GI_BOMB_BAG_40"),
+    (0x35, "GI_GAUNTLETS_SILVER"),
+    (0x36, "GI_GAUNTLETS_GOLD"),
+    (0x37, "GI_SCALE_SILVER"),
+    (0x38, "GI_SCALE_GOLD"),
+    (0x39, "GI_STONE_OF_AGONY"),
+    (0x3a, "GI_GERUDO_CARD"),
+    (0x3b, "GI_OCARINA_FAIRY"),
+    (0x3c, "GI_SEEDS_5"),
+    (0x3d, "GI_HEART_CONTAINER"),
+    (0x3e, "GI_HEART_PIECE"),
+    (0x3f, "GI_KEY_BOSS"),
+    (0x40, "GI_COMPASS"),
+    (0x41, "GI_MAP"),
+    (0x42, "GI_KEY_SMALL"),
+    (0x43, "GI_MAGIC_SMALL"),
+    (0x44, "GI_MAGIC_LARGE"),
+    (0x45, "GI_WALLET_ADULT"),
+    (0x46, "GI_WALLET_GIANT"),
+    (0x47, "GI_WEIRD_EGG"),
+    (0x48, "GI_HEART"),
+    (0x49, "GI_ARROWS_SMALL"),
+    (0x4a, "GI_ARROWS_MEDIUM"),
+    (0x4b, "GI_ARROWS_LARGE"),
+    (0x4c, "GI_RUPEE_GREEN"),
+    (0x4d, "GI_RUPEE_BLUE"),
+    (0x4e, "GI_RUPEE_RED"),
+    (0x4f, "GI_HEART_CONTAINER_2"),
+    (0x50, "GI_MILK"),
+    (0x51, "GI_MASK_GORON"),
+    (0x52, "GI_MASK_ZORA"),
+    (0x53, "GI_MASK_GERUDO"),
+    (0x54, "GI_BRACELET"),
+    (0x55, "GI_RUPEE_PURPLE"),
+    (0x56, "GI_RUPEE_GOLD"),
+    (0x57, "GI_SWORD_BGS"),
+    (0x58, "GI_ARROW_FIRE"),
+    (0x59, "GI_ARROW_ICE"),
+    (0x5a, "GI_ARROW_LIGHT"),
+    (0x5b, "GI_SKULL_TOKEN"),
+    (0x5c, "GI_DINS_FIRE"),
+    (0x5d, "GI_FARORES_WIND"),
+    (0x5e, "GI_NAYRUS_LOVE"),
+    (0x5f, "GI_BULLET_BAG_30"),
+    (0x60, "GI_BULLET_BAG_40"),
+    (0x61, "GI_STICKS_5"),
+    (0x62, "GI_STICKS_10"),
+    (0x63, "GI_NUTS_5_2"),
+    (0x64, "GI_NUTS_10"),
+    (0x65, "GI_BOMBS_1"),
+    (0x66, "GI_BOMBS_10"),
+    (0x67, "GI_BOMBS_20"),
+    (0x68, "GI_BOMBS_30"),
+    (0x69, "GI_SEEDS_30"),
+    (0x6a, "GI_BOMBCHUS_5"),
+    (0x6b, "GI_BOMBCHUS_20"),
+    (0x6c, "GI_FISH"),
+    (0x6d, "GI_BUGS"),
+    (0x6e, "GI_BLUE_FIRE"),
+    (0x6f, "GI_POE"),
+    (0x70, "GI_BIG_POE"),
+    (0x71, "GI_DOOR_KEY"),
+    (0x72, "GI_RUPEE_GREEN_LOSE"),
+    (0x73, "GI_RUPEE_BLUE_LOSE"),
+    (0x74, "GI_RUPEE_RED_LOSE"),
+    (0x75, "GI_RUPEE_PURPLE_LOSE"),
+    (0x76, "GI_HEART_PIECE_WIN"),
+    (0x77, "GI_STICK_UPGRADE_20"),
+    (0x78, "GI_STICK_UPGRADE_30"),
+    (0x79, "GI_NUT_UPGRADE_30"),
+    (0x7a, "GI_NUT_UPGRADE_40"),
+    (0x7b, "GI_BULLET_BAG_50"),
+    (0x7c, "GI_ICE_TRAP"),
+    (0x7d, "GI_TEXT_0"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::header_common::ACTOR_DESC;
+    use crate::header_scene::TRANSITION_ACTOR_DESC;
+    use oot_explorer_reflect::{Value, ValueKind};
+    use oot_explorer_segment::SegmentTable;
+
+    type Spans = Vec<(u32, Option<&'static str>)>;
+
+    /// Reads the params of an actor as their type name and bitfield spans, or `None` for spans if
+    /// they aren't a bitfield.
+    fn params_spans(data: &[u8], desc: TypeDescriptor) -> (String, Option<Spans>) {
+        let value = Value::read(Vrom(data), &SegmentTable::new(), desc, VromAddr(0));
+        let variant = match &value.field("init").unwrap().kind {
+            ValueKind::Union {
+                variant: Some(variant),
+                ..
+            } => variant,
+            _ => panic!("init should be a union with a variant"),
+        };
+        let spans = match &variant.kind {
+            ValueKind::Bitfield { spans, .. } => {
+                Some(spans.iter().map(|span| (span.value, span.name)).collect())
+            }
+            _ => None,
+        };
+        (variant.type_name.clone(), spans)
+    }
+
+    #[test]
+    fn decodes_params() {
+        assert!(ACTOR_PARAMS.windows(2).all(|pair| pair[0].0 < pair[1].0));
+
+        assert_eq!(
+            describe_params(ActorId(0x000a), 0x5823).as_deref(),
+            Some("type=SMALL | contents=GI_MAP | treasure_flag=3"),
+        );
+        assert_eq!(
+            describe_params(ActorId(0x0111), 0x0503).as_deref(),
+            Some("collectible_flag=2 | field_object=1 | drop=RECOVERY_HEART"),
+        );
+        assert_eq!(describe_params(ActorId(0x0000), 0), None);
+    }
+
+    #[test]
+    fn reflects_params_per_actor() {
+        let mut actor = [0; 0x10];
+        actor[0..2].copy_from_slice(&[0x00, 0x0a]);
+        actor[0xe..].copy_from_slice(&[0x58, 0x23]);
+        assert_eq!(
+            params_spans(&actor, ACTOR_DESC),
+            (
+                "En_Box".to_string(),
+                Some(vec![(5, Some("SMALL")), (0x41, Some("GI_MAP")), (3, None)]),
+            ),
+        );
+
+        let mut transition_actor = [0; 0x10];
+        transition_actor[4..6].copy_from_slice(&[0x01, 0x11]);
+        transition_actor[0xe..].copy_from_slice(&[0x05, 0x03]);
+        assert_eq!(
+            params_spans(&transition_actor, TRANSITION_ACTOR_DESC),
+            (
+                "Obj_Tsubo".to_string(),
+                Some(vec![(2, None), (1, None), (3, Some("RECOVERY_HEART"))]),
+            ),
+        );
+
+        // Actors without a decoder fall back to the raw value.
+        assert_eq!(
+            params_spans(&[0; 0x10], ACTOR_DESC),
+            ("u16".to_string(), None),
+        );
+    }
+}
@@ -1,99 +1,204 @@
-use oot_explorer_read::{ReadError, Slice};
-use oot_explorer_reflect::{I16_DESC, SEGMENT_ADDR_DESC, U16_DESC, U32_DESC};
-use oot_explorer_segment::{SegmentAddr, SegmentError, SegmentTable};
-use oot_explorer_vrom::Vrom;
-
-declare_pointer_descriptor!(Collision);
-
-compile_interfaces! {
-    #[layout(size = 0x2c, align_bits = 2)]
-    struct Collision {
-        i16 x_min @0x00;
-        i16 y_min @0x02;
-        i16 z_min @0x04;
-        i16 x_max @0x06;
-        i16 y_max @0x08;
-        i16 z_max @0x0a;
-        struct Vertex[u16 @0x0c]* vertices @0x10;
-        struct Triangle[u16 @0x14]* triangles @0x18;
-        // TODO: Type as TriangleType[?]*
-        SegmentAddr triangle_types_ptr @0x1c;
-        // TODO: Type as ...
-        SegmentAddr camera_data_ptr @0x20;
-        struct WaterBox[u16 @0x24]* water_boxes @0x28;
-    }
-
-    #[layout(size = 6, align_bits = 1)]
-    struct Vertex {
-        i16 x @0;
-        i16 y @2;
-        i16 z @4;
-    }
-
-    #[layout(size = 0x10, align_bits = 1)]
-    struct Triangle {
-        u16 type_ @0x0;
-        u16 vertex_a_and_flags @0x2;
-        u16 vertex_b_and_flags @0x4;
-        u16 vertex_c_and_flags @0x6;
-        i16 plane_a @0x8;
-        i16 plane_b @0xa;
-        i16 plane_c @0xc;
-        i16 plane_d @0xe;
-    }
-
-    #[layout(size = 8, align_bits = 2)]
-    struct TriangleType {
-        u32 high_value @0;
-        u32 low_value @4;
-    }
-
-    #[layout(size = 8, align_bits = 2)]
-    struct CameraData {
-        u16 s_type @0;
-        u16 camera_count @2;
-        SegmentAddr camera_ptr @4;
-    }
-
-    #[layout(size = 0x10, align_bits = 2)]
-    struct WaterBox {
-        i16 x_min @0x0;
-        i16 y_surface @0x2;
-        i16 z_min @0x4;
-        i16 x_span @0x6;
-        i16 z_span @0x8;
-        u32 flags @0xc;
-    }
+use crate::header_scene::{current_scene, SceneHeaderVariant};
+use oot_explorer_derive::{layout, primitive_enum};
+use oot_explorer_read::{FromVrom, InferLen, Layout, MaxIndex, ReadError, Slice, Vec3s};
+use oot_explorer_reflect::{
+    BitfieldDescriptor, BitfieldSpan, EnumDescriptor, PrimitiveType, TypeDescriptor, I16_DESC,
+    U16_DESC, U32_DESC, VEC3S_DESC,
+};
+use oot_explorer_segment::SegmentTable;
+use oot_explorer_vrom::{Vrom, VromAddr};
+
+mod model;
+
+pub use model::{
+    CollisionModel, FloorHit, ModelTriangle, ModelWaterBox, RayHit, TriangleKind, WallHit,
+};
+
+#[layout(size = 0x2c, align_bits = 2)]
+pub struct Collision {
+    #[at(0x00)]
+    x_min: i16,
+    #[at(0x02)]
+    y_min: i16,
+    #[at(0x04)]
+    z_min: i16,
+    #[at(0x06)]
+    x_max: i16,
+    #[at(0x08)]
+    y_max: i16,
+    #[at(0x0a)]
+    z_max: i16,
+    #[at(0x10)]
+    #[count(u16 @ 0x0c)]
+    vertices: *const [Vertex],
+    #[at(0x18)]
+    #[count(u16 @ 0x14)]
+    triangles: *const [Triangle],
+    /// The triangle type count isn't stored. See [`Collision::infer_triangle_type_count`].
+    #[at(0x1c)]
+    #[infer_len(infer_scene_triangle_type_count)]
+    triangle_types: *const [TriangleType],
+    /// The camera data count isn't stored. See [`Collision::infer_camera_data_count`].
+    #[at(0x20)]
+    #[infer_len(infer_scene_camera_data_count)]
+    camera_data: Option<*const [CameraData]>,
+    #[at(0x28)]
+    #[count(u16 @ 0x24)]
+    water_boxes: Option<*const [WaterBox]>,
+}
+
+#[layout(size = 6, align_bits = 1)]
+pub struct Vertex {
+    #[at(0)]
+    x: i16,
+    #[at(2)]
+    y: i16,
+    #[at(4)]
+    z: i16,
+}
+
+#[layout(size = 0x10, align_bits = 1)]
+pub struct Triangle {
+    #[at(0x0)]
+    type_: u16,
+    #[at(0x2)]
+    vertex_a_and_flags: u16,
+    #[at(0x4)]
+    vertex_b_and_flags: u16,
+    #[at(0x6)]
+    vertex_c_and_flags: u16,
+    #[at(0x8)]
+    plane_a: i16,
+    #[at(0xa)]
+    plane_b: i16,
+    #[at(0xc)]
+    plane_c: i16,
+    #[at(0xe)]
+    plane_d: i16,
+}
+
+/// How a triangle behaves when actors touch it, in two words of bitfields.
+#[layout(size = 8, align_bits = 2)]
+pub struct TriangleType {
+    #[at(0)]
+    high_value: TriangleTypeHigh,
+    #[at(4)]
+    low_value: TriangleTypeLow,
+}
+
+/// A camera setting, along with the data it uses. For most settings, the data is a position, a
+/// rotation, and a field of view with flags, for each camera.
+#[layout(size = 8, align_bits = 2)]
+pub struct CameraData {
+    #[at(0)]
+    setting: CameraSetting,
+    #[at(2)]
+    position_count: u16,
+    #[at(4)]
+    #[count(u16 @ 2)]
+    positions: Option<*const [Vec3s]>,
+}
+
+#[primitive_enum(u16)]
+pub enum CameraSetting {
+    NONE = 0x00,
+    NORMAL0 = 0x01,
+    NORMAL1 = 0x02,
+    DUNGEON0 = 0x03,
+    DUNGEON1 = 0x04,
+    NORMAL3 = 0x05,
+    HORSE = 0x06,
+    BOSS_GOHMA = 0x07,
+    BOSS_DODONGO = 0x08,
+    BOSS_BARINADE = 0x09,
+    BOSS_PHANTOM_GANON = 0x0a,
+    BOSS_VOLVAGIA = 0x0b,
+    BOSS_BONGO = 0x0c,
+    BOSS_MORPHA = 0x0d,
+    BOSS_TWINROVA_PLATFORM = 0x0e,
+    BOSS_TWINROVA_FLOOR = 0x0f,
+    BOSS_GANONDORF = 0x10,
+    BOSS_GANON = 0x11,
+    TOWER_CLIMB = 0x12,
+    TOWER_UNUSED = 0x13,
+    MARKET_BALCONY = 0x14,
+    CHU_BOWLING = 0x15,
+    PIVOT_CRAWLSPACE = 0x16,
+    PIVOT_SHOP_BROWSING = 0x17,
+    PIVOT_IN_FRONT = 0x18,
+    PREREND_FIXED = 0x19,
+    PREREND_PIVOT = 0x1a,
+    PREREND_SIDE_SCROLL = 0x1b,
+    DOOR0 = 0x1c,
+    DOORC = 0x1d,
+    CRAWLSPACE = 0x1e,
+    START0 = 0x1f,
+    START1 = 0x20,
+    FREE0 = 0x21,
+    FREE2 = 0x22,
+    PIVOT_CORNER = 0x23,
+    PIVOT_WATER_SURFACE = 0x24,
+    CS_0 = 0x25,
+    CS_TWISTED_HALLWAY = 0x26,
+    FOREST_BIRDS_EYE = 0x27,
+    SLOW_CHEST_CS = 0x28,
+    ITEM_UNUSED = 0x29,
+    CS_3 = 0x2a,
+    CS_ATTENTION = 0x2b,
+    BEAN_GENERIC = 0x2c,
+    BEAN_LOST_WOODS = 0x2d,
+    SCENE_UNUSED = 0x2e,
+    SCENE_TRANSITION = 0x2f,
+    ELEVATOR_PLATFORM = 0x30,
+    FIRE_STAIRCASE = 0x31,
+    FOREST_UNUSED = 0x32,
+    FOREST_DEFEAT_POE = 0x33,
+    BIG_OCTO = 0x34,
+    MEADOW_BIRDS_EYE = 0x35,
+    MEADOW_UNUSED = 0x36,
+    FIRE_BIRDS_EYE = 0x37,
+    TURN_AROUND = 0x38,
+    PIVOT_VERTICAL = 0x39,
+    NORMAL2 = 0x3a,
+    FISHING = 0x3b,
+    CS_C = 0x3c,
+    JABU_TENTACLE = 0x3d,
+    DUNGEON2 = 0x3e,
+    DIRECTED_YAW = 0x3f,
+    PIVOT_FROM_SIDE = 0x40,
+    NORMAL4 = 0x41,
+}
+
+#[layout(size = 0x10, align_bits = 2)]
+pub struct WaterBox {
+    #[at(0x0)]
+    x_min: i16,
+    #[at(0x2)]
+    y_surface: i16,
+    #[at(0x4)]
+    z_min: i16,
+    #[at(0x6)]
+    x_span: i16,
+    #[at(0x8)]
+    z_span: i16,
+    #[at(0xc)]
+    flags: u32,
 }
 
 impl Collision {
+    /// Infers the length of the triangle type list from the largest index referenced by a
+    /// triangle. The list always has at least one entry, even if there are no triangles.
     pub fn infer_triangle_type_count(
         self,
         vrom: Vrom<'_>,
         segment_table: &SegmentTable,
-    ) -> Result<u32, ReadError> {
-        let accumulate_max_triangle_type =
-            |acc: u16, triangle: Result<Triangle, ReadError>| -> Result<u16, ReadError> {
-                Ok(acc.max(triangle?.type_(vrom)))
-            };
-
-        Ok(self
+    ) -> Result<MaxIndex, ReadError> {
+        let MaxIndex(max_index) = self
             .triangles(vrom, segment_table)?
             .iter(vrom)
-            .try_fold(0, accumulate_max_triangle_type)? as u32
-            + 1)
-    }
-
-    pub fn triangle_types_with_len(
-        self,
-        vrom: Vrom<'_>,
-        segment_table: &SegmentTable,
-        len: u32,
-    ) -> Result<Slice<TriangleType>, SegmentError> {
-        Ok(Slice::new(
-            segment_table.resolve(self.triangle_types_ptr(vrom))?,
-            len,
-        ))
+            .map(|triangle| Ok(triangle?.type_(vrom) as u32))
+            .collect::<Result<_, ReadError>>()?;
+        Ok(MaxIndex(Some(max_index.unwrap_or(0))))
     }
 
     pub fn triangle_types_auto_len(
@@ -101,24 +206,92 @@ impl Collision {
         vrom: Vrom<'_>,
         segment_table: &SegmentTable,
     ) -> Result<Slice<TriangleType>, ReadError> {
-        Ok(self.triangle_types_with_len(
+        Ok(self.triangle_types(
             vrom,
             segment_table,
             self.infer_triangle_type_count(vrom, segment_table)?,
         )?)
     }
 
-    pub fn camera_data_with_len(
+    /// Infers the length of the camera data list from the largest index referenced by a triangle
+    /// type or water box.
+    pub fn infer_camera_data_count(
         self,
         vrom: Vrom<'_>,
         segment_table: &SegmentTable,
-        len: u32,
-    ) -> Result<Slice<CameraData>, SegmentError> {
-        Ok(Slice::new(
-            segment_table.resolve(self.camera_data_ptr(vrom))?,
-            len,
-        ))
+    ) -> Result<MaxIndex, ReadError> {
+        let from_triangle_types = self
+            .triangle_types_auto_len(vrom, segment_table)?
+            .iter(vrom)
+            .map(|triangle_type| Ok(triangle_type?.camera_data_index(vrom) as u32));
+        let from_water_boxes = self
+            .water_boxes(vrom, segment_table)?
+            .into_iter()
+            .flat_map(|water_boxes| water_boxes.iter(vrom))
+            .map(|water_box| Ok(water_box?.camera_data_index(vrom) as u32));
+        from_triangle_types.chain(from_water_boxes).collect()
     }
+
+    pub fn camera_data_auto_len(
+        self,
+        vrom: Vrom<'_>,
+        segment_table: &SegmentTable,
+    ) -> Result<Option<Slice<CameraData>>, ReadError> {
+        Ok(self.camera_data(
+            vrom,
+            segment_table,
+            self.infer_camera_data_count(vrom, segment_table)?,
+        )?)
+    }
+}
+
+/// Infers the length of the triangle type list at `addr`, if it belongs to the collision of the
+/// scene that `segment_table` maps. Otherwise, the list is empty.
+pub fn infer_scene_triangle_type_count(
+    vrom: Vrom<'_>,
+    segment_table: &SegmentTable,
+    addr: VromAddr,
+) -> u32 {
+    scene_collision(vrom, segment_table)
+        .filter(|collision| {
+            collision
+                .triangle_types(vrom, segment_table, 0)
+                .is_ok_and(|triangle_types| triangle_types.addr() == addr)
+        })
+        .and_then(|collision| {
+            collision
+                .infer_triangle_type_count(vrom, segment_table)
+                .ok()
+        })
+        .map_or(0, |count| count.infer_len(addr, TriangleType::SIZE))
+}
+
+/// Infers the length of the camera data list at `addr`, if it belongs to the collision of the
+/// scene that `segment_table` maps. Otherwise, the list is empty.
+pub fn infer_scene_camera_data_count(
+    vrom: Vrom<'_>,
+    segment_table: &SegmentTable,
+    addr: VromAddr,
+) -> u32 {
+    scene_collision(vrom, segment_table)
+        .filter(|collision| {
+            matches!(
+                collision.camera_data(vrom, segment_table, 0),
+                Ok(Some(camera_data)) if camera_data.addr() == addr
+            )
+        })
+        .and_then(|collision| collision.infer_camera_data_count(vrom, segment_table).ok())
+        .map_or(0, |count| count.infer_len(addr, CameraData::SIZE))
+}
+
+/// Finds the collision of the scene that `segment_table` maps to the scene segment.
+fn scene_collision(vrom: Vrom<'_>, segment_table: &SegmentTable) -> Option<Collision> {
+    current_scene(vrom, segment_table)?
+        .headers(vrom)
+        .find_map(|header| match header.ok()?.variant(vrom) {
+            SceneHeaderVariant::Collision(header) => header.ptr(vrom, segment_table).ok(),
+            _ => None,
+        })
 }
 
 impl Triangle {
@@ -142,3 +315,296 @@ impl Triangle {
         self.vertex_c_and_flags(vrom) & 0x1fff
     }
 }
+
+impl TriangleType {
+    pub fn camera_data_index(self, vrom: Vrom<'_>) -> u8 {
+        self.high_value(vrom).camera_data_index()
+    }
+}
+
+// TODO: Codegen for bitfields.
+
+/// The first word of a [`TriangleType`].
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct TriangleTypeHigh(pub u32);
+
+impl TriangleTypeHigh {
+    /// Whether Epona can't cross the triangle.
+    pub fn horse_blocked(self) -> bool {
+        self.0 & 0x8000_0000 != 0
+    }
+
+    /// A special behavior of floors, such as voiding out on contact.
+    pub fn floor_property(self) -> u8 {
+        ((self.0 >> 26) & 0xf) as u8
+    }
+
+    /// A special behavior of walls, such as being climbable.
+    pub fn wall_type(self) -> WallType {
+        WallType(((self.0 >> 21) & 0x1f) as u8)
+    }
+
+    /// A special behavior of floors, such as damaging the player.
+    pub fn floor_type(self) -> u8 {
+        ((self.0 >> 13) & 0x1f) as u8
+    }
+
+    /// An index plus one into the scene's exit list, or 0 if touching the triangle doesn't exit
+    /// the scene.
+    pub fn exit_index(self) -> u8 {
+        ((self.0 >> 8) & 0x1f) as u8
+    }
+
+    /// An index into the collision's [`CameraData`] list.
+    pub fn camera_data_index(self) -> u8 {
+        self.0 as u8
+    }
+}
+
+impl FromVrom for TriangleTypeHigh {
+    fn from_vrom(vrom: Vrom<'_>, addr: VromAddr) -> Result<Self, ReadError> {
+        Ok(Self(<u32 as FromVrom>::from_vrom(vrom, addr)?))
+    }
+}
+
+impl Layout for TriangleTypeHigh {
+    const SIZE: u32 = 4;
+}
+
+/// The second word of a [`TriangleType`].
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct TriangleTypeLow(pub u32);
+
+impl TriangleTypeLow {
+    /// Whether touching the triangle as a wall damages the player.
+    pub fn wall_damage(self) -> bool {
+        self.0 & 0x0800_0000 != 0
+    }
+
+    /// The direction that a conveyor moves actors in, in 64ths of a turn.
+    pub fn conveyor_direction(self) -> u8 {
+        ((self.0 >> 21) & 0x3f) as u8
+    }
+
+    pub fn conveyor_speed(self) -> ConveyorSpeed {
+        ConveyorSpeed(((self.0 >> 18) & 0x7) as u8)
+    }
+
+    /// Whether the hookshot and longshot can latch onto the triangle.
+    pub fn hookshotable(self) -> bool {
+        self.0 & 0x0002_0000 != 0
+    }
+
+    /// The amount of echo in sounds played near the triangle.
+    pub fn echo(self) -> u8 {
+        ((self.0 >> 11) & 0x3f) as u8
+    }
+
+    /// An index into the scene's light settings, used while standing on the triangle.
+    pub fn light_setting(self) -> u8 {
+        ((self.0 >> 6) & 0x1f) as u8
+    }
+
+    /// A special behavior of floors, such as sliding down slopes.
+    pub fn floor_effect(self) -> u8 {
+        ((self.0 >> 4) & 0x3) as u8
+    }
+
+    /// What the triangle is made of, which determines the sound of footsteps.
+    pub fn material(self) -> SurfaceMaterial {
+        SurfaceMaterial((self.0 & 0xf) as u8)
+    }
+}
+
+impl FromVrom for TriangleTypeLow {
+    fn from_vrom(vrom: Vrom<'_>, addr: VromAddr) -> Result<Self, ReadError> {
+        Ok(Self(<u32 as FromVrom>::from_vrom(vrom, addr)?))
+    }
+}
+
+impl Layout for TriangleTypeLow {
+    const SIZE: u32 = 4;
+}
+
+#[primitive_enum(u8)]
+pub enum WallType {
+    NONE = 0,
+    NO_LEDGE_GRAB = 1,
+    LADDER = 2,
+    LADDER_TOP = 3,
+    VINES = 4,
+    CRAWLSPACE_1 = 5,
+    CRAWLSPACE_2 = 6,
+    PUSHBLOCK = 7,
+}
+
+#[primitive_enum(u8)]
+pub enum ConveyorSpeed {
+    DISABLED = 0,
+    SLOW = 1,
+    MEDIUM = 2,
+    FAST = 3,
+}
+
+#[primitive_enum(u8)]
+pub enum SurfaceMaterial {
+    DIRT = 0,
+    SAND = 1,
+    STONE = 2,
+    JABU = 3,
+    WATER_SHALLOW = 4,
+    WATER_DEEP = 5,
+    TALL_GRASS = 6,
+    LAVA = 7,
+    GRASS = 8,
+    BRIDGE = 9,
+    WOOD = 10,
+    DIRT_SOFT = 11,
+    ICE = 12,
+    CARPET = 13,
+}
+
+/// Bitfield spans are named after their descriptors. Spans without values show as plain numbers.
+macro_rules! span {
+    ($name:literal, $shift:literal, $mask:literal) => {
+        span!($name, $shift, $mask, &[])
+    };
+    ($name:literal, $shift:literal, $mask:literal, $values:expr) => {
+        BitfieldSpan {
+            shift: $shift,
+            mask: $mask,
+            desc: &EnumDescriptor {
+                name: $name,
+                underlying: PrimitiveType::U32,
+                values: $values,
+            },
+        }
+    };
+}
+
+/// The values of a primitive enum's descriptor, for reuse in a bitfield span.
+const fn enum_values(desc: TypeDescriptor) -> &'static [(u32, &'static str)] {
+    match desc {
+        TypeDescriptor::Enum(desc) => desc.values,
+        _ => panic!("expected an enum descriptor"),
+    }
+}
+
+pub const TRIANGLE_TYPE_HIGH_DESC: TypeDescriptor = TypeDescriptor::Bitfield(&BitfieldDescriptor {
+    name: "TriangleTypeHigh",
+    underlying: PrimitiveType::U32,
+    fields: &[
+        span!("horse_blocked", 31, 1),
+        span!("floor_property", 26, 0xf),
+        span!("wall_type", 21, 0x1f, enum_values(WALL_TYPE_DESC)),
+        span!("floor_type", 13, 0x1f),
+        span!("exit_index", 8, 0x1f),
+        span!("camera_data_index", 0, 0xff),
+    ],
+});
+
+pub const TRIANGLE_TYPE_LOW_DESC: TypeDescriptor = TypeDescriptor::Bitfield(&BitfieldDescriptor {
+    name: "TriangleTypeLow",
+    underlying: PrimitiveType::U32,
+    fields: &[
+        span!("wall_damage", 27, 1),
+        span!("conveyor_direction", 21, 0x3f),
+        span!("conveyor_speed", 18, 0x7, enum_values(CONVEYOR_SPEED_DESC)),
+        span!("hookshotable", 17, 1),
+        span!("echo", 11, 0x3f),
+        span!("light_setting", 6, 0x1f),
+        span!("floor_effect", 4, 0x3),
+        span!("material", 0, 0xf, enum_values(SURFACE_MATERIAL_DESC)),
+    ],
+});
+
+impl WaterBox {
+    pub fn camera_data_index(self, vrom: Vrom<'_>) -> u8 {
+        self.flags(vrom) as u8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oot_explorer_reflect::{Value, ValueKind};
+    use oot_explorer_segment::Segment;
+
+    /// Builds a collision header at 0 whose triangles follow it in the scene segment.
+    fn collision_with_triangle_types(types: &[u16]) -> Vec<u8> {
+        let mut data = vec![0; Collision::SIZE as usize];
+        data[0x14..0x16].copy_from_slice(&(types.len() as u16).to_be_bytes());
+        data[0x18..0x1c].copy_from_slice(&(0x0200_0000 + Collision::SIZE).to_be_bytes());
+        for &type_ in types {
+            let mut triangle = [0; Triangle::SIZE as usize];
+            triangle[0..2].copy_from_slice(&type_.to_be_bytes());
+            data.extend_from_slice(&triangle);
+        }
+        data
+    }
+
+    #[test]
+    fn infers_triangle_type_count_from_largest_type() {
+        let data = collision_with_triangle_types(&[1, 3, 0]);
+        let vrom = Vrom(&data);
+        let segment_table = SegmentTable::new().with(Segment::SCENE, VromAddr(0));
+        let collision = Collision::from_vrom(vrom, VromAddr(0)).unwrap();
+        let count = collision
+            .infer_triangle_type_count(vrom, &segment_table)
+            .unwrap();
+        assert_eq!(count.infer_len(VromAddr(0), TriangleType::SIZE), 4);
+    }
+
+    #[test]
+    fn infers_one_triangle_type_without_triangles() {
+        let data = collision_with_triangle_types(&[]);
+        let vrom = Vrom(&data);
+        let segment_table = SegmentTable::new().with(Segment::SCENE, VromAddr(0));
+        let collision = Collision::from_vrom(vrom, VromAddr(0)).unwrap();
+        let count = collision
+            .infer_triangle_type_count(vrom, &segment_table)
+            .unwrap();
+        assert_eq!(count, MaxIndex(Some(0)));
+        assert_eq!(count.infer_len(VromAddr(0), TriangleType::SIZE), 1);
+    }
+
+    #[test]
+    fn triangle_type_matches_descriptor() {
+        let data = [0x84, 0x40, 0x43, 0x02, 0x00, 0x0e, 0x00, 0x08];
+        let vrom = Vrom(&data);
+        let triangle_type = TriangleType::from_vrom(vrom, VromAddr(0)).unwrap();
+        let high = triangle_type.high_value(vrom);
+        assert!(high.horse_blocked());
+        assert_eq!(high.floor_property(), 1);
+        assert!(high.wall_type() == WallType::LADDER);
+        assert_eq!(high.floor_type(), 2);
+        assert_eq!(high.exit_index(), 3);
+        assert_eq!(triangle_type.camera_data_index(vrom), 2);
+        let low = triangle_type.low_value(vrom);
+        assert!(low.conveyor_speed() == ConveyorSpeed::FAST);
+        assert!(low.hookshotable());
+        assert!(low.material() == SurfaceMaterial::GRASS);
+
+        let value = Value::read(vrom, &SegmentTable::new(), TRIANGLE_TYPE_DESC, VromAddr(0));
+        let spans = |name| match &value.field(name).unwrap().kind {
+            ValueKind::Bitfield { spans, .. } => spans
+                .iter()
+                .map(|span| (span.value, span.name))
+                .collect::<Vec<_>>(),
+            _ => panic!("{} should be a bitfield", name),
+        };
+        assert_eq!(
+            spans("high_value"),
+            vec![
+                (1, None),
+                (1, None),
+                (2, Some("LADDER")),
+                (2, None),
+                (3, None),
+                (2, None),
+            ],
+        );
+        assert_eq!(spans("low_value")[2], (3, Some("FAST")));
+        assert_eq!(spans("low_value")[7], (8, Some("GRASS")));
+    }
+}
@@ -0,0 +1,663 @@
+//! An owned copy of a collision mesh for geometric queries.
+//!
+//! Triangles are kept in a bounding volume hierarchy, so that queries only test the triangles
+//! near them. Triangles are classified the way the game does: floors have normals within 60
+//! degrees of straight up, ceilings have normals within about 37 degrees of straight down, and
+//! everything else is a wall.
+
+use oot_explorer_read::ReadError;
+use oot_explorer_segment::SegmentTable;
+use oot_explorer_vrom::Vrom;
+
+use super::Collision;
+
+/// Triangles whose normal has a larger Y component are floors.
+const FLOOR_MIN_NORMAL_Y: f32 = 0.5;
+
+/// Triangles whose normal has a smaller Y component are ceilings.
+const CEILING_MAX_NORMAL_Y: f32 = -0.8;
+
+/// The largest number of triangles in a leaf of the hierarchy.
+const MAX_LEAF_TRIANGLES: usize = 4;
+
+/// A collision mesh, read from a [`Collision`] and organized for queries.
+pub struct CollisionModel {
+    triangles: Vec<ModelTriangle>,
+    nodes: Vec<Node>,
+    water_boxes: Vec<ModelWaterBox>,
+}
+
+/// A triangle of a [`CollisionModel`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ModelTriangle {
+    /// The triangle's index in the collision's triangle list.
+    pub index: u32,
+    /// An index into the collision's triangle type list.
+    pub type_index: u16,
+    pub vertices: [[f32; 3]; 3],
+    /// The unit normal stored with the triangle.
+    pub normal: [f32; 3],
+    /// The plane's distance term: points on the plane satisfy `dot(normal, p) + dist == 0`.
+    pub dist: f32,
+}
+
+/// A box of water, open to the sky, that extends down from its surface without limit.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ModelWaterBox {
+    /// The water box's index in the collision's water box list.
+    pub index: u32,
+    pub x_min: f32,
+    pub x_max: f32,
+    pub z_min: f32,
+    pub z_max: f32,
+    pub y_surface: f32,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TriangleKind {
+    Floor,
+    Wall,
+    Ceiling,
+}
+
+/// Where a ray hit a triangle.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RayHit<'a> {
+    pub triangle: &'a ModelTriangle,
+    /// The distance along the ray, in multiples of its direction vector.
+    pub t: f32,
+    pub point: [f32; 3],
+}
+
+/// The floor found below a point.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FloorHit<'a> {
+    pub triangle: &'a ModelTriangle,
+    pub y: f32,
+}
+
+/// The closest point on a wall to a point.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WallHit<'a> {
+    pub triangle: &'a ModelTriangle,
+    pub point: [f32; 3],
+    pub distance: f32,
+}
+
+/// A node of the bounding volume hierarchy. Interior nodes are followed by their first child,
+/// and refer to their second.
+#[derive(Clone, Copy, Debug)]
+struct Node {
+    bounds: Bounds,
+    kind: NodeKind,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum NodeKind {
+    Leaf { start: usize, end: usize },
+    Interior { second_child: usize },
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Bounds {
+    min: [f32; 3],
+    max: [f32; 3],
+}
+
+impl CollisionModel {
+    /// Reads the vertices, triangles, and water boxes of `collision`. Triangles that refer to
+    /// vertices outside of the vertex list are left out.
+    pub fn new(
+        vrom: Vrom<'_>,
+        segment_table: &SegmentTable,
+        collision: Collision,
+    ) -> Result<Self, ReadError> {
+        let vertices = collision
+            .vertices(vrom, segment_table)?
+            .iter(vrom)
+            .map(|vertex| {
+                let vertex = vertex?;
+                Ok([
+                    f32::from(vertex.x(vrom)),
+                    f32::from(vertex.y(vrom)),
+                    f32::from(vertex.z(vrom)),
+                ])
+            })
+            .collect::<Result<Vec<_>, ReadError>>()?;
+
+        let mut triangles = vec![];
+        for (index, triangle) in collision
+            .triangles(vrom, segment_table)?
+            .iter(vrom)
+            .enumerate()
+        {
+            let triangle = triangle?;
+            let indices = [
+                triangle.vertex_a(vrom),
+                triangle.vertex_b(vrom),
+                triangle.vertex_c(vrom),
+            ];
+            if indices.iter().any(|&i| usize::from(i) >= vertices.len()) {
+                continue;
+            }
+            triangles.push(ModelTriangle {
+                index: index as u32,
+                type_index: triangle.type_(vrom),
+                vertices: [
+                    vertices[usize::from(indices[0])],
+                    vertices[usize::from(indices[1])],
+                    vertices[usize::from(indices[2])],
+                ],
+                normal: [
+                    f32::from(triangle.plane_a(vrom)) / 32767.0,
+                    f32::from(triangle.plane_b(vrom)) / 32767.0,
+                    f32::from(triangle.plane_c(vrom)) / 32767.0,
+                ],
+                dist: f32::from(triangle.plane_d(vrom)),
+            });
+        }
+
+        let water_boxes = collision
+            .water_boxes(vrom, segment_table)?
+            .into_iter()
+            .flat_map(|water_boxes| water_boxes.iter(vrom))
+            .enumerate()
+            .map(|(index, water_box)| {
+                let water_box = water_box?;
+                let x_min = f32::from(water_box.x_min(vrom));
+                let z_min = f32::from(water_box.z_min(vrom));
+                Ok(ModelWaterBox {
+                    index: index as u32,
+                    x_min,
+                    x_max: x_min + f32::from(water_box.x_span(vrom)),
+                    z_min,
+                    z_max: z_min + f32::from(water_box.z_span(vrom)),
+                    y_surface: f32::from(water_box.y_surface(vrom)),
+                })
+            })
+            .collect::<Result<Vec<_>, ReadError>>()?;
+
+        Ok(Self::from_parts(triangles, water_boxes))
+    }
+
+    /// Builds a model from triangles and water boxes that have already been read.
+    pub fn from_parts(mut triangles: Vec<ModelTriangle>, water_boxes: Vec<ModelWaterBox>) -> Self {
+        let mut nodes = vec![];
+        if !triangles.is_empty() {
+            let len = triangles.len();
+            build(&mut nodes, &mut triangles, 0, len);
+        }
+        CollisionModel {
+            triangles,
+            nodes,
+            water_boxes,
+        }
+    }
+
+    /// The triangles, in the order the hierarchy keeps them rather than the collision's order.
+    pub fn triangles(&self) -> &[ModelTriangle] {
+        &self.triangles
+    }
+
+    pub fn water_boxes(&self) -> &[ModelWaterBox] {
+        &self.water_boxes
+    }
+
+    /// Finds the first triangle hit by the ray from `origin` along `dir`, no further than `max_t`
+    /// multiples of `dir`. Triangles are hit from either side.
+    pub fn raycast(&self, origin: [f32; 3], dir: [f32; 3], max_t: f32) -> Option<RayHit<'_>> {
+        let inv_dir = [1.0 / dir[0], 1.0 / dir[1], 1.0 / dir[2]];
+        let mut best: Option<(usize, f32)> = None;
+        self.visit(
+            &mut best,
+            |best, bounds| {
+                let limit = best.map_or(max_t, |(_, t)| t);
+                bounds.intersects_ray(origin, inv_dir, limit)
+            },
+            |best, index, triangle| {
+                let limit = best.map_or(max_t, |(_, t)| t);
+                if let Some(t) = triangle.intersect_ray(origin, dir) {
+                    if t <= limit {
+                        *best = Some((index, t));
+                    }
+                }
+            },
+        );
+        best.map(|(index, t)| RayHit {
+            triangle: &self.triangles[index],
+            t,
+            point: add(origin, scale(dir, t)),
+        })
+    }
+
+    /// Finds the highest floor at or below `pos`.
+    pub fn floor_below(&self, pos: [f32; 3]) -> Option<FloorHit<'_>> {
+        let mut best: Option<(usize, f32)> = None;
+        self.visit(
+            &mut best,
+            |_, bounds| bounds.contains_xz(pos) && bounds.min[1] <= pos[1],
+            |best, index, triangle| {
+                if triangle.kind() != TriangleKind::Floor || !triangle.contains_xz(pos) {
+                    return;
+                }
+                let y = triangle.height_at(pos[0], pos[2]);
+                if y <= pos[1] && best.is_none_or(|(_, best_y)| y > best_y) {
+                    *best = Some((index, y));
+                }
+            },
+        );
+        best.map(|(index, y)| FloorHit {
+            triangle: &self.triangles[index],
+            y,
+        })
+    }
+
+    /// Finds the wall closest to `pos`, no further away than `radius`.
+    pub fn nearest_wall(&self, pos: [f32; 3], radius: f32) -> Option<WallHit<'_>> {
+        let mut best: Option<(usize, [f32; 3], f32)> = None;
+        self.visit(
+            &mut best,
+            |best, bounds| {
+                let limit = best.map_or(radius, |(_, _, distance)| distance);
+                bounds.distance_squared(pos) <= limit * limit
+            },
+            |best, index, triangle| {
+                if triangle.kind() != TriangleKind::Wall {
+                    return;
+                }
+                let point = triangle.closest_point(pos);
+                let distance = length(sub(pos, point));
+                let limit = best.map_or(radius, |(_, _, distance)| distance);
+                if distance <= limit {
+                    *best = Some((index, point, distance));
+                }
+            },
+        );
+        best.map(|(index, point, distance)| WallHit {
+            triangle: &self.triangles[index],
+            point,
+            distance,
+        })
+    }
+
+    /// Finds the first water box that `pos` is in.
+    pub fn water_box_at(&self, pos: [f32; 3]) -> Option<&ModelWaterBox> {
+        self.water_boxes
+            .iter()
+            .find(|water_box| water_box.contains(pos))
+    }
+
+    /// Walks the hierarchy depth first, entering nodes whose bounds pass `enter`, and calling
+    /// `visit` for each triangle in the leaves reached. Both see the query's current `state`.
+    fn visit<S>(
+        &self,
+        state: &mut S,
+        enter: impl Fn(&S, &Bounds) -> bool,
+        mut visit: impl FnMut(&mut S, usize, &ModelTriangle),
+    ) {
+        let mut stack = vec![];
+        if !self.nodes.is_empty() {
+            stack.push(0);
+        }
+        while let Some(node_index) = stack.pop() {
+            let node = &self.nodes[node_index];
+            if !enter(state, &node.bounds) {
+                continue;
+            }
+            match node.kind {
+                NodeKind::Leaf { start, end } => {
+                    for index in start..end {
+                        visit(state, index, &self.triangles[index]);
+                    }
+                }
+                NodeKind::Interior { second_child } => {
+                    stack.push(second_child);
+                    stack.push(node_index + 1);
+                }
+            }
+        }
+    }
+}
+
+/// Appends the subtree for `triangles[start..end]` to `nodes`, reordering those triangles.
+fn build(nodes: &mut Vec<Node>, triangles: &mut [ModelTriangle], start: usize, end: usize) {
+    let bounds = triangles[start..end]
+        .iter()
+        .map(ModelTriangle::bounds)
+        .reduce(Bounds::union)
+        .unwrap();
+    let node_index = nodes.len();
+    if end - start <= MAX_LEAF_TRIANGLES {
+        nodes.push(Node {
+            bounds,
+            kind: NodeKind::Leaf { start, end },
+        });
+        return;
+    }
+
+    // Split at the median along the axis where the triangles are most spread out.
+    let axis = (0..3)
+        .max_by(|&a, &b| {
+            let extent = |axis: usize| bounds.max[axis] - bounds.min[axis];
+            extent(a).total_cmp(&extent(b))
+        })
+        .unwrap();
+    let mid = (start + end) / 2;
+    triangles[start..end].select_nth_unstable_by(mid - start, |a, b| {
+        a.centroid()[axis].total_cmp(&b.centroid()[axis])
+    });
+
+    nodes.push(Node {
+        bounds,
+        kind: NodeKind::Interior { second_child: 0 },
+    });
+    build(nodes, triangles, start, mid);
+    let second_child = nodes.len();
+    nodes[node_index].kind = NodeKind::Interior { second_child };
+    build(nodes, triangles, mid, end);
+}
+
+impl ModelTriangle {
+    pub fn kind(&self) -> TriangleKind {
+        if self.normal[1] > FLOOR_MIN_NORMAL_Y {
+            TriangleKind::Floor
+        } else if self.normal[1] < CEILING_MAX_NORMAL_Y {
+            TriangleKind::Ceiling
+        } else {
+            TriangleKind::Wall
+        }
+    }
+
+    /// The height of the triangle's plane at (x, z). Only meaningful for floors and ceilings.
+    pub fn height_at(&self, x: f32, z: f32) -> f32 {
+        -(self.normal[0] * x + self.normal[2] * z + self.dist) / self.normal[1]
+    }
+
+    fn bounds(&self) -> Bounds {
+        let point = |v| Bounds { min: v, max: v };
+        let [a, b, c] = self.vertices;
+        point(a).union(point(b)).union(point(c))
+    }
+
+    fn centroid(&self) -> [f32; 3] {
+        let [a, b, c] = self.vertices;
+        scale(add(add(a, b), c), 1.0 / 3.0)
+    }
+
+    /// Whether (x, z) lies within the triangle, seen from above.
+    fn contains_xz(&self, pos: [f32; 3]) -> bool {
+        let [a, b, c] = self.vertices;
+        let edge = |p: [f32; 3], q: [f32; 3]| {
+            (q[0] - p[0]) * (pos[2] - p[2]) - (q[2] - p[2]) * (pos[0] - p[0])
+        };
+        let (ab, bc, ca) = (edge(a, b), edge(b, c), edge(c, a));
+        (ab >= 0.0 && bc >= 0.0 && ca >= 0.0) || (ab <= 0.0 && bc <= 0.0 && ca <= 0.0)
+    }
+
+    /// Intersects the ray with the triangle by the Moller-Trumbore method.
+    fn intersect_ray(&self, origin: [f32; 3], dir: [f32; 3]) -> Option<f32> {
+        let [a, b, c] = self.vertices;
+        let ab = sub(b, a);
+        let ac = sub(c, a);
+        let p = cross(dir, ac);
+        let det = dot(ab, p);
+        if det.abs() < f32::EPSILON {
+            return None;
+        }
+        let inv_det = 1.0 / det;
+        let s = sub(origin, a);
+        let u = dot(s, p) * inv_det;
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+        let q = cross(s, ab);
+        let v = dot(dir, q) * inv_det;
+        if v < 0.0 || u + v > 1.0 {
+            return None;
+        }
+        let t = dot(ac, q) * inv_det;
+        if t >= 0.0 {
+            Some(t)
+        } else {
+            None
+        }
+    }
+
+    /// The point on the triangle closest to `pos`, from Ericson's "Real-Time Collision
+    /// Detection".
+    fn closest_point(&self, pos: [f32; 3]) -> [f32; 3] {
+        let [a, b, c] = self.vertices;
+        let ab = sub(b, a);
+        let ac = sub(c, a);
+        let ap = sub(pos, a);
+        let d1 = dot(ab, ap);
+        let d2 = dot(ac, ap);
+        if d1 <= 0.0 && d2 <= 0.0 {
+            return a;
+        }
+
+        let bp = sub(pos, b);
+        let d3 = dot(ab, bp);
+        let d4 = dot(ac, bp);
+        if d3 >= 0.0 && d4 <= d3 {
+            return b;
+        }
+
+        let vc = d1 * d4 - d3 * d2;
+        if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+            return add(a, scale(ab, d1 / (d1 - d3)));
+        }
+
+        let cp = sub(pos, c);
+        let d5 = dot(ab, cp);
+        let d6 = dot(ac, cp);
+        if d6 >= 0.0 && d5 <= d6 {
+            return c;
+        }
+
+        let vb = d5 * d2 - d1 * d6;
+        if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+            return add(a, scale(ac, d2 / (d2 - d6)));
+        }
+
+        let va = d3 * d6 - d5 * d4;
+        if va <= 0.0 && (d4 - d3) >= 0.0 && (d5 - d6) >= 0.0 {
+            return add(b, scale(sub(c, b), (d4 - d3) / ((d4 - d3) + (d5 - d6))));
+        }
+
+        let denom = 1.0 / (va + vb + vc);
+        add(a, add(scale(ab, vb * denom), scale(ac, vc * denom)))
+    }
+}
+
+impl ModelWaterBox {
+    pub fn contains(&self, pos: [f32; 3]) -> bool {
+        (self.x_min..=self.x_max).contains(&pos[0])
+            && (self.z_min..=self.z_max).contains(&pos[2])
+            && pos[1] <= self.y_surface
+    }
+}
+
+impl Bounds {
+    fn union(self, other: Bounds) -> Bounds {
+        let mut bounds = self;
+        for axis in 0..3 {
+            bounds.min[axis] = bounds.min[axis].min(other.min[axis]);
+            bounds.max[axis] = bounds.max[axis].max(other.max[axis]);
+        }
+        bounds
+    }
+
+    fn contains_xz(&self, pos: [f32; 3]) -> bool {
+        (self.min[0]..=self.max[0]).contains(&pos[0])
+            && (self.min[2]..=self.max[2]).contains(&pos[2])
+    }
+
+    fn distance_squared(&self, pos: [f32; 3]) -> f32 {
+        (0..3)
+            .map(|axis| {
+                let d = (self.min[axis] - pos[axis]).max(pos[axis] - self.max[axis]);
+                d.max(0.0) * d.max(0.0)
+            })
+            .sum()
+    }
+
+    /// Slab test for whether the ray enters the bounds before `max_t`.
+    fn intersects_ray(&self, origin: [f32; 3], inv_dir: [f32; 3], max_t: f32) -> bool {
+        let mut t_min = 0.0f32;
+        let mut t_max = max_t;
+        for axis in 0..3 {
+            let t1 = (self.min[axis] - origin[axis]) * inv_dir[axis];
+            let t2 = (self.max[axis] - origin[axis]) * inv_dir[axis];
+            // NaN comes from a zero direction component with the origin on a slab boundary, which
+            // doesn't rule out a hit. `min` and `max` ignore it.
+            t_min = t_min.max(t1.min(t2));
+            t_max = t_max.min(t1.max(t2));
+        }
+        t_min <= t_max
+    }
+}
+
+fn add(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn sub(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn scale(a: [f32; 3], s: f32) -> [f32; 3] {
+    [a[0] * s, a[1] * s, a[2] * s]
+}
+
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn length(a: [f32; 3]) -> f32 {
+    dot(a, a).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oot_explorer_read::FromVrom;
+    use oot_explorer_segment::Segment;
+    use oot_explorer_vrom::VromAddr;
+
+    fn halves(halves: &[i16]) -> Vec<u8> {
+        halves.iter().flat_map(|half| half.to_be_bytes()).collect()
+    }
+
+    /// A floor from (-100, 0, -100) to (100, 0, 100), a wall facing -X at x = 50, and a water box
+    /// over the floor's -X half.
+    fn collision() -> Vec<u8> {
+        let mut data = halves(&[
+            -100, 0, -100, 100, 200, 100, // bounds
+            8, 0, 0x0200, 0x0030, // vertices
+            4, 0, 0x0200, 0x0060, // triangles
+            0, 0, 0, 0, // triangle types and camera data
+            1, 0, 0x0200, 0x00a0, // water boxes
+        ]);
+        data.extend(halves(&[0, 0]));
+        data.extend(halves(&[
+            -100, 0, -100, 100, 0, -100, 100, 0, 100, -100, 0, 100, // floor
+            50, 0, -100, 50, 200, -100, 50, 200, 100, 50, 0, 100, // wall
+        ]));
+        data.extend(halves(&[
+            0, 0, 3, 2, 0, 0x7fff, 0, 0, // floor
+            0, 0, 2, 1, 0, 0x7fff, 0, 0, // floor
+            1, 4, 5, 6, -0x7fff, 0, 0, 50, // wall
+            1, 4, 6, 7, -0x7fff, 0, 0, 50, // wall
+        ]));
+        data.extend(halves(&[-100, 50, -100, 100, 200, 0, 0, 0]));
+        data
+    }
+
+    fn model(data: &[u8]) -> CollisionModel {
+        let vrom = Vrom(data);
+        let segment_table = SegmentTable::new().with(Segment::SCENE, VromAddr(0));
+        let collision = Collision::from_vrom(vrom, VromAddr(0)).unwrap();
+        CollisionModel::new(vrom, &segment_table, collision).unwrap()
+    }
+
+    #[test]
+    fn answers_queries() {
+        let data = collision();
+        let model = model(&data);
+        assert_eq!(model.triangles().len(), 4);
+
+        let floor = model.floor_below([10.0, 30.0, 20.0]).unwrap();
+        assert_eq!(floor.y, 0.0);
+        assert_eq!(floor.triangle.kind(), TriangleKind::Floor);
+        assert!(model.floor_below([10.0, -1.0, 20.0]).is_none());
+        assert!(model.floor_below([150.0, 30.0, 20.0]).is_none());
+
+        let hit = model
+            .raycast([0.0, 100.0, 0.0], [1.0, 0.0, 0.0], 1000.0)
+            .unwrap();
+        assert_eq!(hit.t, 50.0);
+        assert_eq!(hit.triangle.kind(), TriangleKind::Wall);
+        let hit = model
+            .raycast([0.0, 100.0, 0.0], [0.0, -1.0, 0.0], 1000.0)
+            .unwrap();
+        assert_eq!(hit.point, [0.0, 0.0, 0.0]);
+        assert!(model
+            .raycast([0.0, 100.0, 0.0], [0.0, 1.0, 0.0], 1000.0)
+            .is_none());
+
+        let wall = model.nearest_wall([30.0, 100.0, 0.0], 25.0).unwrap();
+        assert_eq!(wall.point, [50.0, 100.0, 0.0]);
+        assert_eq!(wall.distance, 20.0);
+        assert!(model.nearest_wall([20.0, 100.0, 0.0], 25.0).is_none());
+
+        assert_eq!(model.water_box_at([-50.0, 10.0, 0.0]).unwrap().index, 0);
+        assert!(model.water_box_at([-50.0, 60.0, 0.0]).is_none());
+        assert!(model.water_box_at([50.0, 10.0, 0.0]).is_none());
+    }
+
+    #[test]
+    fn hierarchy_matches_brute_force() {
+        // A grid of floor tiles, enough to need several levels.
+        let mut triangles = vec![];
+        for i in 0..16 {
+            for j in 0..16 {
+                let (x, z) = (i as f32 * 10.0, j as f32 * 10.0);
+                let y = (i + j) as f32;
+                for vertices in [
+                    [[x, y, z], [x, y, z + 10.0], [x + 10.0, y, z]],
+                    [[x + 10.0, y, z], [x, y, z + 10.0], [x + 10.0, y, z + 10.0]],
+                ] {
+                    triangles.push(ModelTriangle {
+                        index: triangles.len() as u32,
+                        type_index: 0,
+                        vertices,
+                        normal: [0.0, 1.0, 0.0],
+                        dist: -y,
+                    });
+                }
+            }
+        }
+        let model = CollisionModel::from_parts(triangles, vec![]);
+        assert!(model.nodes.len() > 1);
+
+        for &(x, z) in &[(5.0, 5.0), (73.0, 121.0), (155.0, 2.0)] {
+            let pos = [x, 100.0, z];
+            let expected = model
+                .triangles()
+                .iter()
+                .filter(|triangle| triangle.contains_xz(pos))
+                .map(|triangle| triangle.height_at(x, z))
+                .fold(f32::NEG_INFINITY, f32::max);
+            assert_eq!(model.floor_below(pos).unwrap().y, expected);
+        }
+    }
+}
@@ -0,0 +1,513 @@
+use std::iter::FusedIterator;
+
+use oot_explorer_derive::{layout, primitive_enum};
+use oot_explorer_read::{
+    aligned_data, is_end, FromVrom, Layout, ReadError, Sentinel, Slice, Vec3s, VromProxy,
+    MAX_INFERRED_LEN,
+};
+use oot_explorer_reflect::{F32_DESC, I32_DESC, I8_DESC, U16_DESC, U8_DESC, VEC3S_DESC};
+use oot_explorer_vrom::{Vrom, VromAddr, VromError};
+
+mod camera;
+mod command_desc;
+
+pub use camera::{CameraSample, CutsceneCamera};
+pub use command_desc::{CUTSCENE_COMMAND_DESC, CUTSCENE_DESC};
+
+/// A cutscene script: a command count and a frame count, followed by commands that each act over
+/// a range of frames.
+#[derive(Clone, Copy)]
+pub struct Cutscene {
+    addr: VromAddr,
+}
+
+impl Cutscene {
+    /// The number of commands in the script. Scripts also end at an
+    /// [`END`](CutsceneCommandType::END) command.
+    pub fn command_count(self, vrom: Vrom<'_>) -> u32 {
+        // Unwrap because size and alignment have already been checked.
+        u32::from_vrom(vrom, self.addr).unwrap()
+    }
+
+    pub fn frame_count(self, vrom: Vrom<'_>) -> u32 {
+        // Unwrap because size and alignment have already been checked.
+        u32::from_vrom(vrom, self.addr + 4).unwrap()
+    }
+
+    pub fn commands(self, vrom: Vrom<'_>) -> CutsceneCommandIter<'_> {
+        CutsceneCommandIter {
+            vrom,
+            addr: Some(self.addr + Self::SIZE),
+            remaining: self.command_count(vrom),
+        }
+    }
+}
+
+impl FromVrom for Cutscene {
+    fn from_vrom(vrom: Vrom<'_>, addr: VromAddr) -> Result<Self, ReadError> {
+        aligned_data::<Self>(vrom, addr)?;
+        Ok(Self { addr })
+    }
+}
+
+impl VromProxy for Cutscene {
+    fn addr(&self) -> VromAddr {
+        self.addr
+    }
+}
+
+impl Layout for Cutscene {
+    const SIZE: u32 = 8;
+    const ALIGN_BITS: u32 = 2;
+}
+
+#[derive(Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct CutsceneCommandIter<'a> {
+    vrom: Vrom<'a>,
+    addr: Option<VromAddr>,
+    remaining: u32,
+}
+
+impl<'a> Iterator for CutsceneCommandIter<'a> {
+    type Item = Result<(VromAddr, CutsceneCommand), ReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let addr = self.addr?;
+        if self.remaining == 0 {
+            self.addr = None;
+            return None;
+        }
+        self.remaining -= 1;
+
+        let result = CutsceneCommand::parse(self.vrom, addr);
+        self.addr = match result {
+            Ok((CutsceneCommand::End, _)) | Err(_) => None,
+            Ok((_, size)) => Some(addr + size),
+        };
+        Some(result.map(|(command, _)| (addr, command)))
+    }
+}
+
+impl<'a> FusedIterator for CutsceneCommandIter<'a> {}
+
+#[primitive_enum(u32)]
+pub enum CutsceneCommandType {
+    CAM_EYE_SPLINE = 0x01,
+    CAM_AT_SPLINE = 0x02,
+    MISC = 0x03,
+    LIGHT_SETTING = 0x04,
+    CAM_EYE_SPLINE_REL_TO_PLAYER = 0x05,
+    CAM_AT_SPLINE_REL_TO_PLAYER = 0x06,
+    CAM_EYE = 0x07,
+    CAM_AT = 0x08,
+    RUMBLE_CONTROLLER = 0x09,
+    PLAYER_CUE = 0x0a,
+    TEXT = 0x13,
+    TRANSITION = 0x2d,
+    START_SEQ = 0x56,
+    STOP_SEQ = 0x57,
+    FADE_OUT_SEQ = 0x7c,
+    TIME = 0x8c,
+    DESTINATION = 0x3e8,
+    END = 0xffff_ffff,
+}
+
+/// One command of a cutscene script. Commands with unlisted types cue actors.
+#[derive(Clone, Copy)]
+pub enum CutsceneCommand {
+    /// Moves the camera's eye or focus ("at") along a spline over a range of frames. Single
+    /// positions are splines of one point.
+    Camera {
+        command_type: CutsceneCommandType,
+        start_frame: u16,
+        end_frame: u16,
+        points: Slice<CameraPoint>,
+    },
+    Misc(Slice<MiscCue>),
+    LightSetting(Slice<LightSettingCue>),
+    Rumble(Slice<RumbleCue>),
+    /// Cues for the player, or for whichever actors respond to `command_type`.
+    ActorCues {
+        command_type: CutsceneCommandType,
+        cues: Slice<ActorCue>,
+    },
+    Text(Slice<TextCue>),
+    Transition(Slice<TransitionCue>),
+    StartSeq(Slice<SeqCue>),
+    StopSeq(Slice<SeqCue>),
+    FadeOutSeq(Slice<FadeOutSeqCue>),
+    Time(Slice<TimeCue>),
+    Destination(Slice<DestinationCue>),
+    End,
+}
+
+impl CutsceneCommand {
+    /// Reads the command at `addr`, along with its size in bytes.
+    pub fn parse(vrom: Vrom<'_>, addr: VromAddr) -> Result<(CutsceneCommand, u32), ReadError> {
+        let command_type = CutsceneCommandType::from_vrom(vrom, addr)?;
+        match command_type {
+            CutsceneCommandType::CAM_EYE_SPLINE
+            | CutsceneCommandType::CAM_AT_SPLINE
+            | CutsceneCommandType::CAM_EYE_SPLINE_REL_TO_PLAYER
+            | CutsceneCommandType::CAM_AT_SPLINE_REL_TO_PLAYER
+            | CutsceneCommandType::CAM_EYE
+            | CutsceneCommandType::CAM_AT => {
+                let points_addr = addr + CAMERA_HEADER_SIZE;
+                let point_count = match command_type {
+                    CutsceneCommandType::CAM_EYE | CutsceneCommandType::CAM_AT => 1,
+                    _ => camera_point_count(vrom, points_addr)?,
+                };
+                let size = CAMERA_HEADER_SIZE + point_count * CameraPoint::SIZE;
+                vrom.slice(addr..addr + size)?;
+                Ok((
+                    CutsceneCommand::Camera {
+                        command_type,
+                        start_frame: u16::from_vrom(vrom, addr + 6)?,
+                        end_frame: u16::from_vrom(vrom, addr + 8)?,
+                        points: Slice::new(points_addr, point_count),
+                    },
+                    size,
+                ))
+            }
+            CutsceneCommandType::MISC => cues(vrom, addr, CutsceneCommand::Misc),
+            CutsceneCommandType::LIGHT_SETTING => cues(vrom, addr, CutsceneCommand::LightSetting),
+            CutsceneCommandType::RUMBLE_CONTROLLER => cues(vrom, addr, CutsceneCommand::Rumble),
+            CutsceneCommandType::TEXT => cues(vrom, addr, CutsceneCommand::Text),
+            CutsceneCommandType::TRANSITION => cues(vrom, addr, CutsceneCommand::Transition),
+            CutsceneCommandType::START_SEQ => cues(vrom, addr, CutsceneCommand::StartSeq),
+            CutsceneCommandType::STOP_SEQ => cues(vrom, addr, CutsceneCommand::StopSeq),
+            CutsceneCommandType::FADE_OUT_SEQ => cues(vrom, addr, CutsceneCommand::FadeOutSeq),
+            CutsceneCommandType::TIME => cues(vrom, addr, CutsceneCommand::Time),
+            CutsceneCommandType::DESTINATION => cues(vrom, addr, CutsceneCommand::Destination),
+            CutsceneCommandType::END => Ok((CutsceneCommand::End, 8)),
+            _ => cues(vrom, addr, |cues| CutsceneCommand::ActorCues {
+                command_type,
+                cues,
+            }),
+        }
+    }
+}
+
+/// The size of a camera command before its points.
+const CAMERA_HEADER_SIZE: u32 = 0xc;
+
+/// Counts the points of a spline, up to and including the one marked as the last.
+fn camera_point_count(vrom: Vrom<'_>, addr: VromAddr) -> Result<u32, ReadError> {
+    let mut count = 0;
+    while count < MAX_INFERRED_LEN {
+        let point = CameraPoint::from_vrom(vrom, addr + count * CameraPoint::SIZE)?;
+        count += 1;
+        if point.is_end(vrom) {
+            break;
+        }
+    }
+    Ok(count)
+}
+
+/// Reads a command that stores a count of cues at offset 4, followed by the cues themselves.
+fn cues<T: FromVrom + Layout>(
+    vrom: Vrom<'_>,
+    addr: VromAddr,
+    f: impl FnOnce(Slice<T>) -> CutsceneCommand,
+) -> Result<(CutsceneCommand, u32), ReadError> {
+    let count = u32::from_vrom(vrom, addr + 4)?;
+    let size = count.saturating_mul(T::SIZE).saturating_add(8);
+    let end = addr
+        .0
+        .checked_add(size)
+        .ok_or(VromError::VromAddrOverflow { addr, offset: size })?;
+    vrom.slice(addr..VromAddr(end))?;
+    Ok((f(Slice::new(addr + 8, count)), size))
+}
+
+/// A point on a camera spline.
+#[layout(size = 0x10, align_bits = 2)]
+#[is_end(is_end::<CameraPoint>)]
+pub struct CameraPoint {
+    /// -1 on the last point of a spline, otherwise 0.
+    #[at(0)]
+    continue_flag: i8,
+    #[at(1)]
+    roll: i8,
+    /// The number of frames to the next point.
+    #[at(2)]
+    frame: u16,
+    /// The field of view, in degrees.
+    #[at(4)]
+    view_angle: f32,
+    #[at(8)]
+    pos: Vec3s,
+}
+
+impl Sentinel for CameraPoint {
+    const ITER_YIELDS_SENTINEL_VALUE: bool = true;
+
+    fn is_end(&self, vrom: Vrom<'_>) -> bool {
+        self.continue_flag(vrom) == -1
+    }
+}
+
+/// Moves an actor from one position to another over a range of frames.
+#[layout(size = 0x30, align_bits = 2)]
+pub struct ActorCue {
+    /// What the actor does. The meaning depends on the actor.
+    #[at(0)]
+    action: u16,
+    #[at(2)]
+    start_frame: u16,
+    #[at(4)]
+    end_frame: u16,
+    #[at(6)]
+    rot: Vec3s,
+    #[at(0xc)]
+    start_x: i32,
+    #[at(0x10)]
+    start_y: i32,
+    #[at(0x14)]
+    start_z: i32,
+    #[at(0x18)]
+    end_x: i32,
+    #[at(0x1c)]
+    end_y: i32,
+    #[at(0x20)]
+    end_z: i32,
+}
+
+#[layout(size = 0x30, align_bits = 2)]
+pub struct MiscCue {
+    #[at(0)]
+    misc_type: u16,
+    #[at(2)]
+    start_frame: u16,
+    #[at(4)]
+    end_frame: u16,
+}
+
+#[layout(size = 0x30, align_bits = 2)]
+pub struct LightSettingCue {
+    #[at(0)]
+    setting_plus_one: u16,
+    #[at(2)]
+    start_frame: u16,
+    #[at(4)]
+    end_frame: u16,
+}
+
+#[layout(size = 0xc, align_bits = 2)]
+pub struct RumbleCue {
+    #[at(2)]
+    start_frame: u16,
+    #[at(4)]
+    end_frame: u16,
+    #[at(6)]
+    source_strength: u8,
+    #[at(7)]
+    duration: u8,
+    #[at(8)]
+    decrease_rate: u8,
+}
+
+#[layout(size = 0xc, align_bits = 1)]
+pub struct TextCue {
+    /// The message to show, or 0xffff for none.
+    #[at(0)]
+    text_id: u16,
+    #[at(2)]
+    start_frame: u16,
+    #[at(4)]
+    end_frame: u16,
+    #[at(6)]
+    text_type: TextType,
+    /// Messages that replace `text_id` depending on the answer to a choice or ocarina song.
+    #[at(8)]
+    alt_text_id_1: u16,
+    #[at(0xa)]
+    alt_text_id_2: u16,
+}
+
+#[primitive_enum(u16)]
+pub enum TextType {
+    NORMAL = 0x00,
+    CHOICE = 0x01,
+    OCARINA_ACTION = 0x02,
+    GORON_RUBY = 0x03,
+    ZORA_SAPPHIRE = 0x04,
+}
+
+#[layout(size = 8, align_bits = 1)]
+pub struct TransitionCue {
+    #[at(0)]
+    transition_type: u16,
+    #[at(2)]
+    start_frame: u16,
+    #[at(4)]
+    end_frame: u16,
+}
+
+/// Starts or stops a sequence of music.
+#[layout(size = 0x30, align_bits = 2)]
+pub struct SeqCue {
+    #[at(0)]
+    seq_id_plus_one: u16,
+    #[at(2)]
+    start_frame: u16,
+    #[at(4)]
+    end_frame: u16,
+}
+
+#[layout(size = 0x30, align_bits = 2)]
+pub struct FadeOutSeqCue {
+    #[at(0)]
+    seq_player: u16,
+    #[at(2)]
+    start_frame: u16,
+    #[at(4)]
+    end_frame: u16,
+}
+
+/// Sets the time of day.
+#[layout(size = 0xc, align_bits = 1)]
+pub struct TimeCue {
+    #[at(2)]
+    start_frame: u16,
+    #[at(4)]
+    end_frame: u16,
+    #[at(6)]
+    hour: u8,
+    #[at(7)]
+    minute: u8,
+}
+
+/// Ends the cutscene by moving to another place or cutscene.
+#[layout(size = 8, align_bits = 1)]
+pub struct DestinationCue {
+    #[at(0)]
+    destination: u16,
+    #[at(2)]
+    start_frame: u16,
+    #[at(4)]
+    end_frame: u16,
+}
+
+impl ActorCue {
+    pub fn start_pos(self, vrom: Vrom<'_>) -> [i32; 3] {
+        [self.start_x(vrom), self.start_y(vrom), self.start_z(vrom)]
+    }
+
+    pub fn end_pos(self, vrom: Vrom<'_>) -> [i32; 3] {
+        [self.end_x(vrom), self.end_y(vrom), self.end_z(vrom)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oot_explorer_reflect::TypeDescriptor;
+
+    fn words(words: &[u32]) -> Vec<u8> {
+        words.iter().flat_map(|word| word.to_be_bytes()).collect()
+    }
+
+    fn script() -> Vec<u8> {
+        let mut data = words(&[4, 100]);
+        // An eye spline of two points.
+        data.extend(words(&[0x01, 0x0001_0000, 0x0064_0000]));
+        data.extend(words(&[0x0000_0010, 0x4270_0000, 0x0001_0002, 0x0003_0000]));
+        data.extend(words(&[0xff00_0000, 0x4270_0000, 0x0004_0005, 0x0006_0000]));
+        // One text cue.
+        data.extend(words(&[0x13, 1, 0x1234_000a, 0x0014_0001, 0xffff_ffff]));
+        // One cue for an actor that responds to command 0x0f.
+        data.extend(words(&[0x0f, 1]));
+        data.extend(words(&[
+            0x0002_0000,
+            0x0010_0000,
+            0,
+            1,
+            2,
+            3,
+            4,
+            5,
+            6,
+            0,
+            0,
+            0,
+        ]));
+        data.extend(words(&[0xffff_ffff, 0]));
+        data
+    }
+
+    #[test]
+    fn parses_commands() {
+        let data = script();
+        let vrom = Vrom(&data);
+        let cutscene = Cutscene::from_vrom(vrom, VromAddr(0)).unwrap();
+        assert_eq!(cutscene.frame_count(vrom), 100);
+
+        let commands: Vec<_> = cutscene.commands(vrom).map(Result::unwrap).collect();
+        let addrs: Vec<_> = commands.iter().map(|&(addr, _)| addr.0).collect();
+        assert_eq!(addrs, [0x08, 0x34, 0x48, 0x80]);
+        match commands[0].1 {
+            CutsceneCommand::Camera {
+                command_type,
+                end_frame,
+                points,
+                ..
+            } => {
+                assert!(command_type == CutsceneCommandType::CAM_EYE_SPLINE);
+                assert_eq!(end_frame, 100);
+                assert_eq!(points.len(), 2);
+                assert_eq!(points.get(vrom, 1).unwrap().pos(vrom).x, 4);
+            }
+            _ => panic!("expected a camera command"),
+        }
+        match commands[1].1 {
+            CutsceneCommand::Text(cues) => {
+                let cue = cues.get(vrom, 0).unwrap();
+                assert_eq!(cue.text_id(vrom), 0x1234);
+                assert!(cue.text_type(vrom) == TextType::CHOICE);
+            }
+            _ => panic!("expected a text command"),
+        }
+        match commands[2].1 {
+            CutsceneCommand::ActorCues { command_type, cues } => {
+                assert_eq!(command_type.0, 0x0f);
+                assert_eq!(cues.get(vrom, 0).unwrap().end_pos(vrom), [4, 5, 6]);
+            }
+            _ => panic!("expected actor cues"),
+        }
+        assert!(matches!(commands[3].1, CutsceneCommand::End));
+    }
+
+    #[test]
+    fn descriptor_measures_commands() {
+        let data = script();
+        let vrom = Vrom(&data);
+        assert_eq!(
+            CUTSCENE_DESC.size_at(vrom, VromAddr(0)),
+            Some(data.len() as u32)
+        );
+
+        let TypeDescriptor::Union(desc) = CUTSCENE_COMMAND_DESC else {
+            unreachable!()
+        };
+        let variant_names: Vec<_> = [0x08, 0x34, 0x48, 0x80]
+            .iter()
+            .map(|&addr| desc.variant_at(vrom, VromAddr(addr)).unwrap().name())
+            .collect();
+        assert_eq!(
+            variant_names,
+            ["CameraSpline", "TextList", "ActorCueList", "CutsceneEnd"]
+        );
+    }
+
+    #[test]
+    fn descriptor_honors_command_count() {
+        let mut data = script();
+        data[..4].copy_from_slice(&2_u32.to_be_bytes());
+        let vrom = Vrom(&data);
+        let cutscene = Cutscene::from_vrom(vrom, VromAddr(0)).unwrap();
+        assert_eq!(cutscene.commands(vrom).count(), 2);
+        assert_eq!(CUTSCENE_DESC.size_at(vrom, VromAddr(0)), Some(0x48));
+    }
+}
@@ -0,0 +1,218 @@
+//! Camera playback for cutscene scripts.
+//!
+//! The game moves the camera's eye and focus along uniform cubic B-splines. Each frame, it
+//! evaluates the window of four points starting at the current key point, then advances the
+//! window's parameter by one over the `frame` value of the window's second point. When the
+//! parameter reaches 1, the window moves on by one point, until its last point is the spline's
+//! last point.
+
+use oot_explorer_read::{ReadError, Slice};
+use oot_explorer_vrom::Vrom;
+
+use super::{CameraPoint, Cutscene, CutsceneCommand, CutsceneCommandType};
+
+/// The camera's eye and focus at one frame of a cutscene.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CutsceneCamera {
+    pub eye: CameraSample,
+    /// The point the camera looks at.
+    pub at: CameraSample,
+}
+
+/// A position along one of a cutscene's camera splines.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CameraSample {
+    pub pos: [f32; 3],
+    /// The roll, in degrees.
+    pub roll: f32,
+    /// The field of view, in degrees.
+    pub view_angle: f32,
+    /// Whether `pos` is an offset from the player, rotated by the player's yaw, rather than a
+    /// position in the scene.
+    pub relative_to_player: bool,
+}
+
+impl Cutscene {
+    /// Evaluates the camera at `frame`, or returns `None` if the script hasn't positioned both the
+    /// eye and the focus by then.
+    ///
+    /// Once a camera command's frames have passed, the camera holds where that command left it.
+    pub fn camera_at(
+        self,
+        vrom: Vrom<'_>,
+        frame: u32,
+    ) -> Result<Option<CutsceneCamera>, ReadError> {
+        let mut eye = None;
+        let mut at = None;
+        for result in self.commands(vrom) {
+            let (_, command) = result?;
+            if let CutsceneCommand::Camera {
+                command_type,
+                start_frame,
+                end_frame,
+                points,
+            } = command
+            {
+                if frame <= u32::from(start_frame) {
+                    continue;
+                }
+                let (track, relative_to_player) = match command_type {
+                    CutsceneCommandType::CAM_EYE_SPLINE | CutsceneCommandType::CAM_EYE => {
+                        (&mut eye, false)
+                    }
+                    CutsceneCommandType::CAM_EYE_SPLINE_REL_TO_PLAYER => (&mut eye, true),
+                    CutsceneCommandType::CAM_AT_SPLINE | CutsceneCommandType::CAM_AT => {
+                        (&mut at, false)
+                    }
+                    CutsceneCommandType::CAM_AT_SPLINE_REL_TO_PLAYER => (&mut at, true),
+                    _ => continue,
+                };
+
+                // The game first evaluates a spline on the frame after its start frame, and stops
+                // on the frame before its end frame.
+                let last_frame = u32::from(end_frame).saturating_sub(1);
+                let steps = frame
+                    .min(last_frame)
+                    .saturating_sub(u32::from(start_frame) + 1);
+                let points = read_points(vrom, points)?;
+                if let Some(mut sample) = evaluate(&points, steps) {
+                    sample.relative_to_player = relative_to_player;
+                    *track = Some(sample);
+                }
+            }
+        }
+        Ok(match (eye, at) {
+            (Some(eye), Some(at)) => Some(CutsceneCamera { eye, at }),
+            _ => None,
+        })
+    }
+}
+
+struct Point {
+    frame: u16,
+    pos: [f32; 3],
+    roll: f32,
+    view_angle: f32,
+}
+
+fn read_points(vrom: Vrom<'_>, points: Slice<CameraPoint>) -> Result<Vec<Point>, ReadError> {
+    points
+        .iter(vrom)
+        .map(|point| {
+            let point = point?;
+            let pos = point.pos(vrom);
+            Ok(Point {
+                frame: point.frame(vrom),
+                pos: [pos.x as f32, pos.y as f32, pos.z as f32],
+                roll: point.roll(vrom) as f32,
+                view_angle: point.view_angle(vrom),
+            })
+        })
+        .collect()
+}
+
+/// Evaluates a spline after the game has advanced it `steps` times.
+fn evaluate(points: &[Point], steps: u32) -> Option<CameraSample> {
+    let last = points.len().checked_sub(1)?;
+    let mut key = 0;
+    let mut u = 0.0f32;
+    for _ in 0..steps {
+        let frames = points[(key + 1).min(last)].frame;
+        if frames == 0 {
+            u = 1.0;
+        } else {
+            u += 1.0 / f32::from(frames);
+        }
+        if u >= 1.0 {
+            if key + 3 >= last {
+                u = 1.0;
+                break;
+            }
+            key += 1;
+            u -= 1.0;
+        }
+    }
+
+    let coeffs = [
+        (1.0 - u) * (1.0 - u) * (1.0 - u) / 6.0,
+        u * u * u * 0.5 - u * u + 2.0 / 3.0,
+        u * u * u * -0.5 + u * u * 0.5 + u * 0.5 + 1.0 / 6.0,
+        u * u * u / 6.0,
+    ];
+    let mut sample = CameraSample {
+        pos: [0.0; 3],
+        roll: 0.0,
+        view_angle: 0.0,
+        relative_to_player: false,
+    };
+    for (i, coeff) in coeffs.iter().enumerate() {
+        // Splines of fewer than four points repeat their last point.
+        let point = &points[(key + i).min(last)];
+        for (pos, &x) in sample.pos.iter_mut().zip(&point.pos) {
+            *pos += coeff * x;
+        }
+        sample.roll += coeff * point.roll;
+        sample.view_angle += coeff * point.view_angle;
+    }
+    sample.roll *= 360.0 / 256.0;
+    Some(sample)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oot_explorer_read::FromVrom;
+    use oot_explorer_vrom::VromAddr;
+
+    fn words(words: &[u32]) -> Vec<u8> {
+        words.iter().flat_map(|word| word.to_be_bytes()).collect()
+    }
+
+    /// A camera point at (x, 0, 0) with a view angle of 60 degrees.
+    fn point(last: bool, frames: u16, x: i16) -> [u32; 4] {
+        [
+            (if last { 0xff00_0000 } else { 0 }) | u32::from(frames),
+            60.0f32.to_bits(),
+            (x as u16 as u32) << 16,
+            0,
+        ]
+    }
+
+    fn script() -> Vec<u8> {
+        let mut data = words(&[3, 100]);
+        // An eye spline over frames 10..50, with points every 10 frames.
+        data.extend(words(&[0x01, 0x000a, 0x0032_0000]));
+        for i in 0..6 {
+            data.extend(words(&point(i == 5, 10, 100 * i as i16)));
+        }
+        // A fixed focus from frame 0.
+        data.extend(words(&[0x08, 0x0000, 0x0064_0000]));
+        data.extend(words(&point(true, 0, -50)));
+        data.extend(words(&[0xffff_ffff, 0]));
+        data
+    }
+
+    #[test]
+    fn follows_splines() {
+        let data = script();
+        let vrom = Vrom(&data);
+        let cutscene = Cutscene::from_vrom(vrom, VromAddr(0)).unwrap();
+
+        assert_eq!(cutscene.camera_at(vrom, 5).unwrap(), None);
+
+        let camera = cutscene.camera_at(vrom, 11).unwrap().unwrap();
+        // The first window of points at 0, 100, 200, 300.
+        assert!((camera.eye.pos[0] - 100.0).abs() < 1e-3);
+        assert!((camera.eye.view_angle - 60.0).abs() < 1e-3);
+        assert!((camera.at.pos[0] + 50.0).abs() < 1e-3);
+
+        // Halfway through the window: (1 + 23 * 100 + 23 * 200 + 300) / 48 is 150.
+        let camera = cutscene.camera_at(vrom, 16).unwrap().unwrap();
+        assert!((camera.eye.pos[0] - 150.0).abs() < 1e-3);
+
+        // The spline ends with the window at 200, 300, 400, 500 fully advanced.
+        let end = cutscene.camera_at(vrom, 49).unwrap().unwrap();
+        assert!((end.eye.pos[0] - 400.0).abs() < 1e-3);
+        assert_eq!(cutscene.camera_at(vrom, 90).unwrap(), Some(end));
+    }
+}
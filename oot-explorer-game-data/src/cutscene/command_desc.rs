@@ -0,0 +1,192 @@
+//! Reflection for cutscene scripts.
+//!
+//! Commands vary in size, so neither scripts nor commands have a fixed size. Most commands store
+//! a count of cues followed by the cues themselves, and camera commands are followed by points up
+//! to the one marked as the last.
+
+use oot_explorer_read::FromVrom;
+use oot_explorer_reflect::{
+    FieldDescriptor, PrimitiveType, StructDescriptor, StructFieldLocation, TypeDescriptor,
+    UnionDescriptor, U16_DESC, U32_DESC,
+};
+use oot_explorer_vrom::{Vrom, VromAddr};
+
+use super::{
+    CutsceneCommandType, ACTOR_CUE_DESC, CAMERA_POINT_DESC, CUTSCENE_COMMAND_TYPE_DESC,
+    DESTINATION_CUE_DESC, FADE_OUT_SEQ_CUE_DESC, LIGHT_SETTING_CUE_DESC, MISC_CUE_DESC,
+    RUMBLE_CUE_DESC, SEQ_CUE_DESC, TEXT_CUE_DESC, TIME_CUE_DESC, TRANSITION_CUE_DESC,
+};
+
+/// A command made of a count of cues at offset 4, followed by the cues.
+macro_rules! cue_list {
+    ($name:literal, $cue:expr) => {
+        TypeDescriptor::Struct(&StructDescriptor {
+            name: $name,
+            size: None,
+            is_end: None,
+            fields: &[FieldDescriptor {
+                name: "cues",
+                location: StructFieldLocation::InlineSlice {
+                    count_offset: 4,
+                    count_desc: PrimitiveType::U32,
+                    offset: 8,
+                },
+                desc: $cue,
+            }],
+        })
+    };
+}
+
+pub const CUTSCENE_DESC: TypeDescriptor = TypeDescriptor::Struct(&StructDescriptor {
+    name: "Cutscene",
+    size: None,
+    is_end: None,
+    fields: &[
+        FieldDescriptor {
+            name: "frame_count",
+            location: StructFieldLocation::Simple { offset: 4 },
+            desc: U32_DESC,
+        },
+        FieldDescriptor {
+            name: "commands",
+            location: StructFieldLocation::InlineSlice {
+                count_offset: 0,
+                count_desc: PrimitiveType::U32,
+                offset: 8,
+            },
+            desc: CUTSCENE_COMMAND_DESC,
+        },
+    ],
+});
+
+pub const CUTSCENE_COMMAND_DESC: TypeDescriptor = TypeDescriptor::Union(&UnionDescriptor {
+    name: "CutsceneCommand",
+    size: None,
+    is_end: Some(is_end_command),
+    end_discriminant: Some(CutsceneCommandType::END.to_u32()),
+    discriminant_offset: 0,
+    discriminant_desc: CUTSCENE_COMMAND_TYPE_DESC,
+    variants: &[
+        (
+            CutsceneCommandType::CAM_EYE_SPLINE.to_u32(),
+            CAMERA_SPLINE_DESC,
+        ),
+        (
+            CutsceneCommandType::CAM_AT_SPLINE.to_u32(),
+            CAMERA_SPLINE_DESC,
+        ),
+        (CutsceneCommandType::MISC.to_u32(), MISC_LIST_DESC),
+        (
+            CutsceneCommandType::LIGHT_SETTING.to_u32(),
+            LIGHT_SETTING_LIST_DESC,
+        ),
+        (
+            CutsceneCommandType::CAM_EYE_SPLINE_REL_TO_PLAYER.to_u32(),
+            CAMERA_SPLINE_DESC,
+        ),
+        (
+            CutsceneCommandType::CAM_AT_SPLINE_REL_TO_PLAYER.to_u32(),
+            CAMERA_SPLINE_DESC,
+        ),
+        (CutsceneCommandType::CAM_EYE.to_u32(), CAMERA_POSITION_DESC),
+        (CutsceneCommandType::CAM_AT.to_u32(), CAMERA_POSITION_DESC),
+        (
+            CutsceneCommandType::RUMBLE_CONTROLLER.to_u32(),
+            RUMBLE_LIST_DESC,
+        ),
+        (
+            CutsceneCommandType::PLAYER_CUE.to_u32(),
+            ACTOR_CUE_LIST_DESC,
+        ),
+        (CutsceneCommandType::TEXT.to_u32(), TEXT_LIST_DESC),
+        (
+            CutsceneCommandType::TRANSITION.to_u32(),
+            TRANSITION_LIST_DESC,
+        ),
+        (CutsceneCommandType::START_SEQ.to_u32(), SEQ_LIST_DESC),
+        (CutsceneCommandType::STOP_SEQ.to_u32(), SEQ_LIST_DESC),
+        (
+            CutsceneCommandType::FADE_OUT_SEQ.to_u32(),
+            FADE_OUT_SEQ_LIST_DESC,
+        ),
+        (CutsceneCommandType::TIME.to_u32(), TIME_LIST_DESC),
+        (
+            CutsceneCommandType::DESTINATION.to_u32(),
+            DESTINATION_LIST_DESC,
+        ),
+        (CutsceneCommandType::END.to_u32(), CUTSCENE_END_DESC),
+    ],
+    fallback: Some(ACTOR_CUE_LIST_DESC),
+});
+
+fn is_end_command(vrom: Vrom<'_>, addr: VromAddr) -> bool {
+    match CutsceneCommandType::from_vrom(vrom, addr) {
+        Ok(command_type) => command_type == CutsceneCommandType::END,
+        Err(_) => true,
+    }
+}
+
+const CAMERA_SPLINE_DESC: TypeDescriptor = TypeDescriptor::Struct(&StructDescriptor {
+    name: "CameraSpline",
+    size: None,
+    is_end: None,
+    fields: &[
+        FieldDescriptor {
+            name: "start_frame",
+            location: StructFieldLocation::Simple { offset: 6 },
+            desc: U16_DESC,
+        },
+        FieldDescriptor {
+            name: "end_frame",
+            location: StructFieldLocation::Simple { offset: 8 },
+            desc: U16_DESC,
+        },
+        FieldDescriptor {
+            name: "points",
+            location: StructFieldLocation::InlineDelimitedList { offset: 0xc },
+            desc: CAMERA_POINT_DESC,
+        },
+    ],
+});
+
+const CAMERA_POSITION_DESC: TypeDescriptor = TypeDescriptor::Struct(&StructDescriptor {
+    name: "CameraPosition",
+    size: Some(0x1c),
+    is_end: None,
+    fields: &[
+        FieldDescriptor {
+            name: "start_frame",
+            location: StructFieldLocation::Simple { offset: 6 },
+            desc: U16_DESC,
+        },
+        FieldDescriptor {
+            name: "end_frame",
+            location: StructFieldLocation::Simple { offset: 8 },
+            desc: U16_DESC,
+        },
+        FieldDescriptor {
+            name: "point",
+            location: StructFieldLocation::Simple { offset: 0xc },
+            desc: CAMERA_POINT_DESC,
+        },
+    ],
+});
+
+const MISC_LIST_DESC: TypeDescriptor = cue_list!("MiscList", MISC_CUE_DESC);
+const LIGHT_SETTING_LIST_DESC: TypeDescriptor =
+    cue_list!("LightSettingList", LIGHT_SETTING_CUE_DESC);
+const RUMBLE_LIST_DESC: TypeDescriptor = cue_list!("RumbleList", RUMBLE_CUE_DESC);
+const ACTOR_CUE_LIST_DESC: TypeDescriptor = cue_list!("ActorCueList", ACTOR_CUE_DESC);
+const TEXT_LIST_DESC: TypeDescriptor = cue_list!("TextList", TEXT_CUE_DESC);
+const TRANSITION_LIST_DESC: TypeDescriptor = cue_list!("TransitionList", TRANSITION_CUE_DESC);
+const SEQ_LIST_DESC: TypeDescriptor = cue_list!("SeqList", SEQ_CUE_DESC);
+const FADE_OUT_SEQ_LIST_DESC: TypeDescriptor = cue_list!("FadeOutSeqList", FADE_OUT_SEQ_CUE_DESC);
+const TIME_LIST_DESC: TypeDescriptor = cue_list!("TimeList", TIME_CUE_DESC);
+const DESTINATION_LIST_DESC: TypeDescriptor = cue_list!("DestinationList", DESTINATION_CUE_DESC);
+
+const CUTSCENE_END_DESC: TypeDescriptor = TypeDescriptor::Struct(&StructDescriptor {
+    name: "CutsceneEnd",
+    size: Some(8),
+    is_end: None,
+    fields: &[],
+});
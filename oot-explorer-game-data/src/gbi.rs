@@ -1,27 +1,23 @@
 use byteorder::{BigEndian, ReadBytesExt};
 use num_traits::FromPrimitive;
-use oot_explorer_read::{aligned_data, is_end, FromVrom, Layout, ReadError, Sentinel};
-use oot_explorer_reflect::{StructDescriptor, TypeDescriptor, I16_DESC, I8_DESC, U8_DESC};
+use oot_explorer_derive::layout;
+use oot_explorer_read::{aligned_data, FromVrom, Layout, ReadError, Sentinel};
+pub use oot_explorer_read::{Qu0_16, Qu10_2, Qu1_11};
+use oot_explorer_reflect::{I16_DESC, I8_DESC, U8_DESC};
 use oot_explorer_segment::SegmentAddr;
 use oot_explorer_vrom::{Vrom, VromAddr};
 use std::fmt::{self, Debug, Formatter};
 use std::ops::{BitAnd, BitAndAssign, Not};
 
-declare_pointer_descriptor!(DisplayList);
+mod instruction_desc;
 
-compile_interfaces! {
-    #[layout(size = 0, align_bits = 2)]
-    struct DisplayList {
-        struct Instruction[..] instructions @0;
-    }
-}
+pub use instruction_desc::{INSTRUCTION_DESC, MATRIX_DESC, OPCODE_DESC, TEXELS_DESC};
 
-pub const INSTRUCTION_DESC: TypeDescriptor = TypeDescriptor::Struct(&StructDescriptor {
-    name: "Instruction",
-    size: Some(8),
-    is_end: Some(is_end::<Instruction>),
-    fields: &[],
-});
+#[layout(size = 0, align_bits = 2)]
+pub struct DisplayList {
+    #[at(0)]
+    instructions: [Instruction],
+}
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Instruction {
@@ -347,66 +343,6 @@ impl Sentinel for Instruction {
     }
 }
 
-/// A fixed-point 0.16 number.
-#[derive(Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
-pub struct Qu0_16(pub u16);
-
-impl Debug for Qu0_16 {
-    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "Qu0_16({})", self.as_f32())
-    }
-}
-
-impl Qu0_16 {
-    pub fn as_f32(self) -> f32 {
-        self.0 as f32 / 65536.0
-    }
-
-    pub fn as_f64(self) -> f64 {
-        self.0 as f64 / 65536.0
-    }
-}
-
-/// A fixed-point 10.2 number.
-#[derive(Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
-pub struct Qu10_2(pub u16);
-
-impl Debug for Qu10_2 {
-    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "Qu10_2({})", self.as_f32())
-    }
-}
-
-impl Qu10_2 {
-    pub fn as_f32(self) -> f32 {
-        (self.0 & 0x0fff) as f32 / 4.0
-    }
-
-    pub fn as_f64(self) -> f64 {
-        (self.0 & 0x0fff) as f64 / 4.0
-    }
-}
-
-/// A fixed-point 10.2 number.
-#[derive(Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
-pub struct Qu1_11(pub u16);
-
-impl Debug for Qu1_11 {
-    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "Qu1_11({})", self.as_f32())
-    }
-}
-
-impl Qu1_11 {
-    pub fn as_f32(self) -> f32 {
-        (self.0 & 0x0fff) as f32 / 2048.0
-    }
-
-    pub fn as_f64(self) -> f64 {
-        (self.0 & 0x0fff) as f64 / 2048.0
-    }
-}
-
 #[derive(
     Clone,
     Copy,
@@ -1405,20 +1341,27 @@ impl AlphaInput {
     }
 }
 
-compile_interfaces! {
-    #[layout(size = 0x10, align_bits = 1)]
-    struct UnlitVertex {
-        i16 position_x @0;
-        i16 position_y @2;
-        i16 position_z @4;
-        // unused
-        i16 texcoord_s @8;
-        i16 texcoord_t @0xa;
-        u8 color_r @0xc;
-        u8 color_g @0xd;
-        u8 color_b @0xe;
-        u8 color_a @0xf;
-    }
+#[layout(size = 0x10, align_bits = 1)]
+pub struct UnlitVertex {
+    #[at(0)]
+    position_x: i16,
+    #[at(2)]
+    position_y: i16,
+    #[at(4)]
+    position_z: i16,
+    // unused
+    #[at(8)]
+    texcoord_s: i16,
+    #[at(0xa)]
+    texcoord_t: i16,
+    #[at(0xc)]
+    color_r: u8,
+    #[at(0xd)]
+    color_g: u8,
+    #[at(0xe)]
+    color_b: u8,
+    #[at(0xf)]
+    color_a: u8,
 }
 
 impl UnlitVertex {
@@ -1444,20 +1387,27 @@ impl UnlitVertex {
     }
 }
 
-compile_interfaces! {
-    #[layout(size = 0x10, align_bits = 1)]
-    struct LitVertex {
-        i16 position_x @0;
-        i16 position_y @2;
-        i16 position_z @4;
-        // unused
-        i16 texcoord_s @8;
-        i16 texcoord_t @0xa;
-        i8 normal_x @0xc;
-        i8 normal_y @0xd;
-        i8 normal_z @0xe;
-        u8 alpha @0xf;
-    }
+#[layout(size = 0x10, align_bits = 1)]
+pub struct LitVertex {
+    #[at(0)]
+    position_x: i16,
+    #[at(2)]
+    position_y: i16,
+    #[at(4)]
+    position_z: i16,
+    // unused
+    #[at(8)]
+    texcoord_s: i16,
+    #[at(0xa)]
+    texcoord_t: i16,
+    #[at(0xc)]
+    normal_x: i8,
+    #[at(0xd)]
+    normal_y: i8,
+    #[at(0xe)]
+    normal_z: i8,
+    #[at(0xf)]
+    alpha: u8,
 }
 
 impl LitVertex {
@@ -0,0 +1,548 @@
+//! Reflection for GBI instructions.
+//!
+//! Instructions pack their parameters into two big-endian words, `w0` and `w1`. Parameters that
+//! occupy whole bytes are described as primitives, and the rest as bitfield spans over a word.
+//! Spans hold raw values, so fixed-point spans are in units of their fraction and counts that
+//! the RSP stores minus one are named as such.
+
+use oot_explorer_read::is_end;
+use oot_explorer_reflect::{
+    BitfieldDescriptor, BitfieldSpan, EnumDescriptor, FieldDescriptor, PointerDescriptor,
+    PrimitiveType, StructDescriptor, StructFieldLocation, TypeDescriptor, UnionDescriptor,
+    BOOL_DESC, QU0_16_DESC, QU1_11_DESC, SEGMENT_ADDR_DESC, U32_DESC, U8_DESC,
+};
+
+use super::{Instruction, DISPLAY_LIST_DESC, UNLIT_VERTEX_DESC};
+
+/// A bitfield span holding a number rather than a named value.
+macro_rules! span {
+    ($name:literal, $shift:literal, $mask:literal) => {
+        span!(
+            $name,
+            $shift,
+            $mask,
+            &EnumDescriptor {
+                name: $name,
+                underlying: PrimitiveType::U32,
+                values: &[],
+            }
+        )
+    };
+    ($name:literal, $shift:literal, $mask:literal, $desc:expr) => {
+        BitfieldSpan {
+            shift: $shift,
+            mask: $mask,
+            desc: $desc,
+        }
+    };
+}
+
+/// A bitfield over one of an instruction's words.
+macro_rules! word {
+    ($name:literal, [$($span:expr),* $(,)?]) => {
+        TypeDescriptor::Bitfield(&BitfieldDescriptor {
+            name: $name,
+            underlying: PrimitiveType::U32,
+            fields: &[$($span),*],
+        })
+    };
+}
+
+macro_rules! field {
+    ($name:literal @ $offset:literal: $desc:expr) => {
+        FieldDescriptor {
+            name: $name,
+            location: StructFieldLocation::Simple { offset: $offset },
+            desc: $desc,
+        }
+    };
+}
+
+/// The parameters of one opcode, named after the corresponding [`Instruction`] variant.
+macro_rules! variant {
+    ($name:literal, [$($field:expr),* $(,)?]) => {
+        TypeDescriptor::Struct(&StructDescriptor {
+            name: $name,
+            size: Some(8),
+            is_end: None,
+            fields: &[$($field),*],
+        })
+    };
+}
+
+macro_rules! pointer {
+    ($name:literal, $target:expr) => {
+        TypeDescriptor::Pointer(&PointerDescriptor {
+            name: $name,
+            target: || $target,
+            nullable: false,
+        })
+    };
+}
+
+pub const INSTRUCTION_DESC: TypeDescriptor = TypeDescriptor::Union(&UnionDescriptor {
+    name: "Instruction",
+    size: Some(8),
+    is_end: Some(is_end::<Instruction>),
+    // A jumping gsSPDisplayList also ends the list.
+    end_discriminant: None,
+    discriminant_offset: 0,
+    discriminant_desc: OPCODE_DESC,
+    variants: &[
+        (0x00, NOOP_DESC),
+        (0x01, VTX_DESC),
+        (0x03, CULL_DL_DESC),
+        (0x04, BRANCH_Z_DESC),
+        (0x05, TRI1_DESC),
+        (0x06, TRI2_DESC),
+        (0xd7, TEXTURE_DESC),
+        (0xd9, GEOMETRY_MODE_DESC),
+        (0xda, MTX_DESC),
+        (0xde, DL_DESC),
+        (0xdf, END_DL_DESC),
+        (0xe1, RDP_HALF1_DESC),
+        (0xe2, SET_OTHER_MODE_L_DESC),
+        (0xe3, SET_OTHER_MODE_H_DESC),
+        (0xe6, RDP_LOAD_SYNC_DESC),
+        (0xe7, RDP_PIPE_SYNC_DESC),
+        (0xe8, RDP_TILE_SYNC_DESC),
+        (0xf0, LOAD_TLUT_DESC),
+        (0xf2, SET_TILE_SIZE_DESC),
+        (0xf3, LOAD_BLOCK_DESC),
+        (0xf5, SET_TILE_DESC),
+        (0xfa, SET_PRIM_COLOR_DESC),
+        (0xfb, SET_ENV_COLOR_DESC),
+        (0xfc, SET_COMBINE_DESC),
+        (0xfd, SET_TIMG_DESC),
+    ],
+    fallback: None,
+});
+
+pub const OPCODE_DESC: TypeDescriptor = TypeDescriptor::Enum(&EnumDescriptor {
+    name: "Opcode",
+    underlying: PrimitiveType::U8,
+    values: &[
+        (0x00, "NOOP"),
+        (0x01, "VTX"),
+        (0x03, "CULL_DL"),
+        (0x04, "BRANCH_Z"),
+        (0x05, "TRI1"),
+        (0x06, "TRI2"),
+        (0xd7, "TEXTURE"),
+        (0xd9, "GEOMETRY_MODE"),
+        (0xda, "MTX"),
+        (0xde, "DL"),
+        (0xdf, "END_DL"),
+        (0xe1, "RDP_HALF1"),
+        (0xe2, "SET_OTHER_MODE_L"),
+        (0xe3, "SET_OTHER_MODE_H"),
+        (0xe6, "RDP_LOAD_SYNC"),
+        (0xe7, "RDP_PIPE_SYNC"),
+        (0xe8, "RDP_TILE_SYNC"),
+        (0xf0, "LOAD_TLUT"),
+        (0xf2, "SET_TILE_SIZE"),
+        (0xf3, "LOAD_BLOCK"),
+        (0xf5, "SET_TILE"),
+        (0xfa, "SET_PRIM_COLOR"),
+        (0xfb, "SET_ENV_COLOR"),
+        (0xfc, "SET_COMBINE"),
+        (0xfd, "SET_TIMG"),
+    ],
+});
+
+/// The target of a texture image pointer. Its size depends on the tile that loads it.
+pub const TEXELS_DESC: TypeDescriptor = TypeDescriptor::Struct(&StructDescriptor {
+    name: "Texels",
+    size: None,
+    is_end: None,
+    fields: &[],
+});
+
+/// A fixed-point 4x4 matrix, stored as 16 integer parts followed by 16 fractional parts.
+pub const MATRIX_DESC: TypeDescriptor = TypeDescriptor::Struct(&StructDescriptor {
+    name: "Matrix",
+    size: Some(0x40),
+    is_end: None,
+    fields: &[],
+});
+
+const TEXTURE_FORMAT_DESC: &EnumDescriptor = &EnumDescriptor {
+    name: "TextureFormat",
+    underlying: PrimitiveType::U8,
+    values: &[(0, "RGBA"), (1, "YUV"), (2, "CI"), (3, "IA"), (4, "I")],
+};
+
+const TEXTURE_DEPTH_DESC: &EnumDescriptor = &EnumDescriptor {
+    name: "TextureDepth",
+    underlying: PrimitiveType::U8,
+    values: &[(0, "BITS4"), (1, "BITS8"), (2, "BITS16"), (3, "BITS32")],
+};
+
+const GEOMETRY_MODE_FLAGS_DESC: TypeDescriptor = word!(
+    "GeometryModeFlags",
+    [
+        span!("zbuffer", 0, 1),
+        span!("shade", 2, 1),
+        span!("cull_front", 9, 1),
+        span!("cull_back", 10, 1),
+        span!("fog", 16, 1),
+        span!("lighting", 17, 1),
+        span!("texture_gen", 18, 1),
+        span!("texture_gen_linear", 19, 1),
+        span!("shading_smooth", 21, 1),
+        span!("clipping", 23, 1),
+    ]
+);
+
+/// Three vertex indices, each stored doubled in one byte.
+const TRI_INDICES_DESC: TypeDescriptor = word!(
+    "TriIndices",
+    [
+        span!("index_0", 17, 0x7f),
+        span!("index_1", 9, 0x7f),
+        span!("index_2", 1, 0x7f),
+    ]
+);
+
+const TILE_SIZE_START_DESC: TypeDescriptor = word!(
+    "TileSizeStart",
+    [span!("start_s", 12, 0xfff), span!("start_t", 0, 0xfff)]
+);
+
+const NOOP_DESC: TypeDescriptor = variant!("Noop", [field!("tag" @ 4: SEGMENT_ADDR_DESC)]);
+
+const VTX_DESC: TypeDescriptor = variant!(
+    "Vtx",
+    [
+        field!("w0" @ 0: word!("VtxW0", [span!("count", 12, 0xff), span!("end", 1, 0x7f)])),
+        FieldDescriptor {
+            name: "vertices",
+            location: StructFieldLocation::Slice {
+                count_offset: 0,
+                count_desc: PrimitiveType::U32,
+                count_span: Some((12, 0xff)),
+                ptr_offset: 4,
+            },
+            desc: UNLIT_VERTEX_DESC,
+        },
+    ]
+);
+
+const CULL_DL_DESC: TypeDescriptor = variant!(
+    "CullDl",
+    [
+        field!("w0" @ 0: word!("CullDlW0", [span!("first", 1, 0xff)])),
+        field!("w1" @ 4: word!("CullDlW1", [span!("last", 1, 0xff)])),
+    ]
+);
+
+const BRANCH_Z_DESC: TypeDescriptor = variant!(
+    "BranchZ",
+    [
+        field!(
+            "w0" @ 0:
+                word!("BranchZW0", [span!("index_0", 12, 0xff), span!("index_1", 0, 0xff)])
+        ),
+        field!("compare" @ 4: U32_DESC),
+    ]
+);
+
+const TRI1_DESC: TypeDescriptor = variant!("Tri1", [field!("index" @ 0: TRI_INDICES_DESC)]);
+
+const TRI2_DESC: TypeDescriptor = variant!(
+    "Tri2",
+    [
+        field!("index_a" @ 0: TRI_INDICES_DESC),
+        field!("index_b" @ 4: TRI_INDICES_DESC),
+    ]
+);
+
+const TEXTURE_DESC: TypeDescriptor = variant!(
+    "Texture",
+    [
+        field!(
+            "w0" @ 0:
+                word!(
+                    "TextureW0",
+                    [span!("max_lod", 11, 0x7), span!("tile", 8, 0x7), span!("enable", 1, 0x7f)]
+                )
+        ),
+        field!("scale_s" @ 4: QU0_16_DESC),
+        field!("scale_t" @ 6: QU0_16_DESC),
+    ]
+);
+
+const GEOMETRY_MODE_DESC: TypeDescriptor = variant!(
+    "GeometryMode",
+    [
+        field!("keep_bits" @ 0: GEOMETRY_MODE_FLAGS_DESC),
+        field!("set_bits" @ 4: GEOMETRY_MODE_FLAGS_DESC),
+    ]
+);
+
+const MTX_DESC: TypeDescriptor = variant!(
+    "Mtx",
+    [
+        field!("params" @ 3: U8_DESC),
+        field!("matrix" @ 4: pointer!("Matrix*", MATRIX_DESC)),
+    ]
+);
+
+const DL_DESC: TypeDescriptor = variant!(
+    "Dl",
+    [
+        field!("jump" @ 1: BOOL_DESC),
+        field!("display_list" @ 4: pointer!("DisplayList*", DISPLAY_LIST_DESC)),
+    ]
+);
+
+const END_DL_DESC: TypeDescriptor = variant!("EndDl", []);
+
+const RDP_HALF1_DESC: TypeDescriptor = variant!("RdpHalf1", [field!("word" @ 4: U32_DESC)]);
+
+/// The modified bits start at `32 - shift_complement - (length_minus_one + 1)`.
+const SET_OTHER_MODE_L_DESC: TypeDescriptor = variant!(
+    "SetOtherModeL",
+    [
+        field!("shift_complement" @ 2: U8_DESC),
+        field!("length_minus_one" @ 3: U8_DESC),
+        field!("set_bits" @ 4: U32_DESC),
+    ]
+);
+
+/// Encoded like [`SET_OTHER_MODE_L_DESC`].
+const SET_OTHER_MODE_H_DESC: TypeDescriptor = variant!(
+    "SetOtherModeH",
+    [
+        field!("shift_complement" @ 2: U8_DESC),
+        field!("length_minus_one" @ 3: U8_DESC),
+        field!("set_bits" @ 4: U32_DESC),
+    ]
+);
+
+const RDP_LOAD_SYNC_DESC: TypeDescriptor = variant!("RdpLoadSync", []);
+
+const RDP_PIPE_SYNC_DESC: TypeDescriptor = variant!("RdpPipeSync", []);
+
+const RDP_TILE_SYNC_DESC: TypeDescriptor = variant!("RdpTileSync", []);
+
+const LOAD_TLUT_DESC: TypeDescriptor = variant!(
+    "LoadTlut",
+    [field!(
+        "w1" @ 4:
+            word!("LoadTlutW1", [span!("tile", 24, 0x7), span!("count_minus_one", 14, 0x3ff)])
+    )]
+);
+
+const SET_TILE_SIZE_DESC: TypeDescriptor = variant!(
+    "SetTileSize",
+    [
+        field!("w0" @ 0: TILE_SIZE_START_DESC),
+        field!(
+            "w1" @ 4:
+                word!(
+                    "SetTileSizeW1",
+                    [span!("tile", 24, 0x7), span!("end_s", 12, 0xfff), span!("end_t", 0, 0xfff)]
+                )
+        ),
+    ]
+);
+
+const LOAD_BLOCK_DESC: TypeDescriptor = variant!(
+    "LoadBlock",
+    [
+        field!("w0" @ 0: TILE_SIZE_START_DESC),
+        field!(
+            "w1" @ 4:
+                word!("LoadBlockW1", [span!("tile", 24, 0x7), span!("texels_minus_one", 12, 0xfff)])
+        ),
+        field!("dxt" @ 6: QU1_11_DESC),
+    ]
+);
+
+const SET_TILE_DESC: TypeDescriptor = variant!(
+    "SetTile",
+    [
+        field!(
+            "w0" @ 0:
+                word!(
+                    "SetTileW0",
+                    [
+                        span!("format", 21, 0x7, TEXTURE_FORMAT_DESC),
+                        span!("depth", 19, 0x3, TEXTURE_DEPTH_DESC),
+                        span!("stride", 9, 0x1ff),
+                        span!("addr", 0, 0x1ff),
+                    ]
+                )
+        ),
+        field!(
+            "w1" @ 4:
+                word!(
+                    "SetTileW1",
+                    [
+                        span!("tile", 24, 0x7),
+                        span!("palette", 20, 0xf),
+                        span!("clamp_t", 19, 1),
+                        span!("mirror_t", 18, 1),
+                        span!("mask_t", 14, 0xf),
+                        span!("shift_t", 10, 0xf),
+                        span!("clamp_s", 9, 1),
+                        span!("mirror_s", 8, 1),
+                        span!("mask_s", 4, 0xf),
+                        span!("shift_s", 0, 0xf),
+                    ]
+                )
+        ),
+    ]
+);
+
+const SET_PRIM_COLOR_DESC: TypeDescriptor = variant!(
+    "SetPrimColor",
+    [
+        field!("min_lod" @ 2: U8_DESC),
+        field!("lod_fraction" @ 3: U8_DESC),
+        field!("r" @ 4: U8_DESC),
+        field!("g" @ 5: U8_DESC),
+        field!("b" @ 6: U8_DESC),
+        field!("a" @ 7: U8_DESC),
+    ]
+);
+
+const SET_ENV_COLOR_DESC: TypeDescriptor = variant!(
+    "SetEnvColor",
+    [
+        field!("r" @ 4: U8_DESC),
+        field!("g" @ 5: U8_DESC),
+        field!("b" @ 6: U8_DESC),
+        field!("a" @ 7: U8_DESC),
+    ]
+);
+
+const SET_COMBINE_DESC: TypeDescriptor = variant!(
+    "SetCombine",
+    [
+        field!(
+            "w0" @ 0:
+                word!(
+                    "SetCombineW0",
+                    [
+                        span!("color_0_a", 20, 0xf),
+                        span!("color_0_c", 15, 0x1f),
+                        span!("alpha_0_a", 12, 0x7),
+                        span!("alpha_0_c", 9, 0x7),
+                        span!("color_1_a", 5, 0xf),
+                        span!("color_1_c", 0, 0x1f),
+                    ]
+                )
+        ),
+        field!(
+            "w1" @ 4:
+                word!(
+                    "SetCombineW1",
+                    [
+                        span!("color_0_b", 28, 0xf),
+                        span!("color_1_b", 24, 0xf),
+                        span!("alpha_1_a", 21, 0x7),
+                        span!("alpha_1_c", 18, 0x7),
+                        span!("color_0_d", 15, 0x7),
+                        span!("alpha_0_b", 12, 0x7),
+                        span!("alpha_0_d", 9, 0x7),
+                        span!("color_1_d", 6, 0x7),
+                        span!("alpha_1_b", 3, 0x7),
+                        span!("alpha_1_d", 0, 0x7),
+                    ]
+                )
+        ),
+    ]
+);
+
+const SET_TIMG_DESC: TypeDescriptor = variant!(
+    "SetTimg",
+    [
+        field!(
+            "w0" @ 0:
+                word!(
+                    "SetTimgW0",
+                    [
+                        span!("format", 21, 0x7, TEXTURE_FORMAT_DESC),
+                        span!("depth", 19, 0x3, TEXTURE_DEPTH_DESC),
+                        span!("width_minus_one", 0, 0xfff),
+                    ]
+                )
+        ),
+        field!("texels" @ 4: pointer!("Texels*", TEXELS_DESC)),
+    ]
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oot_explorer_reflect::{Query, QueryTarget, Value, ValueKind, XrefIndex};
+    use oot_explorer_segment::{Segment, SegmentTable};
+    use oot_explorer_vrom::{Vrom, VromAddr};
+
+    #[test]
+    fn vtx_vertices_are_counted() {
+        let mut data = vec![0x01, 0x00, 0x30, 0x06, 0x03, 0x00, 0x00, 0x08];
+        data.extend((0..0x30).map(|i| i as u8));
+        let segment_table = SegmentTable::new().with(Segment::ROOM, VromAddr(0));
+        let value = Value::read(Vrom(&data), &segment_table, INSTRUCTION_DESC, VromAddr(0));
+        let vertices = value.field("vertices").unwrap();
+        match &vertices.kind {
+            ValueKind::Slice {
+                count, elements, ..
+            } => {
+                assert_eq!(*count, 3);
+                let addrs: Vec<_> = elements.iter().map(|vertex| vertex.addr).collect();
+                assert_eq!(addrs, [0x08, 0x18, 0x28]);
+            }
+            kind => panic!("expected a slice, got {:?}", kind),
+        }
+    }
+
+    #[test]
+    fn vtx_refers_to_all_of_its_vertices() {
+        let mut data = vec![0x01, 0x00, 0x30, 0x06, 0x03, 0x00, 0x00, 0x08];
+        data.extend((0..0x30).map(|i| i as u8));
+        let segment_table = SegmentTable::new().with(Segment::ROOM, VromAddr(0));
+        let mut index = XrefIndex::new();
+        index.add(
+            Vrom(&data),
+            &segment_table,
+            &Query::parse("vtx").unwrap(),
+            &QueryTarget::root(INSTRUCTION_DESC, VromAddr(0)),
+        );
+        let references = index.references_to(VromAddr(0x37));
+        assert_eq!(references.len(), 1);
+        assert_eq!(references[0].query.to_string(), "vtx.vertices");
+        assert_eq!(references[0].target, VromAddr(0x08)..VromAddr(0x38));
+        assert!(index.references_to(VromAddr(0x38)).is_empty());
+    }
+
+    #[test]
+    fn variants_match_opcodes() {
+        let TypeDescriptor::Union(union_desc) = INSTRUCTION_DESC else {
+            unreachable!()
+        };
+        let TypeDescriptor::Enum(opcode_desc) = OPCODE_DESC else {
+            unreachable!()
+        };
+        assert_eq!(union_desc.variants.len(), opcode_desc.values.len());
+        for (&(value, variant), &(opcode, name)) in
+            union_desc.variants.iter().zip(opcode_desc.values)
+        {
+            assert_eq!(value, opcode);
+            let snake_upper: String = variant
+                .name()
+                .chars()
+                .enumerate()
+                .flat_map(|(i, c)| {
+                    let sep = (i > 0 && c.is_ascii_uppercase()).then_some('_');
+                    sep.into_iter().chain(Some(c.to_ascii_uppercase()))
+                })
+                .collect();
+            assert_eq!(snake_upper, name);
+        }
+        assert!(union_desc.variants.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+}
@@ -1,21 +1,88 @@
-use oot_explorer_reflect::{I16_DESC, U16_DESC, U32_DESC};
+use oot_explorer_derive::layout;
+use oot_explorer_read::{FromVrom, MAX_INFERRED_LEN};
+use oot_explorer_reflect::I16_DESC;
+use oot_explorer_segment::{SegmentAddr, SegmentTable};
+use oot_explorer_vrom::{Vrom, VromAddr};
 
-compile_interfaces! {
-    #[layout(size = 0x10, align_bits = 1)]
-    struct Actor {
-        u16 actor_number @ 0;
-        i16 pos_x @ 2;
-        i16 pos_y @ 4;
-        i16 pos_z @ 6;
-        i16 angle_x @ 8;
-        i16 angle_y @ 0xa;
-        i16 angle_z @ 0xc;
-        u16 init @ 0xe;
+use crate::actor::{ActorId, ActorParams, ACTOR_ID_DESC, ACTOR_PARAMS_DESC};
+
+#[layout(size = 0x10, align_bits = 1)]
+pub struct Actor {
+    #[at(0)]
+    actor_number: ActorId,
+    #[at(2)]
+    pos_x: i16,
+    #[at(4)]
+    pos_y: i16,
+    #[at(6)]
+    pos_z: i16,
+    #[at(8)]
+    angle_x: i16,
+    #[at(0xa)]
+    angle_y: i16,
+    #[at(0xc)]
+    angle_z: i16,
+    #[at(0xe)]
+    init: ActorParams,
+}
+
+/// Infers the length of an alternate header list, which holds a NULL or segmented pointer to a
+/// header list for each setup after the default one. The header lists conventionally follow the
+/// alternate header list, so it ends before the earliest one or at the first pointer that doesn't
+/// resolve.
+pub fn infer_alternate_header_count(
+    vrom: Vrom<'_>,
+    segment_table: &SegmentTable,
+    addr: VromAddr,
+) -> u32 {
+    let mut end = VromAddr(addr.0.saturating_add(MAX_INFERRED_LEN * 4));
+    let mut count = 0;
+    while addr + count * 4 < end {
+        match SegmentAddr::from_vrom(vrom, addr + count * 4) {
+            Ok(segment_addr) if segment_addr.is_null() => (),
+            Ok(segment_addr) => match segment_table.resolve(segment_addr) {
+                Ok(target) if target > addr => end = end.min(target),
+                _ => break,
+            },
+            Err(_) => break,
+        }
+        count += 1;
+    }
+    count
+}
+
+/// The indices into an alternate header list that the game tries for `setup`, in order, before
+/// falling back to the default headers. Adult night falls back to adult day.
+pub fn alternate_header_indices(setup: u32) -> impl Iterator<Item = u32> {
+    let fallback = if setup == 3 { Some(1) } else { None };
+    setup.checked_sub(1).into_iter().chain(fallback)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oot_explorer_segment::Segment;
+
+    #[test]
+    fn alternate_header_count_stops_at_first_header_list() {
+        let data = [
+            0x00, 0x00, 0x00, 0x00, // setup 1
+            0x02, 0x00, 0x00, 0x10, // setup 2
+            0x02, 0x00, 0x00, 0x18, // setup 3
+            0x02, 0x00, 0x00, 0x10, // setup 4
+            0x15, 0x00, 0x00, 0x00, // setup 2's headers
+            0x14, 0x00, 0x00, 0x00, 0x14, 0x00, 0x00, 0x00, // setup 3's headers
+        ];
+        let segment_table = SegmentTable::new().with(Segment::SCENE, VromAddr(0));
+        let count = infer_alternate_header_count(Vrom(&data), &segment_table, VromAddr(0));
+        assert_eq!(count, 4);
     }
 
-    #[layout(size = 8, align_bits = 2)]
-    struct AlternateHeadersHeader {
-        // TODO: Type this.
-        u32 ptr @4;
+    #[test]
+    fn adult_night_falls_back_to_adult_day() {
+        assert_eq!(alternate_header_indices(0).collect::<Vec<_>>(), vec![]);
+        assert_eq!(alternate_header_indices(1).collect::<Vec<_>>(), vec![0]);
+        assert_eq!(alternate_header_indices(3).collect::<Vec<_>>(), vec![2, 1]);
+        assert_eq!(alternate_header_indices(4).collect::<Vec<_>>(), vec![3]);
     }
 }
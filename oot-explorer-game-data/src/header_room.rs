@@ -1,92 +1,125 @@
-use oot_explorer_read::{is_end, Sentinel};
+use oot_explorer_derive::{layout, primitive_enum};
+use oot_explorer_read::{is_end, Sentinel, Slice};
 use oot_explorer_reflect::{BOOL_DESC, I8_DESC, U16_DESC, U8_DESC};
-use oot_explorer_vrom::{Vrom, VromAddr};
+use oot_explorer_segment::{SegmentError, SegmentTable};
+use oot_explorer_vrom::Vrom;
 
-use crate::header_common::{
-    Actor, AlternateHeadersHeader, ACTOR_DESC, ALTERNATE_HEADERS_HEADER_DESC,
-};
-use crate::mesh::{Mesh, MESH_PTR_DESC};
+use crate::header_common::{infer_alternate_header_count, Actor, ACTOR_DESC};
+use crate::mesh::{Mesh, MESH_DESC};
 use crate::object::{ObjectId, OBJECT_ID_DESC};
+use crate::room::{Room, ROOM_DESC};
+
+#[primitive_enum(u8)]
+pub enum RoomHeaderType {
+    ACTOR_LIST = 0x01,
+    WIND = 0x05,
+    BEHAVIOR = 0x08,
+    MESH = 0x0a,
+    OBJECT_LIST = 0x0b,
+    TIME = 0x10,
+    SKYBOX = 0x12,
+    END = 0x14,
+    SOUND = 0x16,
+    ALTERNATE_HEADERS = 0x18,
+}
 
-compile_interfaces! {
-    enum RoomHeaderType: u8 {
-        ACTOR_LIST = 0x01;
-        WIND = 0x05;
-        BEHAVIOR = 0x08;
-        MESH = 0x0a;
-        OBJECT_LIST = 0x0b;
-        TIME = 0x10;
-        SKYBOX = 0x12;
-        END = 0x14;
-        SOUND = 0x16;
-        ALTERNATE_HEADERS = 0x18;
-    }
+#[layout(size = 8, align_bits = 2)]
+#[discriminant(RoomHeaderType @ 0)]
+#[is_end(is_end::<RoomHeader>)]
+#[end_discriminant(RoomHeaderType::END)]
+pub enum RoomHeader {
+    ActorList(ActorListHeader) = RoomHeaderType::ACTOR_LIST,
+    Wind(WindHeader) = RoomHeaderType::WIND,
+    Behavior(BehaviorHeader) = RoomHeaderType::BEHAVIOR,
+    Mesh(MeshHeader) = RoomHeaderType::MESH,
+    ObjectList(ObjectListHeader) = RoomHeaderType::OBJECT_LIST,
+    Time(TimeHeader) = RoomHeaderType::TIME,
+    Skybox(RoomSkyboxHeader) = RoomHeaderType::SKYBOX,
+    End(EndHeader) = RoomHeaderType::END,
+    Sound(RoomSoundHeader) = RoomHeaderType::SOUND,
+    AlternateHeaders(RoomAlternateHeadersHeader) = RoomHeaderType::ALTERNATE_HEADERS,
+}
 
-    #[is_end(is_end::<RoomHeader>)]
-    #[layout(size = 8, align_bits = 2)]
-    union RoomHeader: RoomHeaderType @0 {
-        struct ActorListHeader actor_list #RoomHeaderType::ACTOR_LIST;
-        struct WindHeader wind #RoomHeaderType::WIND;
-        struct BehaviorHeader behavior #RoomHeaderType::BEHAVIOR;
-        struct MeshHeader mesh #RoomHeaderType::MESH;
-        struct ObjectListHeader object_list #RoomHeaderType::OBJECT_LIST;
-        struct TimeHeader time #RoomHeaderType::TIME;
-        struct RoomSkyboxHeader skybox #RoomHeaderType::SKYBOX;
-        struct EndHeader end #RoomHeaderType::END;
-        struct RoomSoundHeader sound #RoomHeaderType::SOUND;
-        struct AlternateHeadersHeader alternate_headers #RoomHeaderType::ALTERNATE_HEADERS;
-    }
+#[layout(size = 8, align_bits = 2)]
+pub struct ActorListHeader {
+    #[at(4)]
+    #[count(u8 @ 1)]
+    actor_list: *const [Actor],
+}
 
-    #[layout(size = 8, align_bits = 2)]
-    struct ActorListHeader {
-        struct Actor[u8 @1]* actor_list @4;
-    }
+#[layout(size = 8, align_bits = 2)]
+pub struct WindHeader {
+    #[at(4)]
+    west: i8,
+    #[at(5)]
+    up: i8,
+    #[at(6)]
+    south: i8,
+    #[at(7)]
+    strength: u8,
+}
 
-    #[layout(size = 8, align_bits = 2)]
-    struct WindHeader {
-        i8 west @4;
-        i8 up @5;
-        i8 south @6;
-        u8 strength @7;
-    }
+#[layout(size = 8, align_bits = 2)]
+pub struct BehaviorHeader {
+    // Affects Sun's Song, backflipping with A.
+    #[at(1)]
+    x: u8,
+    #[at(6)]
+    flags: u8,
+    #[at(7)]
+    idle_animation_or_heat: u8,
+}
 
-    #[layout(size = 8, align_bits = 2)]
-    struct BehaviorHeader {
-        // Affects Sun's Song, backflipping with A.
-        u8 x @1;
-        u8 flags @6;
-        u8 idle_animation_or_heat @7;
-    }
+#[layout(size = 8, align_bits = 2)]
+pub struct MeshHeader {
+    #[at(4)]
+    mesh: *const Mesh,
+}
 
-    #[layout(size = 8, align_bits = 2)]
-    struct MeshHeader {
-        struct Mesh* mesh @4;
-    }
+#[layout(size = 8, align_bits = 2)]
+pub struct ObjectListHeader {
+    #[at(4)]
+    #[count(u8 @ 1)]
+    objects: *const [ObjectId],
+}
 
-    #[layout(size = 8, align_bits = 2)]
-    struct ObjectListHeader {
-        ObjectId[u8 @1]* objects @4;
-    }
+#[layout(size = 8, align_bits = 2)]
+pub struct TimeHeader {
+    #[at(4)]
+    raw_time_override: u16,
+    #[at(6)]
+    time_speed: i8,
+}
 
-    #[layout(size = 8, align_bits = 2)]
-    struct TimeHeader {
-        u16 raw_time_override @4;
-        i8 time_speed @6;
-    }
+#[layout(size = 8, align_bits = 2)]
+pub struct RoomSkyboxHeader {
+    #[at(4)]
+    disable_sky: bool,
+    #[at(5)]
+    disable_sun_moon: bool,
+}
 
-    #[layout(size = 8, align_bits = 2)]
-    struct RoomSkyboxHeader {
-        bool disable_sky @4;
-        bool disable_sun_moon @5;
-    }
+#[layout(size = 8, align_bits = 2)]
+pub struct EndHeader {}
+
+#[layout(size = 8, align_bits = 2)]
+pub struct RoomSoundHeader {
+    #[at(7)]
+    echo: u8,
+}
 
-    #[layout(size = 8, align_bits = 2)]
-    struct EndHeader {}
+#[layout(size = 8, align_bits = 2)]
+pub struct RoomAlternateHeadersHeader {
+    #[at(4)]
+    #[infer_len(infer_alternate_header_count)]
+    setups: *const [RoomSetup],
+}
 
-    #[layout(size = 8, align_bits = 2)]
-    struct RoomSoundHeader {
-        u8 echo @7;
-    }
+/// The headers of an alternate setup, numbered as for [`SceneSetup`](crate::header_scene::SceneSetup).
+#[layout(size = 4, align_bits = 2)]
+pub struct RoomSetup {
+    #[at(0)]
+    headers: Option<*const Room>,
 }
 
 impl Sentinel for RoomHeader {
@@ -97,6 +130,21 @@ impl Sentinel for RoomHeader {
     }
 }
 
+impl RoomAlternateHeadersHeader {
+    pub fn setups_auto_len(
+        self,
+        vrom: Vrom<'_>,
+        segment_table: &SegmentTable,
+    ) -> Result<Slice<RoomSetup>, SegmentError> {
+        let addr = self.setups(vrom, segment_table, 0)?.addr();
+        self.setups(
+            vrom,
+            segment_table,
+            infer_alternate_header_count(vrom, segment_table, addr),
+        )
+    }
+}
+
 impl BehaviorHeader {
     // TODO: Codegen for bitfields.
     pub fn disable_warp_songs(self, vrom: Vrom<'_>) -> u8 {
@@ -1,179 +1,263 @@
 use std::ops::Range;
 
-use oot_explorer_read::{is_end, ReadError, Sentinel};
+use oot_explorer_derive::{layout, primitive_enum};
+use oot_explorer_read::{
+    is_end, FromVrom, InferLen, Layout, ReadError, Sentinel, Slice, UntilNext, Vec3s,
+    MAX_INFERRED_LEN,
+};
 use oot_explorer_reflect::{
-    RangeSourced, BOOL_DESC, I16_DESC, U16_DESC, U32_DESC, U8_DESC, VROM_ADDR_DESC,
+    RangeSourced, BOOL_DESC, I16_DESC, U16_DESC, U8_DESC, VEC3S_DESC, VROM_ADDR_DESC,
 };
+use oot_explorer_segment::{Segment, SegmentAddr, SegmentError, SegmentTable};
 use oot_explorer_vrom::{Vrom, VromAddr};
 
-use crate::collision::{Collision, COLLISION_PTR_DESC};
-use crate::header_common::{
-    Actor, AlternateHeadersHeader, ACTOR_DESC, ALTERNATE_HEADERS_HEADER_DESC,
-};
+use crate::actor::{ActorId, TransitionActorParams, ACTOR_ID_DESC, TRANSITION_ACTOR_PARAMS_DESC};
+use crate::collision::{Collision, COLLISION_DESC};
+use crate::cutscene::{Cutscene, CUTSCENE_DESC};
+use crate::header_common::{infer_alternate_header_count, Actor, ACTOR_DESC};
 use crate::room::Room;
-use crate::scene::{Lighting, LIGHTING_DESC};
-
-compile_interfaces! {
-    enum SceneHeaderType: u8 {
-        START_POSITIONS = 0x00;
-        COLLISION = 0x03;
-        ROOM_LIST = 0x04;
-        ENTRANCE_LIST = 0x06;
-        SPECIAL_OBJECTS = 0x07;
-        PATHWAYS = 0x0d;
-        TRANSITION_ACTORS = 0x0e;
-        LIGHTING = 0x0f;
-        SKYBOX = 0x11;
-        EXIT_LIST = 0x13;
-        END = 0x14;
-        SOUND = 0x15;
-        ALTERNATE_HEADERS = 0x18;
-        CAMERA_AND_WORLD_MAP = 0x19;
-    }
+use crate::scene::{Lighting, Scene, LIGHTING_DESC, SCENE_DESC};
 
-    #[is_end(is_end::<SceneHeader>)]
-    #[layout(size = 8, align_bits = 2)]
-    union SceneHeader: SceneHeaderType @0 {
-        struct StartPositionsHeader start_positions #SceneHeaderType::START_POSITIONS;
-        struct CollisionHeader collision #SceneHeaderType::COLLISION;
-        struct RoomListHeader room_list #SceneHeaderType::ROOM_LIST;
-        struct EntranceListHeader entrance_list #SceneHeaderType::ENTRANCE_LIST;
-        struct SpecialObjectsHeader special_objects #SceneHeaderType::SPECIAL_OBJECTS;
-        struct PathwaysHeader pathways #SceneHeaderType::PATHWAYS;
-        struct TransitionActorsHeader transition_actors #SceneHeaderType::TRANSITION_ACTORS;
-        struct LightingHeader lighting #SceneHeaderType::LIGHTING;
-        struct SceneSkyboxHeader skybox #SceneHeaderType::SKYBOX;
-        struct ExitListHeader exit_list #SceneHeaderType::EXIT_LIST;
-        struct EndHeader end #SceneHeaderType::END;
-        struct SceneSoundHeader sound #SceneHeaderType::SOUND;
-        struct AlternateHeadersHeader alternate_headers #SceneHeaderType::ALTERNATE_HEADERS;
-        struct CameraAndWorldMapHeader camera_and_world_map #SceneHeaderType::CAMERA_AND_WORLD_MAP;
-    }
+#[primitive_enum(u8)]
+pub enum SceneHeaderType {
+    START_POSITIONS = 0x00,
+    COLLISION = 0x03,
+    ROOM_LIST = 0x04,
+    ENTRANCE_LIST = 0x06,
+    SPECIAL_OBJECTS = 0x07,
+    PATHWAYS = 0x0d,
+    TRANSITION_ACTORS = 0x0e,
+    LIGHTING = 0x0f,
+    SKYBOX = 0x11,
+    EXIT_LIST = 0x13,
+    END = 0x14,
+    SOUND = 0x15,
+    CUTSCENE = 0x17,
+    ALTERNATE_HEADERS = 0x18,
+    CAMERA_AND_WORLD_MAP = 0x19,
+}
 
-    #[layout(size = 8, align_bits = 2)]
-        struct StartPositionsHeader {
-        struct Actor[u8 @1]* start_positions @4;
-    }
+#[layout(size = 8, align_bits = 2)]
+#[discriminant(SceneHeaderType @ 0)]
+#[is_end(is_end::<SceneHeader>)]
+#[end_discriminant(SceneHeaderType::END)]
+pub enum SceneHeader {
+    StartPositions(StartPositionsHeader) = SceneHeaderType::START_POSITIONS,
+    Collision(CollisionHeader) = SceneHeaderType::COLLISION,
+    RoomList(RoomListHeader) = SceneHeaderType::ROOM_LIST,
+    EntranceList(EntranceListHeader) = SceneHeaderType::ENTRANCE_LIST,
+    SpecialObjects(SpecialObjectsHeader) = SceneHeaderType::SPECIAL_OBJECTS,
+    Pathways(PathwaysHeader) = SceneHeaderType::PATHWAYS,
+    TransitionActors(TransitionActorsHeader) = SceneHeaderType::TRANSITION_ACTORS,
+    Lighting(LightingHeader) = SceneHeaderType::LIGHTING,
+    Skybox(SceneSkyboxHeader) = SceneHeaderType::SKYBOX,
+    ExitList(ExitListHeader) = SceneHeaderType::EXIT_LIST,
+    End(EndHeader) = SceneHeaderType::END,
+    Sound(SceneSoundHeader) = SceneHeaderType::SOUND,
+    Cutscene(CutsceneHeader) = SceneHeaderType::CUTSCENE,
+    AlternateHeaders(SceneAlternateHeadersHeader) = SceneHeaderType::ALTERNATE_HEADERS,
+    CameraAndWorldMap(CameraAndWorldMapHeader) = SceneHeaderType::CAMERA_AND_WORLD_MAP,
+}
 
-    #[layout(size = 8, align_bits = 2)]
-    struct CollisionHeader {
-        struct Collision* ptr @4;
-    }
+#[layout(size = 8, align_bits = 2)]
+pub struct StartPositionsHeader {
+    #[at(4)]
+    #[count(u8 @ 1)]
+    start_positions: *const [Actor],
+}
 
-    #[layout(size = 8, align_bits = 2)]
-    struct RoomListHeader {
-        u8 room_count @1;
-        struct RoomListEntry[u8 @1]* room_list @4;
-    }
+#[layout(size = 8, align_bits = 2)]
+pub struct CollisionHeader {
+    #[at(4)]
+    ptr: *const Collision,
+}
 
-    #[layout(size = 0x8, align_bits = 2)]
-    struct RoomListEntry {
-        VromAddr start @0;
-        VromAddr end @4;
-    }
+#[layout(size = 8, align_bits = 2)]
+pub struct RoomListHeader {
+    #[at(1)]
+    room_count: u8,
+    #[at(4)]
+    #[count(u8 @ 1)]
+    room_list: *const [RoomListEntry],
+}
 
-    #[layout(size = 8, align_bits = 2)]
-    struct EntranceListHeader {
-        // TODO: Type this as Entrance[?]*.
-        u32 ptr @4;
-    }
+#[layout(size = 0x8, align_bits = 2)]
+pub struct RoomListEntry {
+    #[at(0)]
+    start: VromAddr,
+    #[at(4)]
+    end: VromAddr,
+}
 
-    #[layout(size = 2, align_bits = 0)]
-    struct Entrance {
-        u8 start_position @0;
-        u8 room @1;
-    }
+#[layout(size = 8, align_bits = 2)]
+pub struct EntranceListHeader {
+    #[at(4)]
+    #[infer_len(infer_len_until_next_scene_data::<Entrance>)]
+    entrance_list: *const [Entrance],
+}
 
-    #[layout(size = 8, align_bits = 2)]
-    struct SpecialObjectsHeader {
-        ElfMessage elf_message @1;
-        GlobalObject global_object @6;
-    }
+#[layout(size = 2, align_bits = 0)]
+pub struct Entrance {
+    #[at(0)]
+    start_position: u8,
+    #[at(1)]
+    room: u8,
+}
 
-    enum ElfMessage: u8 {
-        NONE = 0x00;
-        FIELD = 0x01;
-        YDAN = 0x02;
-    }
+#[layout(size = 8, align_bits = 2)]
+pub struct SpecialObjectsHeader {
+    #[at(1)]
+    elf_message: ElfMessage,
+    #[at(6)]
+    global_object: GlobalObject,
+}
 
-    enum GlobalObject: u16 {
-        NONE = 0x0000;
-        FIELD = 0x0002;
-        DANGEON = 0x0003;
-    }
+#[primitive_enum(u8)]
+pub enum ElfMessage {
+    NONE = 0x00,
+    FIELD = 0x01,
+    YDAN = 0x02,
+}
 
-    #[layout(size = 8, align_bits = 2)]
-    struct PathwaysHeader {
-        // TODO: Type this.
-        u32 ptr @4;
-    }
+#[primitive_enum(u16)]
+pub enum GlobalObject {
+    NONE = 0x0000,
+    FIELD = 0x0002,
+    DANGEON = 0x0003,
+}
 
-    #[layout(size = 8, align_bits = 2)]
-    struct TransitionActorsHeader {
-        struct TransitionActor[u8 @1]* transition_actors @4;
-    }
+#[layout(size = 8, align_bits = 2)]
+pub struct PathwaysHeader {
+    #[at(4)]
+    #[infer_len(infer_path_count)]
+    paths: *const [Path],
+}
 
-    #[layout(size = 0x10, align_bits = 1)]
-    struct TransitionActor {
-        u8 room_from_front @0;
-        u8 camera_from_front @1;
-        u8 room_from_back @2;
-        u8 camera_from_back @3;
-        u16 actor_number @4;
-        i16 pos_x @6;
-        i16 pos_y @8;
-        i16 pos_z @0xa;
-        i16 rot_y @0xc;
-        u16 init @0xe;
-    }
+/// A route through the scene, followed by NPCs, cameras, and moving platforms.
+#[layout(size = 8, align_bits = 2)]
+pub struct Path {
+    #[at(0)]
+    point_count: u8,
+    #[at(4)]
+    #[count(u8 @ 0)]
+    points: *const [Vec3s],
+}
 
-    #[layout(size = 8, align_bits = 2)]
-    struct LightingHeader {
-        struct Lighting[u8 @1]* lighting @4;
-    }
+#[layout(size = 8, align_bits = 2)]
+pub struct TransitionActorsHeader {
+    #[at(4)]
+    #[count(u8 @ 1)]
+    transition_actors: *const [TransitionActor],
+}
 
-    #[layout(size = 8, align_bits = 2)]
-    struct SceneSkyboxHeader {
-        u8 skybox @4;
-        bool cloudy @5;
-        bool indoor_lighting @6;
-    }
+#[layout(size = 0x10, align_bits = 1)]
+pub struct TransitionActor {
+    #[at(0)]
+    room_from_front: u8,
+    #[at(1)]
+    camera_from_front: u8,
+    #[at(2)]
+    room_from_back: u8,
+    #[at(3)]
+    camera_from_back: u8,
+    #[at(4)]
+    actor_number: ActorId,
+    #[at(6)]
+    pos_x: i16,
+    #[at(8)]
+    pos_y: i16,
+    #[at(0xa)]
+    pos_z: i16,
+    #[at(0xc)]
+    rot_y: i16,
+    #[at(0xe)]
+    init: TransitionActorParams,
+}
 
-    #[layout(size = 8, align_bits = 2)]
-    struct ExitListHeader {
-        // TODO: Type this as Exit[?]*.
-        u32 ptr @4;
-    }
+#[layout(size = 8, align_bits = 2)]
+pub struct LightingHeader {
+    #[at(4)]
+    #[count(u8 @ 1)]
+    lighting: *const [Lighting],
+}
 
-    #[layout(size = 2, align_bits = 1)]
-    struct Exit {
-        u16 value @0;
-    }
+#[layout(size = 8, align_bits = 2)]
+pub struct SceneSkyboxHeader {
+    #[at(4)]
+    skybox: u8,
+    #[at(5)]
+    cloudy: bool,
+    #[at(6)]
+    indoor_lighting: bool,
+}
 
-    #[layout(size = 8, align_bits = 2)]
-    struct EndHeader {}
+#[layout(size = 8, align_bits = 2)]
+pub struct ExitListHeader {
+    #[at(4)]
+    #[infer_len(infer_len_until_next_scene_data::<Exit>)]
+    exit_list: *const [Exit],
+}
 
-    #[layout(size = 8, align_bits = 2)]
-    struct SceneSoundHeader {
-        u8 settings @1;
-        u8 night_sound @6;
-        u8 music @7;
-    }
+/// A way out of the scene, used by collision polygons and transition actors that refer to it by
+/// position in the exit list.
+#[layout(size = 2, align_bits = 1)]
+pub struct Exit {
+    /// An index into the global entrance table.
+    #[at(0)]
+    entrance_index: u16,
+}
 
-    #[layout(size = 8, align_bits = 2)]
-    struct CameraAndWorldMapHeader {
-        Camera camera @1;
-        u8 world_map_location @7;
-    }
+#[layout(size = 8, align_bits = 2)]
+pub struct EndHeader {}
 
-    enum Camera: u8 {
-        FREE = 0x00;
-        FIXED_WITH_ALTERNATE = 0x10;
-        ROTATE_WITH_ALTERNATE = 0x20;
-        FIXED = 0x30;
-        ROTATE = 0x40;
-        SHOOTING_GALLERY = 0x50;
-    }
+#[layout(size = 8, align_bits = 2)]
+pub struct SceneSoundHeader {
+    #[at(1)]
+    settings: u8,
+    #[at(6)]
+    night_sound: u8,
+    #[at(7)]
+    music: u8,
+}
+
+#[layout(size = 8, align_bits = 2)]
+pub struct CutsceneHeader {
+    #[at(4)]
+    cutscene: *const Cutscene,
+}
+
+#[layout(size = 8, align_bits = 2)]
+pub struct SceneAlternateHeadersHeader {
+    #[at(4)]
+    #[infer_len(infer_alternate_header_count)]
+    setups: *const [SceneSetup],
+}
+
+/// The headers of an alternate setup. Element `i` of an alternate header list is setup `i + 1`,
+/// where setups 1 to 3 are child night, adult day, and adult night, and later setups are used by
+/// cutscenes. A NULL pointer means the setup falls back to another one.
+#[layout(size = 4, align_bits = 2)]
+pub struct SceneSetup {
+    #[at(0)]
+    headers: Option<*const Scene>,
+}
+
+#[layout(size = 8, align_bits = 2)]
+pub struct CameraAndWorldMapHeader {
+    #[at(1)]
+    camera: Camera,
+    #[at(7)]
+    world_map_location: u8,
+}
+
+#[primitive_enum(u8)]
+pub enum Camera {
+    FREE = 0x00,
+    FIXED_WITH_ALTERNATE = 0x10,
+    ROTATE_WITH_ALTERNATE = 0x20,
+    FIXED = 0x30,
+    ROTATE = 0x40,
+    SHOOTING_GALLERY = 0x50,
 }
 
 impl Sentinel for SceneHeader {
@@ -184,6 +268,108 @@ impl Sentinel for SceneHeader {
     }
 }
 
+/// Infers the length of a list in the scene that `segment_table` maps as the number of whole
+/// elements that fit before the next structure that the scene's headers point to.
+pub fn infer_len_until_next_scene_data<T: Layout>(
+    vrom: Vrom<'_>,
+    segment_table: &SegmentTable,
+    addr: VromAddr,
+) -> u32 {
+    match current_scene(vrom, segment_table) {
+        Some(scene) => {
+            UntilNext(&scene.known_data_addrs(vrom, segment_table)).infer_len(addr, T::SIZE)
+        }
+        None => 0,
+    }
+}
+
+/// Infers the number of paths in the path list at `addr`.
+///
+/// The list is bounded by the next structure that the scene's headers point to, or by
+/// [`MAX_INFERRED_LEN`] if the list comes last. It ends early at the first entry that doesn't look
+/// like a path: one with no points, or whose points don't resolve through `segment_table`.
+pub fn infer_path_count(vrom: Vrom<'_>, segment_table: &SegmentTable, addr: VromAddr) -> u32 {
+    let bound = match infer_len_until_next_scene_data::<Path>(vrom, segment_table, addr) {
+        0 => MAX_INFERRED_LEN,
+        len => len,
+    };
+    Slice::<Path>::new(addr, bound)
+        .iter(vrom)
+        .take_while(|path| match path {
+            Ok(path) => path.point_count(vrom) > 0 && path.points(vrom, segment_table).is_ok(),
+            Err(_) => false,
+        })
+        .count() as u32
+}
+
+/// Finds the scene that `segment_table` maps to [`Segment::SCENE`].
+pub(crate) fn current_scene(vrom: Vrom<'_>, segment_table: &SegmentTable) -> Option<Scene> {
+    let addr = segment_table
+        .resolve(SegmentAddr(u32::from(Segment::SCENE.0) << 24))
+        .ok()?;
+    Scene::from_vrom(vrom, addr).ok()
+}
+
+impl SceneAlternateHeadersHeader {
+    pub fn setups_auto_len(
+        self,
+        vrom: Vrom<'_>,
+        segment_table: &SegmentTable,
+    ) -> Result<Slice<SceneSetup>, SegmentError> {
+        let addr = self.setups(vrom, segment_table, 0)?.addr();
+        self.setups(
+            vrom,
+            segment_table,
+            infer_alternate_header_count(vrom, segment_table, addr),
+        )
+    }
+}
+
+impl EntranceListHeader {
+    pub fn entrance_list_auto_len(
+        self,
+        vrom: Vrom<'_>,
+        segment_table: &SegmentTable,
+    ) -> Result<Slice<Entrance>, SegmentError> {
+        let addr = self.entrance_list(vrom, segment_table, 0)?.addr();
+        self.entrance_list(
+            vrom,
+            segment_table,
+            infer_len_until_next_scene_data::<Entrance>(vrom, segment_table, addr),
+        )
+    }
+}
+
+impl PathwaysHeader {
+    pub fn paths_auto_len(
+        self,
+        vrom: Vrom<'_>,
+        segment_table: &SegmentTable,
+    ) -> Result<Slice<Path>, SegmentError> {
+        let addr = self.paths(vrom, segment_table, 0)?.addr();
+        self.paths(
+            vrom,
+            segment_table,
+            infer_path_count(vrom, segment_table, addr),
+        )
+    }
+}
+
+impl ExitListHeader {
+    pub fn exit_list_auto_len(
+        self,
+        vrom: Vrom<'_>,
+        segment_table: &SegmentTable,
+    ) -> Result<Slice<Exit>, SegmentError> {
+        let addr = self.exit_list(vrom, segment_table, 0)?.addr();
+        self.exit_list(
+            vrom,
+            segment_table,
+            infer_len_until_next_scene_data::<Exit>(vrom, segment_table, addr),
+        )
+    }
+}
+
 impl RoomListEntry {
     pub fn room_range(self, vrom: Vrom<'_>) -> Range<VromAddr> {
         self.start(vrom)..self.end(vrom)
@@ -193,3 +379,63 @@ impl RoomListEntry {
         RangeSourced::from_vrom_range(vrom, self.room_range(vrom))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use oot_explorer_reflect::TypeDescriptor;
+
+    use super::*;
+
+    fn setup_headers(setup: TypeDescriptor) -> TypeDescriptor {
+        let TypeDescriptor::Struct(setup) = setup else {
+            unreachable!()
+        };
+        let TypeDescriptor::Pointer(pointer) = setup.fields[0].desc else {
+            unreachable!()
+        };
+        let TypeDescriptor::Struct(headers) = pointer.target() else {
+            unreachable!()
+        };
+        headers.fields[0].desc
+    }
+
+    #[test]
+    fn setups_point_to_header_lists() {
+        assert_eq!(setup_headers(SCENE_SETUP_DESC).name(), "SceneHeader");
+        assert_eq!(
+            setup_headers(crate::header_room::ROOM_SETUP_DESC).name(),
+            "RoomHeader",
+        );
+    }
+
+    #[test]
+    fn alternate_setups_bound_inferred_lists() {
+        let data = [
+            0x18, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x18, // alternate headers
+            0x13, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x20, // exit list
+            0x14, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // end
+            0x02, 0x00, 0x00, 0x28, // setup 1
+            0x00, 0x00, 0x00, 0x00, // setup 2
+            0x00, 0x01, 0x00, 0x02, 0x00, 0x03, 0x00, 0x04, // exits
+            0x06, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x38, // setup 1's entrance list
+            0x14, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // setup 1's end
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // setup 1's entrances
+        ];
+        let vrom = Vrom(&data);
+        let segment_table = SegmentTable::new().with(Segment::SCENE, VromAddr(0));
+        let scene = Scene::from_vrom(vrom, VromAddr(0)).unwrap();
+        assert_eq!(
+            scene.known_data_addrs(vrom, &segment_table),
+            [
+                VromAddr(0x18),
+                VromAddr(0x20),
+                VromAddr(0x28),
+                VromAddr(0x38)
+            ],
+        );
+        assert_eq!(
+            infer_len_until_next_scene_data::<u16>(vrom, &segment_table, VromAddr(0x20)),
+            4,
+        );
+    }
+}
@@ -1,13 +1,6 @@
-#![recursion_limit = "256"]
-#![cfg_attr(feature = "trace_macros", feature(trace_macros))]
-
-#[cfg(feature = "trace_macros")]
-trace_macros!(true);
-
-#[macro_use]
-mod macros;
-
+pub mod actor;
 pub mod collision;
+pub mod cutscene;
 pub mod gbi;
 pub mod header_common;
 pub mod header_room;
@@ -1,91 +1,112 @@
-use oot_explorer_read::{FromVrom, ReadError, Slice};
+use oot_explorer_derive::{layout, primitive_enum};
+use oot_explorer_read::Slice;
 use oot_explorer_reflect::{I16_DESC, I8_DESC, SEGMENT_ADDR_DESC, U8_DESC};
 use oot_explorer_segment::{SegmentAddr, SegmentError, SegmentTable};
-use oot_explorer_vrom::{Vrom, VromAddr};
+use oot_explorer_vrom::Vrom;
 
-use crate::gbi::DisplayList;
+use crate::gbi::{DisplayList, DISPLAY_LIST_DESC};
 
-declare_pointer_descriptor!(Mesh);
-declare_pointer_descriptor!(SimpleMeshEntry);
-
-compile_interfaces! {
-    enum MeshType: u8 {
-        SIMPLE = 0x00;
-        JFIF = 0x01;
-        CLIPPED = 0x02;
-    }
+#[primitive_enum(u8)]
+pub enum MeshType {
+    SIMPLE = 0x00,
+    JFIF = 0x01,
+    CLIPPED = 0x02,
+}
 
-    #[layout(size = 0x10, align_bits = 2)]
-    union Mesh: MeshType @0 {
-        struct SimpleMesh simple #MeshType::SIMPLE;
-        struct JfifMesh jfif #MeshType::JFIF;
-        struct ClippedMesh clipped #MeshType::CLIPPED;
-    }
+#[layout(size = 0x10, align_bits = 2)]
+#[discriminant(MeshType @ 0)]
+pub enum Mesh {
+    Simple(SimpleMesh) = MeshType::SIMPLE,
+    Jfif(JfifMesh) = MeshType::JFIF,
+    Clipped(ClippedMesh) = MeshType::CLIPPED,
+}
 
-    #[layout(size = 0xc, align_bits = 2)]
-    struct SimpleMesh {
-        u8 count @1;
-        SegmentAddr start @4;
-        SegmentAddr end @8;
-    }
+#[layout(size = 0xc, align_bits = 2)]
+pub struct SimpleMesh {
+    #[at(1)]
+    count: u8,
+    #[at(4)]
+    start: SegmentAddr,
+    #[at(8)]
+    end: SegmentAddr,
+}
 
-    #[layout(size = 8, align_bits = 2)]
-    struct SimpleMeshEntry {
-        // TODO: Guarded getters in codegen, then type these as pointers.
-        SegmentAddr opaque_display_list_ptr @0;
-        SegmentAddr translucent_display_list_ptr @4;
-    }
+#[layout(size = 8, align_bits = 2)]
+pub struct SimpleMeshEntry {
+    #[at(0)]
+    opaque_display_list: Option<*const DisplayList>,
+    #[at(4)]
+    translucent_display_list: Option<*const DisplayList>,
+}
 
-    enum JfifMeshType: u8 {
-        SINGLE = 0x01;
-        MULTIPLE = 0x02;
-    }
+#[primitive_enum(u8)]
+pub enum JfifMeshType {
+    SINGLE = 0x01,
+    MULTIPLE = 0x02,
+}
 
-    #[layout(size = 0x10, align_bits = 2)]
-    union JfifMesh: JfifMeshType @1 {
-        struct SingleJfif single #JfifMeshType::SINGLE;
-        struct MultipleJfif multiple #JfifMeshType::MULTIPLE;
-    }
+#[layout(size = 0x10, align_bits = 2)]
+#[discriminant(JfifMeshType @ 1)]
+pub enum JfifMesh {
+    Single(SingleJfif) = JfifMeshType::SINGLE,
+    Multiple(MultipleJfif) = JfifMeshType::MULTIPLE,
+}
 
-    #[layout(size = 0x20, align_bits = 2)]
-    struct SingleJfif {
-        struct SimpleMeshEntry* mesh_entry @4;
-        struct Background background @8;
-    }
+#[layout(size = 0x20, align_bits = 2)]
+pub struct SingleJfif {
+    #[at(4)]
+    mesh_entry: *const SimpleMeshEntry,
+    #[at(8)]
+    background: Background,
+}
 
-    #[layout(size = 0x10, align_bits = 2)]
-    struct MultipleJfif {
-        struct SimpleMeshEntry* mesh_entry @4;
-        struct MultipleJfifEntry[u8 @8]* background_entries @0x0c;
-    }
+#[layout(size = 0x10, align_bits = 2)]
+pub struct MultipleJfif {
+    #[at(4)]
+    mesh_entry: *const SimpleMeshEntry,
+    #[at(0x0c)]
+    #[count(u8 @ 8)]
+    background_entries: *const [MultipleJfifEntry],
+}
 
-    #[layout(size = 0x1c, align_bits = 2)]
-    struct MultipleJfifEntry {
-        i8 id @2;
-        struct Background background @4;
-    }
+#[layout(size = 0x1c, align_bits = 2)]
+pub struct MultipleJfifEntry {
+    #[at(2)]
+    id: i8,
+    #[at(4)]
+    background: Background,
+}
 
-    #[layout(size = 0x18, align_bits = 2)]
-    struct Background {
-        SegmentAddr ptr @0;
-    }
+#[layout(size = 0x18, align_bits = 2)]
+pub struct Background {
+    #[at(0)]
+    ptr: SegmentAddr,
+}
 
-    #[layout(size = 0xc, align_bits = 2)]
-    struct ClippedMesh {
-        u8 count @1;
-        SegmentAddr start @4;
-        SegmentAddr end @8;
-    }
+#[layout(size = 0xc, align_bits = 2)]
+pub struct ClippedMesh {
+    #[at(1)]
+    count: u8,
+    #[at(4)]
+    start: SegmentAddr,
+    #[at(8)]
+    end: SegmentAddr,
+}
 
-    #[layout(size = 0x10, align_bits = 2)]
-    struct ClippedMeshEntry {
-        i16 x_max @0;
-        i16 z_max @2;
-        i16 x_min @4;
-        i16 z_min @6;
-        SegmentAddr opaque_display_list_ptr @8;
-        SegmentAddr translucent_display_list_ptr @0xc;
-    }
+#[layout(size = 0x10, align_bits = 2)]
+pub struct ClippedMeshEntry {
+    #[at(0)]
+    x_max: i16,
+    #[at(2)]
+    z_max: i16,
+    #[at(4)]
+    x_min: i16,
+    #[at(6)]
+    z_min: i16,
+    #[at(8)]
+    opaque_display_list: Option<*const DisplayList>,
+    #[at(0xc)]
+    translucent_display_list: Option<*const DisplayList>,
 }
 
 impl SimpleMesh {
@@ -101,45 +122,6 @@ impl SimpleMesh {
     }
 }
 
-pub trait MeshEntry {
-    fn opaque_display_list(
-        self,
-        vrom: Vrom<'_>,
-        segment_table: &SegmentTable,
-    ) -> Result<Option<DisplayList>, ReadError>;
-    fn translucent_display_list(
-        self,
-        vrom: Vrom<'_>,
-        segment_table: &SegmentTable,
-    ) -> Result<Option<DisplayList>, ReadError>;
-}
-
-impl MeshEntry for SimpleMeshEntry {
-    // TODO: Guarded getters in codegen.
-
-    fn opaque_display_list(
-        self,
-        vrom: Vrom<'_>,
-        segment_table: &SegmentTable,
-    ) -> Result<Option<DisplayList>, ReadError> {
-        self.opaque_display_list_ptr(vrom)
-            .non_null()
-            .map(|segment_addr| DisplayList::from_vrom(vrom, segment_table.resolve(segment_addr)?))
-            .transpose()
-    }
-
-    fn translucent_display_list(
-        self,
-        vrom: Vrom<'_>,
-        segment_table: &SegmentTable,
-    ) -> Result<Option<DisplayList>, ReadError> {
-        self.translucent_display_list_ptr(vrom)
-            .non_null()
-            .map(|segment_addr| DisplayList::from_vrom(vrom, segment_table.resolve(segment_addr)?))
-            .transpose()
-    }
-}
-
 impl ClippedMesh {
     pub fn entries(
         self,
@@ -152,27 +134,3 @@ impl ClippedMesh {
         ))
     }
 }
-
-impl MeshEntry for ClippedMeshEntry {
-    fn opaque_display_list(
-        self,
-        vrom: Vrom<'_>,
-        segment_table: &SegmentTable,
-    ) -> Result<Option<DisplayList>, ReadError> {
-        self.opaque_display_list_ptr(vrom)
-            .non_null()
-            .map(|segment_addr| DisplayList::from_vrom(vrom, segment_table.resolve(segment_addr)?))
-            .transpose()
-    }
-
-    fn translucent_display_list(
-        self,
-        vrom: Vrom<'_>,
-        segment_table: &SegmentTable,
-    ) -> Result<Option<DisplayList>, ReadError> {
-        self.translucent_display_list_ptr(vrom)
-            .non_null()
-            .map(|segment_addr| DisplayList::from_vrom(vrom, segment_table.resolve(segment_addr)?))
-            .transpose()
-    }
-}
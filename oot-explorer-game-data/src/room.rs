@@ -1,9 +1,44 @@
-use crate::header_room::{RoomHeader, ROOM_HEADER_DESC};
+use oot_explorer_derive::layout;
+use oot_explorer_read::ReadError;
+use oot_explorer_segment::SegmentTable;
+use oot_explorer_vrom::Vrom;
 
-compile_interfaces! {
-    //  TODO: Don't specify a size for unsized types!
-    #[layout(size = 8, align_bits = 2)]
-    struct Room {
-        struct RoomHeader[..] headers @0;
+use crate::header_common::alternate_header_indices;
+use crate::header_room::{RoomHeader, RoomHeaderVariant, ROOM_HEADER_DESC};
+
+//  TODO: Don't specify a size for unsized types!
+#[layout(size = 8, align_bits = 2)]
+pub struct Room {
+    #[at(0)]
+    headers: [RoomHeader],
+}
+
+impl Room {
+    /// Returns the room whose headers the game uses for `setup`, like [`Scene::setup`].
+    ///
+    /// [`Scene::setup`]: crate::scene::Scene::setup
+    pub fn setup(
+        self,
+        vrom: Vrom<'_>,
+        segment_table: &SegmentTable,
+        setup: u32,
+    ) -> Result<Room, ReadError> {
+        let header = self.headers(vrom).find_map(|header| match header {
+            Ok(header) => match header.variant(vrom) {
+                RoomHeaderVariant::AlternateHeaders(header) => Some(Ok(header)),
+                _ => None,
+            },
+            Err(e) => Some(Err(e)),
+        });
+        let setups = match header.transpose()? {
+            Some(header) => header.setups_auto_len(vrom, segment_table)?,
+            None => return Ok(self),
+        };
+        for index in alternate_header_indices(setup).filter(|&index| index < setups.len()) {
+            if let Some(room) = setups.get(vrom, index)?.headers(vrom, segment_table)? {
+                return Ok(room);
+            }
+        }
+        Ok(self)
     }
 }
@@ -1,45 +1,183 @@
+use oot_explorer_derive::layout;
+use oot_explorer_read::{ReadError, Slice, VromProxy};
 use oot_explorer_reflect::{I8_DESC, U16_DESC, U8_DESC};
+use oot_explorer_segment::SegmentTable;
+use oot_explorer_vrom::{Vrom, VromAddr};
 
-use crate::header_scene::{SceneHeader, SCENE_HEADER_DESC};
+use crate::header_common::alternate_header_indices;
+use crate::header_scene::{
+    SceneAlternateHeadersHeader, SceneHeader, SceneHeaderVariant, SCENE_HEADER_DESC,
+};
 
-compile_interfaces! {
-    //  TODO: Don't specify a size for unsized types!
-    #[layout(size = 8, align_bits = 2)]
-    struct Scene {
-        struct SceneHeader[..] headers @0;
+//  TODO: Don't specify a size for unsized types!
+#[layout(size = 8, align_bits = 2)]
+pub struct Scene {
+    #[at(0)]
+    headers: [SceneHeader],
+}
+
+#[layout(size = 0x16, align_bits = 1)]
+pub struct Lighting {
+    // TODO: Support inline struct fields.
+    #[at(0)]
+    ambient_color_r: u8,
+    #[at(1)]
+    ambient_color_g: u8,
+    #[at(2)]
+    ambient_color_b: u8,
+    // TODO: Support inline struct fields.
+    #[at(3)]
+    diffuse_color_a_r: u8,
+    #[at(4)]
+    diffuse_color_a_g: u8,
+    #[at(5)]
+    diffuse_color_a_b: u8,
+    // TODO: Support arrays of fixed size.
+    #[at(6)]
+    diffuse_direction_a_x: i8,
+    #[at(7)]
+    diffuse_direction_a_y: i8,
+    #[at(8)]
+    diffuse_direction_a_z: i8,
+    // TODO: Support inline struct fields.
+    #[at(9)]
+    diffuse_color_b_r: u8,
+    #[at(0xa)]
+    diffuse_color_b_g: u8,
+    #[at(0xb)]
+    diffuse_color_b_b: u8,
+    // TODO: Support arrays of fixed size.
+    #[at(0xc)]
+    diffuse_direction_b_x: i8,
+    #[at(0xd)]
+    diffuse_direction_b_y: i8,
+    #[at(0xe)]
+    diffuse_direction_b_z: i8,
+    // TODO: Support inline struct fields.
+    #[at(0xf)]
+    fog_color_r: u8,
+    #[at(0x10)]
+    fog_color_g: u8,
+    #[at(0x11)]
+    fog_color_b: u8,
+
+    // TODO: This is a bitfield!
+    // fog_start = fog_start_and_flags & 0x03ff
+    // flags = fog_start_and_flags >> 10
+    #[at(0x12)]
+    fog_start_and_flags: u16,
+    #[at(0x14)]
+    draw_distance: u16,
+}
+
+impl Scene {
+    /// Returns the scene whose headers the game uses for `setup`. Setup 0 is the default, and
+    /// setups without headers of their own fall back as described by
+    /// [`alternate_header_indices`].
+    pub fn setup(
+        self,
+        vrom: Vrom<'_>,
+        segment_table: &SegmentTable,
+        setup: u32,
+    ) -> Result<Scene, ReadError> {
+        let setups = match self.alternate_headers_header(vrom)? {
+            Some(header) => header.setups_auto_len(vrom, segment_table)?,
+            None => return Ok(self),
+        };
+        for index in alternate_header_indices(setup).filter(|&index| index < setups.len()) {
+            if let Some(scene) = setups.get(vrom, index)?.headers(vrom, segment_table)? {
+                return Ok(scene);
+            }
+        }
+        Ok(self)
+    }
+
+    fn alternate_headers_header(
+        self,
+        vrom: Vrom<'_>,
+    ) -> Result<Option<SceneAlternateHeadersHeader>, ReadError> {
+        let header = self.headers(vrom).find_map(|header| match header {
+            Ok(header) => match header.variant(vrom) {
+                SceneHeaderVariant::AlternateHeaders(header) => Some(Ok(header)),
+                _ => None,
+            },
+            Err(e) => Some(Err(e)),
+        });
+        header.transpose()
+    }
+
+    /// Returns the VROM address of every structure that this scene's headers point to, including
+    /// the header lists of its alternate setups and what they point to.
+    ///
+    /// Some lists in a scene have no stored length. These addresses can be used with
+    /// [`UntilNext`](oot_explorer_read::UntilNext) to bound such lists by the next known structure.
+    pub fn known_data_addrs(self, vrom: Vrom<'_>, segment_table: &SegmentTable) -> Vec<VromAddr> {
+        let mut addrs = vec![];
+        self.add_header_targets(vrom, segment_table, &mut addrs);
+        let setups = match self.alternate_headers_header(vrom) {
+            Ok(Some(header)) => header.setups_auto_len(vrom, segment_table).ok(),
+            _ => None,
+        };
+        for setup in setups.into_iter().flat_map(|setups| setups.iter(vrom)) {
+            if let Ok(Some(scene)) = setup.and_then(|setup| setup.headers(vrom, segment_table)) {
+                addrs.push(scene.addr());
+                scene.add_header_targets(vrom, segment_table, &mut addrs);
+            }
+        }
+        addrs
     }
 
-    #[layout(size = 0x16, align_bits = 1)]
-    struct Lighting {
-        // TODO: Support inline struct fields.
-        u8 ambient_color_r @0;
-        u8 ambient_color_g @1;
-        u8 ambient_color_b @2;
-        // TODO: Support inline struct fields.
-        u8 diffuse_color_a_r @3;
-        u8 diffuse_color_a_g @4;
-        u8 diffuse_color_a_b @5;
-        // TODO: Support arrays of fixed size.
-        i8 diffuse_direction_a_x @6;
-        i8 diffuse_direction_a_y @7;
-        i8 diffuse_direction_a_z @8;
-        // TODO: Support inline struct fields.
-        u8 diffuse_color_b_r @9;
-        u8 diffuse_color_b_g @0xa;
-        u8 diffuse_color_b_b @0xb;
-        // TODO: Support arrays of fixed size.
-        i8 diffuse_direction_b_x @0xc;
-        i8 diffuse_direction_b_y @0xd;
-        i8 diffuse_direction_b_z @0xe;
-        // TODO: Support inline struct fields.
-        u8 fog_color_r @0xf;
-        u8 fog_color_g @0x10;
-        u8 fog_color_b @0x11;
-
-        // TODO: This is a bitfield!
-        // fog_start = fog_start_and_flags & 0x03ff
-        // flags = fog_start_and_flags >> 10
-        u16 fog_start_and_flags @0x12;
-        u16 draw_distance @0x14;
+    fn add_header_targets(
+        self,
+        vrom: Vrom<'_>,
+        segment_table: &SegmentTable,
+        addrs: &mut Vec<VromAddr>,
+    ) {
+        for header in self.headers(vrom) {
+            let header = match header {
+                Ok(header) => header,
+                Err(_) => break,
+            };
+            let addr = match header.variant(vrom) {
+                SceneHeaderVariant::StartPositions(header) => {
+                    header.start_positions(vrom, segment_table).map(Slice::addr)
+                }
+                SceneHeaderVariant::Collision(header) => {
+                    header.ptr(vrom, segment_table).map(|ptr| ptr.addr())
+                }
+                SceneHeaderVariant::RoomList(header) => {
+                    header.room_list(vrom, segment_table).map(Slice::addr)
+                }
+                SceneHeaderVariant::EntranceList(header) => header
+                    .entrance_list(vrom, segment_table, 0)
+                    .map(Slice::addr),
+                SceneHeaderVariant::Pathways(header) => {
+                    header.paths(vrom, segment_table, 0).map(Slice::addr)
+                }
+                SceneHeaderVariant::TransitionActors(header) => header
+                    .transition_actors(vrom, segment_table)
+                    .map(Slice::addr),
+                SceneHeaderVariant::Lighting(header) => {
+                    header.lighting(vrom, segment_table).map(Slice::addr)
+                }
+                SceneHeaderVariant::ExitList(header) => {
+                    header.exit_list(vrom, segment_table, 0).map(Slice::addr)
+                }
+                SceneHeaderVariant::Cutscene(header) => {
+                    header.cutscene(vrom, segment_table).map(|ptr| ptr.addr())
+                }
+                SceneHeaderVariant::AlternateHeaders(header) => {
+                    header.setups(vrom, segment_table, 0).map(Slice::addr)
+                }
+                SceneHeaderVariant::SpecialObjects(_)
+                | SceneHeaderVariant::Skybox(_)
+                | SceneHeaderVariant::End(_)
+                | SceneHeaderVariant::Sound(_)
+                | SceneHeaderVariant::CameraAndWorldMap(_) => continue,
+            };
+            if let Ok(addr) = addr {
+                addrs.push(addr);
+            }
+        }
     }
 }
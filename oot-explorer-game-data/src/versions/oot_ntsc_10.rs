@@ -1,37 +1,1727 @@
 use std::ops::Range;
 
-use crate::scene::Scene;
-use oot_explorer_read::{ReadError, Slice};
-use oot_explorer_reflect::{RangeSourced, U8_DESC, VROM_ADDR_DESC};
+use crate::actor::{
+    describe_params, ActorId, ActorInit, ActorOverlay, ACTOR_INIT_DESC, ACTOR_OVERLAY_DESC,
+};
+use crate::header_common::{Actor, ACTOR_DESC};
+use crate::header_room::RoomHeaderVariant;
+use crate::header_scene::{
+    Entrance, Exit, RoomListHeader, SceneHeaderVariant, TransitionActor, EXIT_DESC,
+    TRANSITION_ACTOR_DESC,
+};
+use crate::object::ObjectId;
+use crate::room::{Room, ROOM_DESC};
+use crate::scene::{Scene, SCENE_DESC};
+use oot_explorer_derive::layout;
+use oot_explorer_read::{FromVrom, Layout, ReadError, Slice, VromProxy};
+use oot_explorer_reflect::{
+    BitfieldDescriptor, BitfieldSpan, EnumDescriptor, PrimitiveType, Query, QueryError, QueryStep,
+    QueryTarget, RangeSourced, SearchHit, TypeDescriptor, Value, XrefIndex, U8_DESC,
+    VROM_ADDR_DESC,
+};
 use oot_explorer_rom::RomAddr;
+use oot_explorer_segment::{Segment, SegmentTable};
 use oot_explorer_vrom::{FileIndex, FileTable, GetFileError, Vrom, VromAddr};
 
 pub const FILE_TABLE_ROM_ADDR: RomAddr = RomAddr(0x00007430);
 
-pub const SCENE_TABLE_FILE_INDEX: FileIndex = FileIndex(0x1b);
+/// The index of `code`, which holds the scene, entrance, object, and actor overlay tables.
+pub const CODE_FILE_INDEX: FileIndex = FileIndex(0x1b);
+
 pub const SCENE_TABLE_OFFSET: u32 = 0xea440;
 pub const SCENE_TABLE_COUNT: u32 = 101;
 
 pub fn get_scene_table(file_table: &FileTable) -> Result<Slice<SceneTableEntry>, GetFileError> {
     Ok(Slice::new(
-        file_table.file_vrom_range(SCENE_TABLE_FILE_INDEX)?.start + SCENE_TABLE_OFFSET,
+        file_table.file_vrom_range(CODE_FILE_INDEX)?.start + SCENE_TABLE_OFFSET,
         SCENE_TABLE_COUNT,
     ))
 }
 
-compile_interfaces! {
-    #[layout(size = 0x14, align_bits = 2)]
-    struct SceneTableEntry {
-        VromAddr scene_start @0;
-        VromAddr scene_end @4;
-        VromAddr raw_title_card_start @8;
-        VromAddr raw_title_card_end @0xc;
-        u8 unknown_a @0x10;
-        u8 render_init_function @0x11;
-        u8 unknown_b @0x12;
+/// The entrance table immediately precedes the scene table in `code`.
+pub const ENTRANCE_TABLE_OFFSET: u32 = SCENE_TABLE_OFFSET - ENTRANCE_TABLE_COUNT * 4;
+pub const ENTRANCE_TABLE_COUNT: u32 = 0x614;
+
+pub fn get_entrance_table(
+    file_table: &FileTable,
+) -> Result<Slice<EntranceTableEntry>, GetFileError> {
+    Ok(Slice::new(
+        file_table.file_vrom_range(CODE_FILE_INDEX)?.start + ENTRANCE_TABLE_OFFSET,
+        ENTRANCE_TABLE_COUNT,
+    ))
+}
+
+/// The object table immediately precedes the entrance table in `code`.
+pub const OBJECT_TABLE_OFFSET: u32 = ENTRANCE_TABLE_OFFSET - OBJECT_TABLE_COUNT * 8;
+pub const OBJECT_TABLE_COUNT: u32 = 0x192;
+
+pub fn get_object_table(file_table: &FileTable) -> Result<Slice<ObjectTableEntry>, GetFileError> {
+    Ok(Slice::new(
+        file_table.file_vrom_range(CODE_FILE_INDEX)?.start + OBJECT_TABLE_OFFSET,
+        OBJECT_TABLE_COUNT,
+    ))
+}
+
+/// Looks up the VROM range of an object's file. Returns `None` if the ID is unused or out of
+/// range.
+pub fn get_object_range(
+    file_table: &FileTable,
+    vrom: Vrom<'_>,
+    object_id: ObjectId,
+) -> Result<Option<Range<VromAddr>>, ReadError> {
+    let index = u32::from(object_id.0);
+    if index >= OBJECT_TABLE_COUNT {
+        return Ok(None);
+    }
+    Ok(get_object_table(file_table)?
+        .get(vrom, index)?
+        .vrom_range(vrom))
+}
+
+/// Finds the address that [`Segment::OBJECT`] points to while the game draws with each object in
+/// a room's object list. Objects that are unused or out of range are left out.
+pub fn get_room_object_bases(
+    file_table: &FileTable,
+    vrom: Vrom<'_>,
+    segment_table: &SegmentTable,
+    room: Room,
+) -> Result<Vec<(ObjectId, VromAddr)>, ReadError> {
+    let header = room.headers(vrom).find_map(|header| match header {
+        Ok(header) => match header.variant(vrom) {
+            RoomHeaderVariant::ObjectList(header) => Some(Ok(header)),
+            _ => None,
+        },
+        Err(e) => Some(Err(e)),
+    });
+    let objects = match header.transpose()? {
+        Some(header) => header.objects(vrom, segment_table)?,
+        None => return Ok(vec![]),
+    };
+    let mut bases = vec![];
+    for object_id in objects.iter(vrom) {
+        let object_id = object_id?;
+        if let Some(range) = get_object_range(file_table, vrom, object_id)? {
+            bases.push((object_id, range.start));
+        }
+    }
+    Ok(bases)
+}
+
+/// The age and time of day that an entrance is taken at. Exits and actors refer to the child day
+/// entry, and the entries for the other variants follow it in the entrance table in this order.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EntranceVariant {
+    ChildDay = 0,
+    ChildNight = 1,
+    AdultDay = 2,
+    AdultNight = 3,
+}
+
+/// Looks up an entrance index in the entrance table. Returns `None` if the index or its variant is
+/// out of range.
+pub fn get_entrance(
+    file_table: &FileTable,
+    vrom: Vrom<'_>,
+    entrance_index: u32,
+    variant: EntranceVariant,
+) -> Result<Option<EntranceTableEntry>, ReadError> {
+    let index = match entrance_index.checked_add(variant as u32) {
+        Some(index) if index < ENTRANCE_TABLE_COUNT => index,
+        _ => return Ok(None),
+    };
+    Ok(Some(get_entrance_table(file_table)?.get(vrom, index)?))
+}
+
+/// Names of the scenes in the scene table, by index.
+pub const SCENE_NAMES: [&str; SCENE_TABLE_COUNT as usize] = [
+    "Inside the Deku Tree",
+    "Dodongo's Cavern",
+    "Inside Jabu-Jabu's Belly",
+    "Forest Temple",
+    "Fire Temple",
+    "Water Temple",
+    "Spirit Temple",
+    "Shadow Temple",
+    "Bottom of the Well",
+    "Ice Cavern",
+    "Ganon's Tower",
+    "Gerudo Training Ground",
+    "Thieves' Hideout",
+    "Inside Ganon's Castle",
+    "Ganon's Tower (Collapsing)",
+    "Inside Ganon's Castle (Collapsing)",
+    "Treasure Box Shop",
+    "Gohma's Lair",
+    "King Dodongo's Lair",
+    "Barinade's Lair",
+    "Phantom Ganon's Lair",
+    "Volvagia's Lair",
+    "Morpha's Lair",
+    "Twinrova's Lair",
+    "Bongo Bongo's Lair",
+    "Ganondorf's Lair",
+    "Tower Collapse Exterior",
+    "Market Entrance (Child - Day)",
+    "Market Entrance (Child - Night)",
+    "Market Entrance (Adult)",
+    "Back Alley (Day)",
+    "Back Alley (Night)",
+    "Market (Child - Day)",
+    "Market (Child - Night)",
+    "Market (Adult)",
+    "Temple of Time Exterior (Child - Day)",
+    "Temple of Time Exterior (Child - Night)",
+    "Temple of Time Exterior (Adult)",
+    "Know-It-All Brothers' House",
+    "House of Twins",
+    "Mido's House",
+    "Saria's House",
+    "Carpenter Boss's House",
+    "Back Alley House (Man in Green)",
+    "Bazaar",
+    "Kokiri Shop",
+    "Goron Shop",
+    "Zora Shop",
+    "Kakariko Potion Shop",
+    "Market Potion Shop",
+    "Bombchu Shop",
+    "Happy Mask Shop",
+    "Link's House",
+    "Back Alley House (Dog Lady)",
+    "Stable",
+    "Impa's House",
+    "Lakeside Laboratory",
+    "Carpenters' Tent",
+    "Gravekeeper's Hut",
+    "Great Fairy's Fountain (Upgrades)",
+    "Fairy's Fountain",
+    "Great Fairy's Fountain (Spells)",
+    "Grottos",
+    "Grave (Redead)",
+    "Grave (Fairy's Fountain)",
+    "Royal Family's Tomb",
+    "Shooting Gallery",
+    "Temple of Time",
+    "Chamber of the Sages",
+    "Castle Hedge Maze (Day)",
+    "Castle Hedge Maze (Night)",
+    "Cutscene Map",
+    "Damp\u{e9}'s Grave & Windmill",
+    "Fishing Pond",
+    "Castle Courtyard",
+    "Bombchu Bowling Alley",
+    "Ranch House & Silo",
+    "Guard House",
+    "Granny's Potion Shop",
+    "Ganon's Tower Collapse & Battle",
+    "House of Skulltula",
+    "Hyrule Field",
+    "Kakariko Village",
+    "Graveyard",
+    "Zora's River",
+    "Kokiri Forest",
+    "Sacred Forest Meadow",
+    "Lake Hylia",
+    "Zora's Domain",
+    "Zora's Fountain",
+    "Gerudo Valley",
+    "Lost Woods",
+    "Desert Colossus",
+    "Gerudo's Fortress",
+    "Haunted Wasteland",
+    "Hyrule Castle",
+    "Death Mountain Trail",
+    "Death Mountain Crater",
+    "Goron City",
+    "Lon Lon Ranch",
+    "Ganon's Castle Exterior",
+];
+
+/// The actor overlay table in `code`, indexed by [`ActorId`].
+pub const ACTOR_OVERLAY_TABLE_OFFSET: u32 = 0xd7490;
+pub const ACTOR_OVERLAY_TABLE_COUNT: u32 = 0x1d7;
+
+/// The VRAM address that `code` is linked at.
+pub const CODE_VRAM_START: u32 = 0x800110a0;
+
+pub fn get_actor_overlay_table(
+    file_table: &FileTable,
+) -> Result<Slice<ActorOverlay>, GetFileError> {
+    Ok(Slice::new(
+        file_table.file_vrom_range(CODE_FILE_INDEX)?.start + ACTOR_OVERLAY_TABLE_OFFSET,
+        ACTOR_OVERLAY_TABLE_COUNT,
+    ))
+}
+
+/// Looks up an actor in the actor overlay table. Returns `None` if the ID is out of range.
+pub fn get_actor_overlay(
+    file_table: &FileTable,
+    vrom: Vrom<'_>,
+    actor_id: ActorId,
+) -> Result<Option<ActorOverlay>, ReadError> {
+    let index = u32::from(actor_id.0);
+    if index >= ACTOR_OVERLAY_TABLE_COUNT {
+        return Ok(None);
+    }
+    Ok(Some(get_actor_overlay_table(file_table)?.get(vrom, index)?))
+}
+
+/// The file holding an actor's overlay, or `None` if the actor's code is part of `code` or the
+/// ID is unused or out of range.
+pub fn get_actor_overlay_file(
+    file_table: &FileTable,
+    vrom: Vrom<'_>,
+    actor_id: ActorId,
+) -> Result<Option<FileIndex>, ReadError> {
+    Ok(get_actor_overlay(file_table, vrom, actor_id)?
+        .and_then(|overlay| overlay.overlay_range(vrom))
+        .and_then(|range| file_table.find_file(range.start)))
+}
+
+/// Reads an actor's [`ActorInit`] from its overlay file, or from `code` for actors without one.
+/// Returns `None` if the ID is unused or out of range.
+pub fn get_actor_init(
+    file_table: &FileTable,
+    vrom: Vrom<'_>,
+    actor_id: ActorId,
+) -> Result<Option<ActorInit>, ReadError> {
+    let overlay = match get_actor_overlay(file_table, vrom, actor_id)? {
+        Some(overlay) => overlay,
+        None => return Ok(None),
+    };
+    let init_info = overlay.init_info(vrom);
+    let addr = if init_info == 0 {
+        None
+    } else if overlay.overlay_range(vrom).is_some() {
+        overlay.vram_to_vrom(vrom, init_info)
+    } else {
+        let code = file_table.file_vrom_range(CODE_FILE_INDEX)?;
+        init_info
+            .checked_sub(CODE_VRAM_START)
+            .map(|offset| code.start + offset)
+            .filter(|&addr| addr < code.end)
+    };
+    addr.map(|addr| ActorInit::from_vrom(vrom, addr))
+        .transpose()
+}
+
+/// Names of the actors in the actor overlay table, by ID. These are the names of the actors' source
+/// files, as in `ovl_En_Kusa`.
+pub const ACTOR_NAMES: [&str; ACTOR_OVERLAY_TABLE_COUNT as usize] = [
+    "Player",
+    "Unset_1",
+    "En_Test",
+    "Unset_3",
+    "En_GirlA",
+    "Unset_5",
+    "Unset_6",
+    "En_Part",
+    "En_Light",
+    "En_Door",
+    "En_Box",
+    "Bg_Dy_Yoseizo",
+    "Bg_Hidan_Firewall",
+    "En_Poh",
+    "En_Okuta",
+    "Bg_Ydan_Sp",
+    "En_Bom",
+    "En_Wallmas",
+    "En_Dodongo",
+    "En_Firefly",
+    "En_Horse",
+    "En_Item00",
+    "En_Arrow",
+    "Unset_17",
+    "En_Elf",
+    "En_Niw",
+    "Unset_1A",
+    "En_Tite",
+    "En_Reeba",
+    "En_Peehat",
+    "En_Butte",
+    "Unset_1F",
+    "En_Insect",
+    "En_Fish",
+    "Unset_22",
+    "En_Holl",
+    "En_Scene_Change",
+    "En_Zf",
+    "En_Hata",
+    "Boss_Dodongo",
+    "Boss_Goma",
+    "En_Zl1",
+    "En_Viewer",
+    "En_Goma",
+    "Bg_Pushbox",
+    "En_Bubble",
+    "Door_Shutter",
+    "En_Dodojr",
+    "En_Bdfire",
+    "Unset_31",
+    "En_Boom",
+    "En_Torch2",
+    "En_Bili",
+    "En_Tp",
+    "Unset_36",
+    "En_St",
+    "En_Bw",
+    "En_A_Obj",
+    "En_Eiyer",
+    "En_River_Sound",
+    "En_Horse_Normal",
+    "En_Ossan",
+    "Bg_Treemouth",
+    "Bg_Dodoago",
+    "Bg_Hidan_Dalm",
+    "Bg_Hidan_Hrock",
+    "En_Horse_Ganon",
+    "Bg_Hidan_Rock",
+    "Bg_Hidan_Rsekizou",
+    "Bg_Hidan_Sekizou",
+    "Bg_Hidan_Sima",
+    "Bg_Hidan_Syoku",
+    "En_Xc",
+    "Bg_Hidan_Curtain",
+    "Bg_Spot00_Hanebasi",
+    "En_Mb",
+    "En_Bombf",
+    "En_Zl2",
+    "Bg_Hidan_Fslift",
+    "En_OE2",
+    "Bg_Ydan_Hasi",
+    "Bg_Ydan_Maruta",
+    "Boss_Ganondrof",
+    "Unset_53",
+    "En_Am",
+    "En_Dekubaba",
+    "En_M_Fire1",
+    "En_M_Thunder",
+    "Bg_Ddan_Jd",
+    "Bg_Breakwall",
+    "En_Jj",
+    "En_Horse_Zelda",
+    "Bg_Ddan_Kd",
+    "Door_Warp1",
+    "Obj_Syokudai",
+    "Item_B_Heart",
+    "En_Dekunuts",
+    "Bg_Menkuri_Kaiten",
+    "Bg_Menkuri_Eye",
+    "En_Vali",
+    "Bg_Mizu_Movebg",
+    "Bg_Mizu_Water",
+    "Arms_Hook",
+    "En_fHG",
+    "Bg_Mori_Hineri",
+    "En_Bb",
+    "Bg_Toki_Hikari",
+    "En_Yukabyun",
+    "Bg_Toki_Swd",
+    "En_Fhg_Fire",
+    "Bg_Mjin",
+    "Bg_Hidan_Kousi",
+    "Door_Toki",
+    "Bg_Hidan_Hamstep",
+    "En_Bird",
+    "Unset_73",
+    "Unset_74",
+    "Unset_75",
+    "Unset_76",
+    "En_Wood02",
+    "Unset_78",
+    "Unset_79",
+    "Unset_7A",
+    "Unset_7B",
+    "En_Lightbox",
+    "En_Pu_box",
+    "Unset_7E",
+    "Unset_7F",
+    "En_Trap",
+    "En_Arow_Trap",
+    "En_Vase",
+    "Unset_83",
+    "En_Ta",
+    "En_Tk",
+    "Bg_Mori_Bigst",
+    "Bg_Mori_Elevator",
+    "Bg_Mori_Kaitenkabe",
+    "Bg_Mori_Rakkatenjo",
+    "En_Vm",
+    "Demo_Effect",
+    "Demo_Kankyo",
+    "Bg_Hidan_Fwbig",
+    "En_Floormas",
+    "En_Heishi1",
+    "En_Rd",
+    "En_Po_Sisters",
+    "Bg_Heavy_Block",
+    "Bg_Po_Event",
+    "Obj_Mure",
+    "En_Sw",
+    "Boss_Fd",
+    "Object_Kankyo",
+    "En_Du",
+    "En_Fd",
+    "En_Horse_Link_Child",
+    "Door_Ana",
+    "Bg_Spot02_Objects",
+    "Bg_Haka",
+    "Magic_Wind",
+    "Magic_Fire",
+    "Unset_A0",
+    "En_Ru1",
+    "Boss_Fd2",
+    "En_Fd_Fire",
+    "En_Dh",
+    "En_Dha",
+    "En_Rl",
+    "En_Encount1",
+    "Demo_Du",
+    "Demo_Im",
+    "Demo_Tre_Lgt",
+    "En_Fw",
+    "Bg_Vb_Sima",
+    "En_Vb_Ball",
+    "Bg_Haka_Megane",
+    "Bg_Haka_MeganeBG",
+    "Bg_Haka_Ship",
+    "Bg_Haka_Sgami",
+    "Unset_B2",
+    "En_Heishi2",
+    "En_Encount2",
+    "En_Fire_Rock",
+    "En_Brob",
+    "Mir_Ray",
+    "Bg_Spot09_Obj",
+    "Bg_Spot18_Obj",
+    "Boss_Va",
+    "Bg_Haka_Tubo",
+    "Bg_Haka_Trap",
+    "Bg_Haka_Huta",
+    "Bg_Haka_Zou",
+    "Bg_Spot17_Funen",
+    "En_Syateki_Itm",
+    "En_Syateki_Man",
+    "En_Tana",
+    "En_Nb",
+    "Boss_Mo",
+    "En_Sb",
+    "En_Bigokuta",
+    "En_Karebaba",
+    "Bg_Bdan_Objects",
+    "Demo_Sa",
+    "Demo_Go",
+    "En_In",
+    "En_Tr",
+    "Bg_Spot16_Bombstone",
+    "Unset_CE",
+    "Bg_Hidan_Kowarerukabe",
+    "Bg_Bombwall",
+    "Bg_Spot08_Iceblock",
+    "En_Ru2",
+    "Obj_Dekujr",
+    "Bg_Mizu_Uzu",
+    "Bg_Spot06_Objects",
+    "Bg_Ice_Objects",
+    "Bg_Haka_Water",
+    "Unset_D8",
+    "En_Ma2",
+    "En_Bom_Chu",
+    "En_Horse_Game_Check",
+    "Boss_Tw",
+    "En_Rr",
+    "En_Ba",
+    "En_Bx",
+    "En_Anubice",
+    "En_Anubice_Fire",
+    "Bg_Mori_Hashigo",
+    "Bg_Mori_Hashira4",
+    "Bg_Mori_Idomizu",
+    "Bg_Spot16_Doughnut",
+    "Bg_Bdan_Switch",
+    "En_Ma1",
+    "Boss_Ganon",
+    "Boss_Sst",
+    "Unset_EA",
+    "Unset_EB",
+    "En_Ny",
+    "En_Fr",
+    "Item_Shield",
+    "Bg_Ice_Shelter",
+    "En_Ice_Hono",
+    "Item_Ocarina",
+    "Unset_F2",
+    "Unset_F3",
+    "Magic_Dark",
+    "Demo_6K",
+    "En_Anubice_Tag",
+    "Bg_Haka_Gate",
+    "Bg_Spot15_Saku",
+    "Bg_Jya_Goroiwa",
+    "Bg_Jya_Zurerukabe",
+    "Unset_FB",
+    "Bg_Jya_Cobra",
+    "Bg_Jya_Kanaami",
+    "Fishing",
+    "Obj_Oshihiki",
+    "Bg_Gate_Shutter",
+    "Eff_Dust",
+    "Bg_Spot01_Fusya",
+    "Bg_Spot01_Idohashira",
+    "Bg_Spot01_Idomizu",
+    "Bg_Po_Syokudai",
+    "Bg_Ganon_Otyuka",
+    "Bg_Spot15_Rrbox",
+    "Bg_Umajump",
+    "Unset_109",
+    "Arrow_Fire",
+    "Arrow_Ice",
+    "Arrow_Light",
+    "Unset_10D",
+    "Unset_10E",
+    "Item_Etcetera",
+    "Obj_Kibako",
+    "Obj_Tsubo",
+    "En_Wonder_Item",
+    "En_Ik",
+    "Demo_Ik",
+    "En_Skj",
+    "En_Skjneedle",
+    "En_G_Switch",
+    "Demo_Ext",
+    "Demo_Shd",
+    "En_Dns",
+    "Elf_Msg",
+    "En_Honotrap",
+    "En_Tubo_Trap",
+    "Obj_Ice_Poly",
+    "Bg_Spot03_Taki",
+    "Bg_Spot07_Taki",
+    "En_Fz",
+    "En_Po_Relay",
+    "Bg_Relay_Objects",
+    "En_Diving_Game",
+    "En_Kusa",
+    "Obj_Bean",
+    "Obj_Bombiwa",
+    "Unset_128",
+    "Unset_129",
+    "Obj_Switch",
+    "Obj_Elevator",
+    "Obj_Lift",
+    "Obj_Hsblock",
+    "En_Okarina_Tag",
+    "En_Yabusame_Mark",
+    "En_Goroiwa",
+    "En_Ex_Ruppy",
+    "En_Toryo",
+    "En_Daiku",
+    "Unset_134",
+    "En_Nwc",
+    "En_Blkobj",
+    "Item_Inbox",
+    "En_Ge1",
+    "Obj_Blockstop",
+    "En_Sda",
+    "En_Clear_Tag",
+    "En_Niw_Lady",
+    "En_Gm",
+    "En_Ms",
+    "En_Hs",
+    "Bg_Ingate",
+    "En_Kanban",
+    "En_Heishi3",
+    "En_Syateki_Niw",
+    "En_Attack_Niw",
+    "Bg_Spot01_Idosoko",
+    "En_Sa",
+    "En_Wonder_Talk",
+    "Bg_Gjyo_Bridge",
+    "En_Ds",
+    "En_Mk",
+    "En_Bom_Bowl_Man",
+    "En_Bom_Bowl_Pit",
+    "En_Owl",
+    "En_Ishi",
+    "Obj_Hana",
+    "Obj_Lightswitch",
+    "Obj_Mure2",
+    "En_Go",
+    "En_Fu",
+    "Unset_154",
+    "En_Changer",
+    "Bg_Jya_Megami",
+    "Bg_Jya_Lift",
+    "Bg_Jya_Bigmirror",
+    "Bg_Jya_Bombchuiwa",
+    "Bg_Jya_Amishutter",
+    "Bg_Jya_Bombiwa",
+    "Bg_Spot18_Basket",
+    "Unset_15D",
+    "En_Ganon_Organ",
+    "En_Siofuki",
+    "En_Stream",
+    "Unset_161",
+    "En_Mm",
+    "En_Ko",
+    "En_Kz",
+    "En_Weather_Tag",
+    "Bg_Sst_Floor",
+    "En_Ani",
+    "En_Ex_Item",
+    "Bg_Jya_Ironobj",
+    "En_Js",
+    "En_Jsjutan",
+    "En_Cs",
+    "En_Md",
+    "En_Hy",
+    "En_Ganon_Mant",
+    "En_Okarina_Effect",
+    "En_Mag",
+    "Door_Gerudo",
+    "Elf_Msg2",
+    "Demo_Gt",
+    "En_Po_Field",
+    "Efc_Erupc",
+    "Bg_Zg",
+    "En_Heishi4",
+    "En_Zl3",
+    "Boss_Ganon2",
+    "En_Kakasi",
+    "En_Takara_Man",
+    "Obj_Makeoshihiki",
+    "Oceff_Spot",
+    "End_Title",
+    "Unset_180",
+    "En_Torch",
+    "Demo_Ec",
+    "Shot_Sun",
+    "En_Dy_Extra",
+    "En_Wonder_Talk2",
+    "En_Ge2",
+    "Obj_Roomtimer",
+    "En_Ssh",
+    "En_Sth",
+    "Oceff_Wipe",
+    "Oceff_Storm",
+    "En_Weiyer",
+    "Bg_Spot05_Soko",
+    "Bg_Jya_1flift",
+    "Bg_Jya_Haheniron",
+    "Bg_Spot12_Gate",
+    "Bg_Spot12_Saku",
+    "En_Hintnuts",
+    "En_Nutsball",
+    "Bg_Spot00_Break",
+    "En_Shopnuts",
+    "En_It",
+    "En_GeldB",
+    "Oceff_Wipe2",
+    "Oceff_Wipe3",
+    "En_Niw_Girl",
+    "En_Dog",
+    "En_Si",
+    "Bg_Spot01_Objects2",
+    "Obj_Comb",
+    "Bg_Spot11_Bakudankabe",
+    "Obj_Kibako2",
+    "En_Dnt_Demo",
+    "En_Dnt_Jiji",
+    "En_Dnt_Nomal",
+    "En_Guest",
+    "Bg_Bom_Guard",
+    "En_Hs2",
+    "Demo_Kekkai",
+    "Bg_Spot08_Bakudankabe",
+    "Bg_Spot17_Bakudankabe",
+    "Unset_1AA",
+    "Obj_Mure3",
+    "En_Tg",
+    "En_Mu",
+    "En_Go2",
+    "En_Wf",
+    "En_Skb",
+    "Demo_Gj",
+    "Demo_Geff",
+    "Bg_Gnd_Firemeiro",
+    "Bg_Gnd_Darkmeiro",
+    "Bg_Gnd_Soulmeiro",
+    "Bg_Gnd_Nisekabe",
+    "Bg_Gnd_Iceblock",
+    "En_Gb",
+    "En_Gs",
+    "Bg_Mizu_Bwall",
+    "Bg_Mizu_Shutter",
+    "En_Daiku_Kakariko",
+    "Bg_Bowl_Wall",
+    "En_Wall_Tubo",
+    "En_Po_Desert",
+    "En_Crow",
+    "Door_Killer",
+    "Bg_Spot11_Oasis",
+    "Bg_Spot18_Futa",
+    "Bg_Spot18_Shutter",
+    "En_Ma3",
+    "En_Cow",
+    "Bg_Ice_Turara",
+    "Bg_Ice_Shutter",
+    "En_Kakasi2",
+    "En_Kakasi3",
+    "Oceff_Wipe4",
+    "En_Eg",
+    "Bg_Menkuri_Nisekabe",
+    "En_Zo",
+    "Obj_Makekinsuta",
+    "En_Ge3",
+    "Obj_Timeblock",
+    "Obj_Hamishi",
+    "En_Zl4",
+    "En_Mm2",
+    "Bg_Jya_Block",
+    "Obj_Warp2block",
+];
+
+/// Plain descriptions of some well-known actors, sorted by ID.
+const ACTOR_DESCRIPTIONS: &[(u16, &str)] = &[
+    (0x000, "Link"),
+    (0x008, "flame"),
+    (0x009, "door"),
+    (0x00a, "treasure chest"),
+    (0x00d, "poe"),
+    (0x00e, "octorok"),
+    (0x010, "bomb"),
+    (0x011, "wallmaster"),
+    (0x012, "dodongo"),
+    (0x013, "keese"),
+    (0x014, "Epona"),
+    (0x015, "collectible"),
+    (0x018, "fairy"),
+    (0x019, "cucco"),
+    (0x01b, "tektite"),
+    (0x01c, "leever"),
+    (0x01d, "peahat"),
+    (0x01e, "butterfly"),
+    (0x020, "bugs"),
+    (0x021, "fish"),
+    (0x023, "room transition plane"),
+    (0x025, "lizalfos"),
+    (0x027, "King Dodongo"),
+    (0x028, "Queen Gohma"),
+    (0x02b, "gohma larva"),
+    (0x02e, "sliding door"),
+    (0x02f, "baby dodongo"),
+    (0x034, "biri"),
+    (0x037, "skulltula"),
+    (0x03a, "stinger"),
+    (0x03d, "shopkeeper"),
+    (0x04b, "moblin"),
+    (0x052, "Phantom Ganon"),
+    (0x054, "armos"),
+    (0x055, "deku baba"),
+    (0x05d, "blue warp"),
+    (0x05e, "torch"),
+    (0x05f, "heart container"),
+    (0x060, "deku scrub"),
+    (0x063, "bari"),
+    (0x069, "bubble"),
+    (0x077, "tree"),
+    (0x08a, "beamos"),
+    (0x08e, "floormaster"),
+    (0x090, "redead"),
+    (0x091, "poe sisters"),
+    (0x095, "skullwalltula"),
+    (0x096, "Volvagia"),
+    (0x09b, "grotto"),
+    (0x0a4, "dead hand"),
+    (0x0ba, "Barinade"),
+    (0x0c4, "Morpha"),
+    (0x0c5, "shell blade"),
+    (0x0c6, "big octo"),
+    (0x0c7, "withered deku baba"),
+    (0x0dc, "Twinrova"),
+    (0x0dd, "like like"),
+    (0x0e0, "anubis"),
+    (0x0e8, "Ganondorf"),
+    (0x0e9, "Bongo Bongo"),
+    (0x0ed, "frogs"),
+    (0x0fe, "fishing pond"),
+    (0x0ff, "push block"),
+    (0x110, "small crate"),
+    (0x111, "pot"),
+    (0x113, "iron knuckle"),
+    (0x115, "skull kid"),
+    (0x121, "freezard"),
+    (0x125, "bush"),
+    (0x127, "bombable boulder"),
+    (0x12a, "switch"),
+    (0x130, "rolling boulder"),
+    (0x138, "gerudo"),
+    (0x141, "sign"),
+    (0x14d, "Kaepora Gaebora"),
+    (0x14e, "rock"),
+    (0x152, "goron"),
+    (0x17a, "Ganon"),
+    (0x17b, "Pierre"),
+    (0x186, "gerudo guard"),
+    (0x195, "business scrub"),
+    (0x197, "gerudo fighter"),
+    (0x19b, "dog"),
+    (0x19c, "gold skulltula token"),
+    (0x1a0, "large crate"),
+    (0x1af, "wolfos"),
+    (0x1b0, "stalchild"),
+    (0x1c0, "guay"),
+    (0x1c6, "cow"),
+    (0x1ce, "zora"),
+];
+
+/// A name for an actor ID, like "En_Kusa (bush)". Returns `None` if the ID is out of range.
+pub fn actor_display_name(actor_id: ActorId) -> Option<String> {
+    let name = ACTOR_NAMES.get(usize::from(actor_id.0))?;
+    Some(
+        match ACTOR_DESCRIPTIONS.binary_search_by_key(&actor_id.0, |&(id, _)| id) {
+            Ok(index) => format!("{} ({})", name, ACTOR_DESCRIPTIONS[index].1),
+            Err(_) => name.to_string(),
+        },
+    )
+}
+
+/// A name for an object ID, like "object_kusa". Returns `None` if the ID is out of range.
+pub fn object_display_name(object_id: ObjectId) -> Option<&'static str> {
+    OBJECT_NAMES.get(usize::from(object_id.0)).copied()
+}
+
+/// Names of the objects in the object table, by ID. These are the names of the objects' files.
+pub const OBJECT_NAMES: [&str; OBJECT_TABLE_COUNT as usize] = [
+    "Unset_0",
+    "gameplay_keep",
+    "gameplay_field_keep",
+    "gameplay_dangeon_keep",
+    "Unset_4",
+    "Unset_5",
+    "object_human",
+    "object_okuta",
+    "object_crow",
+    "object_poh",
+    "object_dy_obj",
+    "object_wallmaster",
+    "object_dodongo",
+    "object_firefly",
+    "object_box",
+    "object_fire",
+    "Unset_10",
+    "Unset_11",
+    "object_bubble",
+    "object_niw",
+    "object_link_boy",
+    "object_link_child",
+    "object_tite",
+    "object_reeba",
+    "object_peehat",
+    "object_kingdodongo",
+    "object_horse",
+    "object_zf",
+    "object_goma",
+    "object_zl1",
+    "object_gol",
+    "object_dodojr",
+    "object_torch2",
+    "object_bl",
+    "object_tp",
+    "object_oA1",
+    "object_st",
+    "object_bw",
+    "object_ei",
+    "object_horse_normal",
+    "object_oB1",
+    "object_o_anime",
+    "object_spot04_objects",
+    "object_ddan_objects",
+    "object_hidan_objects",
+    "object_horse_ganon",
+    "object_oA2",
+    "object_spot00_objects",
+    "object_mb",
+    "object_bombf",
+    "object_sk2",
+    "object_oE1",
+    "object_oE_anime",
+    "object_oE2",
+    "object_ydan_objects",
+    "object_gnd",
+    "object_am",
+    "object_dekubaba",
+    "Unset_3A",
+    "object_oA3",
+    "object_oA4",
+    "object_oA5",
+    "object_oA6",
+    "object_oA7",
+    "object_jj",
+    "object_oA8",
+    "object_oA9",
+    "object_oB2",
+    "object_oB3",
+    "object_oB4",
+    "object_horse_zelda",
+    "object_opening_demo1",
+    "object_warp1",
+    "object_b_heart",
+    "object_dekunuts",
+    "object_oE3",
+    "object_oE4",
+    "object_menkuri_objects",
+    "object_oE5",
+    "object_oE6",
+    "object_oE7",
+    "object_oE8",
+    "object_oE9",
+    "object_oE10",
+    "object_oE11",
+    "object_oE12",
+    "object_vali",
+    "object_oA10",
+    "object_oA11",
+    "object_mizu_objects",
+    "object_fhg",
+    "object_ossan",
+    "object_mori_hineri1",
+    "object_Bb",
+    "object_toki_objects",
+    "object_yukabyun",
+    "object_zl2",
+    "object_mjin",
+    "object_mjin_flash",
+    "object_mjin_dark",
+    "object_mjin_flame",
+    "object_mjin_ice",
+    "object_mjin_soul",
+    "object_mjin_wind",
+    "object_mjin_oka",
+    "object_haka_objects",
+    "object_spot06_objects",
+    "object_ice_objects",
+    "object_relay_objects",
+    "object_po_field",
+    "object_po_composer",
+    "object_mori_hineri1a",
+    "object_mori_hineri2",
+    "object_mori_hineri2a",
+    "object_mori_objects",
+    "object_mori_tex",
+    "object_spot08_obj",
+    "object_warp2",
+    "object_hata",
+    "object_bird",
+    "Unset_78",
+    "Unset_79",
+    "Unset_7A",
+    "Unset_7B",
+    "object_wood02",
+    "Unset_7D",
+    "Unset_7E",
+    "Unset_7F",
+    "Unset_80",
+    "object_lightbox",
+    "object_pu_box",
+    "Unset_83",
+    "Unset_84",
+    "object_trap",
+    "object_vase",
+    "object_im",
+    "object_ta",
+    "object_tk",
+    "object_xc",
+    "object_vm",
+    "object_bv",
+    "object_hakach_objects",
+    "object_efc_crystal_light",
+    "object_efc_fire_ball",
+    "object_efc_flash",
+    "object_efc_lgt_shower",
+    "object_efc_star_field",
+    "object_god_lgt",
+    "object_light_ring",
+    "object_triforce_spot",
+    "object_bdan_objects",
+    "object_sd",
+    "object_rd",
+    "object_po_sisters",
+    "object_heavy_object",
+    "object_gndd",
+    "object_fd",
+    "object_du",
+    "object_fw",
+    "object_medal",
+    "object_horse_link_child",
+    "object_spot02_objects",
+    "object_haka",
+    "object_ru1",
+    "object_syokudai",
+    "object_fd2",
+    "object_dh",
+    "object_rl",
+    "object_efc_tw",
+    "object_demo_tre_lgt",
+    "object_gi_key",
+    "object_mir_ray",
+    "object_brob",
+    "object_gi_jewel",
+    "object_spot09_obj",
+    "object_spot18_obj",
+    "object_bdoor",
+    "object_spot17_obj",
+    "object_shop_dungen",
+    "object_nb",
+    "object_mo",
+    "object_sb",
+    "object_gi_melody",
+    "object_gi_heart",
+    "object_gi_compass",
+    "object_gi_bosskey",
+    "object_gi_medal",
+    "object_gi_nuts",
+    "object_sa",
+    "object_gi_hearts",
+    "object_gi_arrowcase",
+    "object_gi_bombpouch",
+    "object_in",
+    "object_tr",
+    "object_spot16_obj",
+    "object_oE1s",
+    "object_oE4s",
+    "object_os_anime",
+    "object_gi_bottle",
+    "object_gi_stick",
+    "object_gi_map",
+    "object_oF1d_map",
+    "object_ru2",
+    "object_gi_shield_1",
+    "object_dekujr",
+    "object_gi_magicpot",
+    "object_gi_bomb_1",
+    "object_oF1s",
+    "object_ma2",
+    "object_gi_purse",
+    "object_hni",
+    "object_tw",
+    "object_rr",
+    "object_bxa",
+    "object_anubice",
+    "object_gi_gerudo",
+    "object_gi_arrow",
+    "object_gi_bomb_2",
+    "object_gi_egg",
+    "object_gi_scale",
+    "object_gi_shield_2",
+    "object_gi_hookshot",
+    "object_gi_ocarina",
+    "object_gi_milk",
+    "object_ma1",
+    "object_ganon",
+    "object_sst",
+    "Unset_E3",
+    "Unset_E4",
+    "object_ny",
+    "object_fr",
+    "object_gi_pachinko",
+    "object_gi_boomerang",
+    "object_gi_bow",
+    "object_gi_glasses",
+    "object_gi_liquid",
+    "object_ani",
+    "object_demo_6k",
+    "object_gi_shield_3",
+    "object_gi_letter",
+    "object_spot15_obj",
+    "object_jya_obj",
+    "object_gi_clothes",
+    "object_gi_bean",
+    "object_gi_fish",
+    "object_gi_saw",
+    "object_gi_hammer",
+    "object_gi_grass",
+    "object_gi_longsword",
+    "object_spot01_objects",
+    "Unset_FA",
+    "object_md",
+    "object_km1",
+    "object_kw1",
+    "object_zo",
+    "object_kz",
+    "object_umajump",
+    "object_masterkokiri",
+    "object_masterkokirihead",
+    "object_mastergolon",
+    "object_masterzoora",
+    "object_aob",
+    "object_ik",
+    "object_ahg",
+    "object_cne",
+    "object_gi_niwatori",
+    "object_skj",
+    "object_gi_bottle_letter",
+    "object_bji",
+    "object_bba",
+    "object_gi_ocarina_0",
+    "object_ds",
+    "object_ane",
+    "object_boj",
+    "object_spot03_object",
+    "object_spot07_object",
+    "object_fz",
+    "object_bob",
+    "object_ge1",
+    "object_yabusame_point",
+    "object_gi_boots_2",
+    "object_gi_seed",
+    "object_gnd_magic",
+    "object_d_elevator",
+    "object_d_hsblock",
+    "object_d_lift",
+    "object_mamenoki",
+    "object_goroiwa",
+    "Unset_120",
+    "object_toryo",
+    "object_daiku",
+    "Unset_123",
+    "object_nwc",
+    "object_blkobj",
+    "object_gm",
+    "object_ms",
+    "object_hs",
+    "object_ingate",
+    "object_lightswitch",
+    "object_kusa",
+    "object_tsubo",
+    "object_gi_gloves",
+    "object_gi_coin",
+    "object_kanban",
+    "object_gjyo_objects",
+    "object_owl",
+    "object_mk",
+    "object_fu",
+    "object_gi_ki_tan_mask",
+    "object_gi_redead_mask",
+    "object_gi_skj_mask",
+    "object_gi_rabit_mask",
+    "object_gi_truth_mask",
+    "object_ganon_objects",
+    "object_siofuki",
+    "object_stream",
+    "object_mm",
+    "object_fa",
+    "object_os",
+    "object_gi_eye_lotion",
+    "object_gi_powder",
+    "object_gi_mushroom",
+    "object_gi_ticketstone",
+    "object_gi_brokensword",
+    "object_js",
+    "object_cs",
+    "object_gi_prescription",
+    "object_gi_bracelet",
+    "object_gi_soldout",
+    "object_gi_frog",
+    "object_mag",
+    "object_door_gerudo",
+    "object_gt",
+    "object_efc_erupc",
+    "object_zl2_anime1",
+    "object_zl2_anime2",
+    "object_gi_golonmask",
+    "object_gi_zoramask",
+    "object_gi_gerudomask",
+    "object_ganon2",
+    "object_ka",
+    "object_ts",
+    "object_zg",
+    "object_gi_hoverboots",
+    "object_gi_m_arrow",
+    "object_ds2",
+    "object_ec",
+    "object_fish",
+    "object_gi_sutaru",
+    "object_gi_goddess",
+    "object_ssh",
+    "object_bigokuta",
+    "object_bg",
+    "object_spot05_objects",
+    "object_spot12_obj",
+    "object_bombiwa",
+    "object_hintnuts",
+    "object_rs",
+    "object_spot00_break",
+    "object_gla",
+    "object_shopnuts",
+    "object_geldb",
+    "object_gr",
+    "object_dog",
+    "object_jya_iron",
+    "object_jya_door",
+    "Unset_16E",
+    "object_spot11_obj",
+    "object_kibako2",
+    "object_dns",
+    "object_dnk",
+    "object_gi_fire",
+    "object_gi_insect",
+    "object_gi_butterfly",
+    "object_gi_ghost",
+    "object_gi_soul",
+    "object_bowl",
+    "object_demo_kekkai",
+    "object_efc_doughnut",
+    "object_gi_dekupouch",
+    "object_ganon_anime1",
+    "object_ganon_anime2",
+    "object_ganon_anime3",
+    "object_gi_rupy",
+    "object_spot01_matoya",
+    "object_spot01_matoyab",
+    "object_mu",
+    "object_wf",
+    "object_skb",
+    "object_gj",
+    "object_geff",
+    "object_haka_door",
+    "object_gs",
+    "object_ps",
+    "object_bwall",
+    "object_cow",
+    "object_cob",
+    "object_gi_sword_1",
+    "object_door_killer",
+    "object_ouke_haka",
+    "object_timeblock",
+    "object_zl4",
+];
+
+/// The result of resolving a [`Query`] against the scene table.
+pub struct ResolvedQuery {
+    /// The VROM range of the scene or room file that the query is rooted in.
+    pub file_range: Range<VromAddr>,
+    pub segment_table: SegmentTable,
+    pub target: QueryTarget,
+}
+
+/// Resolves a query rooted at `scene[S]`, `scene[S].room[R]`, `entrance[E]`, `actor[A]`, or
+/// `object[O]`. Scene roots may select an alternate setup, as in `scene[S].setup[N]` or
+/// `scene[S].setup[N].room[R]`.
+pub fn resolve_query(
+    file_table: &FileTable,
+    vrom: Vrom<'_>,
+    query: &Query,
+) -> Result<ResolvedQuery, QueryError> {
+    let (root, index, steps) = match query.steps.as_slice() {
+        [QueryStep::Field(root), QueryStep::Index(index), steps @ ..] => (root, *index, steps),
+        _ => return Err(QueryError::UnknownRoot(query.to_string())),
+    };
+    match root.as_str() {
+        "scene" => resolve_scene_query(file_table, vrom, index, steps),
+        "entrance" => resolve_entrance_query(file_table, vrom, index, steps),
+        "actor" => resolve_actor_query(file_table, vrom, index, steps),
+        "object" => resolve_object_query(file_table, vrom, index, steps),
+        _ => Err(QueryError::UnknownRoot(query.to_string())),
+    }
+}
+
+fn resolve_entrance_query(
+    file_table: &FileTable,
+    vrom: Vrom<'_>,
+    entrance_index: u32,
+    steps: &[QueryStep],
+) -> Result<ResolvedQuery, QueryError> {
+    let entrance_table = get_entrance_table(file_table).map_err(ReadError::from)?;
+    if entrance_index >= entrance_table.len() {
+        return Err(QueryError::IndexOutOfRange {
+            index: entrance_index,
+            len: entrance_table.len(),
+        });
+    }
+    let entrance = entrance_table.get(vrom, entrance_index)?;
+    let segment_table = SegmentTable::new();
+    let target = QueryTarget::root(ENTRANCE_TABLE_ENTRY_DESC, entrance.addr());
+
+    Ok(ResolvedQuery {
+        target: Query::resolve(vrom, &segment_table, target, steps)?,
+        file_range: file_table
+            .file_vrom_range(CODE_FILE_INDEX)
+            .map_err(ReadError::from)?,
+        segment_table,
+    })
+}
+
+fn resolve_actor_query(
+    file_table: &FileTable,
+    vrom: Vrom<'_>,
+    actor_index: u32,
+    steps: &[QueryStep],
+) -> Result<ResolvedQuery, QueryError> {
+    let actor_overlay_table = get_actor_overlay_table(file_table).map_err(ReadError::from)?;
+    if actor_index >= actor_overlay_table.len() {
+        return Err(QueryError::IndexOutOfRange {
+            index: actor_index,
+            len: actor_overlay_table.len(),
+        });
+    }
+    let overlay = actor_overlay_table.get(vrom, actor_index)?;
+    let segment_table = SegmentTable::new();
+    let target = QueryTarget::root(ACTOR_OVERLAY_DESC, overlay.addr());
+
+    Ok(ResolvedQuery {
+        target: Query::resolve(vrom, &segment_table, target, steps)?,
+        file_range: file_table
+            .file_vrom_range(CODE_FILE_INDEX)
+            .map_err(ReadError::from)?,
+        segment_table,
+    })
+}
+
+fn resolve_object_query(
+    file_table: &FileTable,
+    vrom: Vrom<'_>,
+    object_index: u32,
+    steps: &[QueryStep],
+) -> Result<ResolvedQuery, QueryError> {
+    let object_table = get_object_table(file_table).map_err(ReadError::from)?;
+    if object_index >= object_table.len() {
+        return Err(QueryError::IndexOutOfRange {
+            index: object_index,
+            len: object_table.len(),
+        });
+    }
+    let entry = object_table.get(vrom, object_index)?;
+    let segment_table = SegmentTable::new();
+    let target = QueryTarget::root(OBJECT_TABLE_ENTRY_DESC, entry.addr());
+
+    Ok(ResolvedQuery {
+        target: Query::resolve(vrom, &segment_table, target, steps)?,
+        file_range: file_table
+            .file_vrom_range(CODE_FILE_INDEX)
+            .map_err(ReadError::from)?,
+        segment_table,
+    })
+}
+
+fn resolve_scene_query(
+    file_table: &FileTable,
+    vrom: Vrom<'_>,
+    scene_index: u32,
+    steps: &[QueryStep],
+) -> Result<ResolvedQuery, QueryError> {
+    let scene_table = get_scene_table(file_table).map_err(ReadError::from)?;
+    if scene_index >= scene_table.len() {
+        return Err(QueryError::IndexOutOfRange {
+            index: scene_index,
+            len: scene_table.len(),
+        });
+    }
+    let scene = scene_table.get(vrom, scene_index)?.scene(vrom)?;
+    let mut file_range = scene.vrom_range();
+    let mut segment_table = SegmentTable::new().with(Segment::SCENE, scene.addr());
+
+    let (setup, steps) = match steps {
+        [QueryStep::Field(name), QueryStep::Index(setup), steps @ ..] if name == "setup" => {
+            (*setup, steps)
+        }
+        _ => (0, steps),
+    };
+    let scene = scene.setup(vrom, &segment_table, setup)?;
+    let mut target = QueryTarget::root(SCENE_DESC, scene.addr());
+
+    let steps = match steps {
+        [QueryStep::Field(name), QueryStep::Index(room_index), steps @ ..] if name == "room" => {
+            let room_list = find_room_list_header(vrom, scene)
+                .ok_or_else(|| QueryError::NoSuchVariant("RoomList".to_string()))?
+                .room_list(vrom, &segment_table)?;
+            if *room_index >= room_list.len() {
+                return Err(QueryError::IndexOutOfRange {
+                    index: *room_index,
+                    len: room_list.len(),
+                });
+            }
+            file_range = room_list.get(vrom, *room_index)?.room_range(vrom);
+            segment_table = segment_table.with(Segment::ROOM, file_range.start);
+            let room =
+                Room::from_vrom(vrom, file_range.start)?.setup(vrom, &segment_table, setup)?;
+            target = QueryTarget::root(ROOM_DESC, room.addr());
+            steps
+        }
+        _ => steps,
+    };
+
+    Ok(ResolvedQuery {
+        target: Query::resolve(vrom, &segment_table, target, steps)?,
+        file_range,
+        segment_table,
+    })
+}
+
+/// The types that data in this version is reached from. Every other reflected type is reachable
+/// from one of these.
+pub const ROOT_DESCS: &[TypeDescriptor] = &[
+    SCENE_DESC,
+    ROOM_DESC,
+    SCENE_TABLE_ENTRY_DESC,
+    ENTRANCE_TABLE_ENTRY_DESC,
+    ACTOR_OVERLAY_DESC,
+    ACTOR_INIT_DESC,
+    OBJECT_TABLE_ENTRY_DESC,
+];
+
+/// Finds a type by name among the types reachable from scenes and rooms.
+pub fn find_type(name: &str) -> Option<TypeDescriptor> {
+    oot_explorer_reflect::find_type(ROOT_DESCS, name)
+}
+
+/// The instances found by [`search`], along with the scenes that couldn't be fully searched.
+pub struct SearchResults {
+    pub hits: Vec<SearchHit>,
+    /// The index and error of each scene whose scene or rooms failed to read. Hits found before
+    /// the error are still included.
+    pub errors: Vec<(u32, ReadError)>,
+}
+
+/// Finds every instance of `desc` reachable from any scene or room in the scene table for which
+/// `predicate` returns true. A scene that fails to read doesn't stop the search.
+pub fn search<P>(
+    file_table: &FileTable,
+    vrom: Vrom<'_>,
+    desc: TypeDescriptor,
+    mut predicate: P,
+) -> SearchResults
+where
+    P: FnMut(&Value) -> bool,
+{
+    let mut hits = vec![];
+    let mut errors = vec![];
+    for scene_index in 0..SCENE_TABLE_COUNT {
+        let result = for_each_scene_root(
+            file_table,
+            vrom,
+            scene_index,
+            |segment_table, query, target| {
+                hits.extend(oot_explorer_reflect::search(
+                    vrom,
+                    segment_table,
+                    query,
+                    target,
+                    desc,
+                    &mut predicate,
+                ));
+            },
+        );
+        if let Err(e) = result {
+            errors.push((scene_index, e));
+        }
+    }
+    SearchResults { hits, errors }
+}
+
+/// Indexes every pointer and slice reachable from a scene and its rooms.
+pub fn xref_scene(
+    file_table: &FileTable,
+    vrom: Vrom<'_>,
+    scene_index: u32,
+) -> Result<XrefIndex, ReadError> {
+    let mut index = XrefIndex::new();
+    for_each_scene_root(
+        file_table,
+        vrom,
+        scene_index,
+        |segment_table, query, target| index.add(vrom, segment_table, query, target),
+    )?;
+    Ok(index)
+}
+
+/// The index built by [`xref_all`], along with the scenes that couldn't be fully indexed.
+pub struct XrefResults {
+    pub index: XrefIndex,
+    /// The index and error of each scene whose scene or rooms failed to read. References found
+    /// before the error are still indexed.
+    pub errors: Vec<(u32, ReadError)>,
+}
+
+/// Indexes every pointer and slice reachable from any scene or room in the scene table. A scene
+/// that fails to read doesn't stop the indexing.
+pub fn xref_all(file_table: &FileTable, vrom: Vrom<'_>) -> XrefResults {
+    let mut index = XrefIndex::new();
+    let mut errors = vec![];
+    for scene_index in 0..SCENE_TABLE_COUNT {
+        let result = for_each_scene_root(
+            file_table,
+            vrom,
+            scene_index,
+            |segment_table, query, target| index.add(vrom, segment_table, query, target),
+        );
+        if let Err(e) = result {
+            errors.push((scene_index, e));
+        }
+    }
+    XrefResults { index, errors }
+}
+
+/// Calls `f` with the segment table, query, and target for a scene and then for each of its rooms.
+fn for_each_scene_root<F>(
+    file_table: &FileTable,
+    vrom: Vrom<'_>,
+    scene_index: u32,
+    mut f: F,
+) -> Result<(), ReadError>
+where
+    F: FnMut(&SegmentTable, &Query, &QueryTarget),
+{
+    let scene = get_scene_table(file_table)?
+        .get(vrom, scene_index)?
+        .scene(vrom)?
+        .into_inner();
+    let segment_table = SegmentTable::new().with(Segment::SCENE, scene.addr());
+    let scene_query = Query {
+        steps: vec![
+            QueryStep::Field("scene".to_string()),
+            QueryStep::Index(scene_index),
+        ],
+    };
+    f(
+        &segment_table,
+        &scene_query,
+        &QueryTarget::root(SCENE_DESC, scene.addr()),
+    );
+
+    let room_list = find_room_list_header(vrom, scene)
+        .map(|header| header.room_list(vrom, &segment_table))
+        .transpose()?;
+    for (room_index, room_list_entry) in
+        room_list.into_iter().flat_map(|x| x.iter(vrom)).enumerate()
+    {
+        let room_addr = room_list_entry?.room_range(vrom).start;
+        let mut room_query = scene_query.clone();
+        room_query.steps.push(QueryStep::Field("room".to_string()));
+        room_query.steps.push(QueryStep::Index(room_index as u32));
+        f(
+            &segment_table.with(Segment::ROOM, room_addr),
+            &room_query,
+            &QueryTarget::root(ROOM_DESC, room_addr),
+        );
+    }
+    Ok(())
+}
+
+/// Describes a value in terms of data elsewhere in the ROM, like where an exit leads. Returns
+/// `None` for types that have nothing more to say than their fields.
+pub fn summarize(
+    file_table: &FileTable,
+    vrom: Vrom<'_>,
+    desc: TypeDescriptor,
+    addr: VromAddr,
+) -> Option<String> {
+    if desc.name() == EXIT_DESC.name() {
+        let entrance_index = Exit::from_vrom(vrom, addr).ok()?.entrance_index(vrom);
+        let entrance = get_entrance(
+            file_table,
+            vrom,
+            entrance_index.into(),
+            EntranceVariant::ChildDay,
+        )
+        .ok()??;
+        let scene_name = SCENE_NAMES.get(entrance.scene(vrom) as usize)?;
+        Some(format!(
+            "\u{2192} {}, spawn {}",
+            scene_name,
+            entrance.spawn(vrom),
+        ))
+    } else if desc.name() == ACTOR_DESC.name() {
+        let actor = Actor::from_vrom(vrom, addr).ok()?;
+        summarize_actor(actor.actor_number(vrom), actor.init(vrom).0)
+    } else if desc.name() == TRANSITION_ACTOR_DESC.name() {
+        let actor = TransitionActor::from_vrom(vrom, addr).ok()?;
+        summarize_actor(actor.actor_number(vrom), actor.init(vrom).0)
+    } else if desc.name() == ACTOR_INIT_DESC.name() {
+        let init = ActorInit::from_vrom(vrom, addr).ok()?;
+        Some(format!(
+            "{}, using {}",
+            actor_display_name(init.id(vrom))?,
+            object_display_name(init.object_id(vrom))?,
+        ))
+    } else if desc.name() == OBJECT_TABLE_ENTRY_DESC.name() {
+        let object_table = get_object_table(file_table).ok()?;
+        let offset = addr.0.checked_sub(object_table.addr().0)?;
+        let index = offset / ObjectTableEntry::SIZE;
+        if offset % ObjectTableEntry::SIZE != 0 || index >= object_table.len() {
+            return None;
+        }
+        object_display_name(ObjectId(index as u16)).map(str::to_string)
+    } else {
+        None
+    }
+}
+
+/// Names an actor and decodes its params, if the params have a known meaning.
+fn summarize_actor(actor_id: ActorId, params: u16) -> Option<String> {
+    let name = actor_display_name(actor_id)?;
+    Some(match describe_params(actor_id, params) {
+        Some(params) => format!("{}: {}", name, params),
+        None => name,
+    })
+}
+
+fn find_room_list_header(vrom: Vrom<'_>, scene: Scene) -> Option<RoomListHeader> {
+    find_scene_header(vrom, scene, |variant| match variant {
+        SceneHeaderVariant::RoomList(header) => Some(header),
+        _ => None,
+    })
+}
+
+fn find_scene_header<T, F>(vrom: Vrom<'_>, scene: Scene, mut f: F) -> Option<T>
+where
+    F: FnMut(SceneHeaderVariant) -> Option<T>,
+{
+    scene
+        .headers(vrom)
+        .find_map(|header| f(header.ok()?.variant(vrom)))
+}
+
+/// Where an object's file is in VROM.
+#[layout(size = 8, align_bits = 2)]
+pub struct ObjectTableEntry {
+    #[at(0)]
+    vrom_start: VromAddr,
+    #[at(4)]
+    vrom_end: VromAddr,
+}
+
+impl ObjectTableEntry {
+    /// The VROM range of the object's file, or `None` for unused entries.
+    pub fn vrom_range(self, vrom: Vrom<'_>) -> Option<Range<VromAddr>> {
+        let start = self.vrom_start(vrom);
+        let end = self.vrom_end(vrom);
+        if start == end {
+            None
+        } else {
+            Some(start..end)
+        }
     }
 }
 
+#[layout(size = 0x14, align_bits = 2)]
+pub struct SceneTableEntry {
+    #[at(0)]
+    scene_start: VromAddr,
+    #[at(4)]
+    scene_end: VromAddr,
+    #[at(8)]
+    raw_title_card_start: VromAddr,
+    #[at(0xc)]
+    raw_title_card_end: VromAddr,
+    #[at(0x10)]
+    unknown_a: u8,
+    #[at(0x11)]
+    render_init_function: u8,
+    #[at(0x12)]
+    unknown_b: u8,
+}
+
 impl SceneTableEntry {
     pub fn scene_range(self, vrom: Vrom<'_>) -> Range<VromAddr> {
         self.scene_start(vrom)..self.scene_end(vrom)
@@ -50,3 +1740,360 @@ impl SceneTableEntry {
         }
     }
 }
+
+/// Where an entrance index leads.
+#[layout(size = 4, align_bits = 2)]
+pub struct EntranceTableEntry {
+    /// An index into the scene table.
+    #[at(0)]
+    scene: u8,
+    /// An index into the scene's entrance list.
+    #[at(1)]
+    spawn: u8,
+    #[at(2)]
+    flags: EntranceFlags,
+}
+
+impl EntranceTableEntry {
+    /// The scene table entry for the scene that the entrance leads to, or `None` if the scene
+    /// index is out of range.
+    pub fn scene_table_entry(
+        self,
+        vrom: Vrom<'_>,
+        file_table: &FileTable,
+    ) -> Result<Option<SceneTableEntry>, ReadError> {
+        let scene_index = u32::from(self.scene(vrom));
+        if scene_index >= SCENE_TABLE_COUNT {
+            return Ok(None);
+        }
+        Ok(Some(get_scene_table(file_table)?.get(vrom, scene_index)?))
+    }
+
+    /// The element of the destination scene's entrance list that the entrance spawns at, which
+    /// names the start position and room. Returns `None` if the scene has no entrance list or the
+    /// spawn index is out of range.
+    pub fn scene_entrance(
+        self,
+        vrom: Vrom<'_>,
+        file_table: &FileTable,
+    ) -> Result<Option<Entrance>, ReadError> {
+        let scene = match self.scene_table_entry(vrom, file_table)? {
+            Some(entry) => entry.scene(vrom)?.into_inner(),
+            None => return Ok(None),
+        };
+        let segment_table = SegmentTable::new().with(Segment::SCENE, scene.addr());
+        let entrance_list = match find_scene_header(vrom, scene, |variant| match variant {
+            SceneHeaderVariant::EntranceList(header) => Some(header),
+            _ => None,
+        }) {
+            Some(header) => header.entrance_list_auto_len(vrom, &segment_table)?,
+            None => return Ok(None),
+        };
+        let spawn = u32::from(self.spawn(vrom));
+        if spawn >= entrance_list.len() {
+            return Ok(None);
+        }
+        Ok(Some(entrance_list.get(vrom, spawn)?))
+    }
+
+    /// The start position in the destination scene that the entrance spawns at. Returns `None` if
+    /// the scene's headers don't lead to one.
+    pub fn start_position(
+        self,
+        vrom: Vrom<'_>,
+        file_table: &FileTable,
+    ) -> Result<Option<Actor>, ReadError> {
+        let entrance = match self.scene_entrance(vrom, file_table)? {
+            Some(entrance) => entrance,
+            None => return Ok(None),
+        };
+        let scene = match self.scene_table_entry(vrom, file_table)? {
+            Some(entry) => entry.scene(vrom)?.into_inner(),
+            None => return Ok(None),
+        };
+        let segment_table = SegmentTable::new().with(Segment::SCENE, scene.addr());
+        let start_positions = match find_scene_header(vrom, scene, |variant| match variant {
+            SceneHeaderVariant::StartPositions(header) => Some(header),
+            _ => None,
+        }) {
+            Some(header) => header.start_positions(vrom, &segment_table)?,
+            None => return Ok(None),
+        };
+        let index = u32::from(entrance.start_position(vrom));
+        if index >= start_positions.len() {
+            return Ok(None);
+        }
+        Ok(Some(start_positions.get(vrom, index)?))
+    }
+}
+
+// TODO: Codegen for bitfields.
+
+/// How the game transitions into and out of an entrance.
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct EntranceFlags(pub u16);
+
+impl EntranceFlags {
+    /// Whether the background music keeps playing through the transition.
+    pub fn continue_bgm(self) -> bool {
+        self.0 & 0x8000 != 0
+    }
+
+    pub fn display_title_card(self) -> bool {
+        self.0 & 0x4000 != 0
+    }
+
+    /// The transition type used when leaving the previous scene.
+    pub fn end_transition(self) -> u8 {
+        ((self.0 >> 7) & 0x7f) as u8
+    }
+
+    /// The transition type used when entering the destination scene.
+    pub fn start_transition(self) -> u8 {
+        (self.0 & 0x7f) as u8
+    }
+}
+
+impl FromVrom for EntranceFlags {
+    fn from_vrom(vrom: Vrom<'_>, addr: VromAddr) -> Result<Self, ReadError> {
+        Ok(Self(<u16 as FromVrom>::from_vrom(vrom, addr)?))
+    }
+}
+
+impl Layout for EntranceFlags {
+    const SIZE: u32 = 2;
+}
+
+/// Transition types, by value.
+const TRANSITION_TYPES: &[(u32, &str)] = &[
+    (0x00, "WIPE"),
+    (0x01, "TRIFORCE"),
+    (0x02, "FADE_BLACK"),
+    (0x03, "FADE_WHITE"),
+    (0x04, "FADE_BLACK_FAST"),
+    (0x05, "FADE_WHITE_FAST"),
+    (0x06, "FADE_BLACK_SLOW"),
+    (0x07, "FADE_WHITE_SLOW"),
+    (0x08, "WIPE_FAST"),
+    (0x09, "FILL_WHITE2"),
+    (0x0a, "FILL_WHITE"),
+    (0x0b, "INSTANT"),
+    (0x0c, "FILL_BROWN"),
+];
+
+/// Bitfield spans are named after their descriptors.
+macro_rules! span {
+    ($name:literal, $shift:literal, $mask:literal, $values:expr) => {
+        BitfieldSpan {
+            shift: $shift,
+            mask: $mask,
+            desc: &EnumDescriptor {
+                name: $name,
+                underlying: PrimitiveType::U16,
+                values: $values,
+            },
+        }
+    };
+}
+
+pub const ENTRANCE_FLAGS_DESC: TypeDescriptor = TypeDescriptor::Bitfield(&BitfieldDescriptor {
+    name: "EntranceFlags",
+    underlying: PrimitiveType::U16,
+    fields: &[
+        span!("continue_bgm", 15, 1, &[]),
+        span!("display_title_card", 14, 1, &[]),
+        span!("end_transition", 7, 0x7f, TRANSITION_TYPES),
+        span!("start_transition", 0, 0x7f, TRANSITION_TYPES),
+    ],
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actor::{ActorCategory, ACTOR_ID_DESC};
+    use oot_explorer_reflect::ValueKind;
+
+    #[test]
+    fn entrance_flags_match_descriptor() {
+        let data = [0x27, 0x03, 0xc1, 0x02];
+        let vrom = Vrom(&data);
+        let entry = EntranceTableEntry::from_vrom(vrom, VromAddr(0)).unwrap();
+        let flags = entry.flags(vrom);
+        assert!(flags.continue_bgm());
+        assert!(flags.display_title_card());
+        assert_eq!(flags.end_transition(), 2);
+        assert_eq!(flags.start_transition(), 2);
+
+        let value = Value::read(
+            vrom,
+            &SegmentTable::new(),
+            ENTRANCE_TABLE_ENTRY_DESC,
+            VromAddr(0),
+        );
+        let spans = match &value.field("flags").unwrap().kind {
+            ValueKind::Bitfield { spans, .. } => spans,
+            _ => panic!("flags should be a bitfield"),
+        };
+        let spans: Vec<_> = spans.iter().map(|span| (span.value, span.name)).collect();
+        assert_eq!(
+            spans,
+            vec![
+                (1, None),
+                (1, None),
+                (2, Some("FADE_BLACK")),
+                (2, Some("FADE_BLACK")),
+            ],
+        );
+    }
+
+    #[test]
+    fn names_actors() {
+        assert_eq!(
+            actor_display_name(ActorId(0x125)).as_deref(),
+            Some("En_Kusa (bush)"),
+        );
+        assert_eq!(
+            actor_display_name(ActorId(0x1d6)).as_deref(),
+            Some("Obj_Warp2block"),
+        );
+        assert_eq!(actor_display_name(ActorId(0x1d7)), None);
+        assert!(ACTOR_DESCRIPTIONS
+            .windows(2)
+            .all(|pair| pair[0].0 < pair[1].0));
+
+        let data = 0x125_u16.to_be_bytes();
+        let value = Value::read(
+            Vrom(&data),
+            &SegmentTable::new(),
+            ACTOR_ID_DESC,
+            VromAddr(0),
+        );
+        assert!(matches!(
+            value.kind,
+            ValueKind::Enum {
+                name: Some("En_Kusa"),
+                ..
+            }
+        ));
+    }
+
+    /// A file table and VROM holding `code`, with the actor overlay table at its end, and one
+    /// overlay file after it. Actor 0's `ActorInit` is in `code`, actor 2's is in the overlay, and
+    /// actor 1 is unused.
+    fn actor_files() -> (FileTable, Vec<u8>) {
+        let code_end = ACTOR_OVERLAY_TABLE_OFFSET + ACTOR_OVERLAY_TABLE_COUNT * 0x20;
+        let overlay_start = code_end;
+        let mut data = vec![0; (overlay_start + 0x100) as usize];
+        let mut put = |addr: u32, words: &[u32]| {
+            for (i, word) in words.iter().enumerate() {
+                let addr = addr as usize + i * 4;
+                data[addr..addr + 4].copy_from_slice(&word.to_be_bytes());
+            }
+        };
+        let table = ACTOR_OVERLAY_TABLE_OFFSET;
+        put(table + 0x14, &[CODE_VRAM_START + 0x100]);
+        put(
+            table + 0x40,
+            &[
+                overlay_start,
+                overlay_start + 0x100,
+                0x8080_0000,
+                0x8080_0100,
+                0,
+                0x8080_0040,
+            ],
+        );
+        put(0x100, &[0x0000_0200]);
+        put(overlay_start + 0x40, &[0x0002_0400]);
+
+        let mut file_ranges = vec![VromAddr(0)..VromAddr(0); CODE_FILE_INDEX.0 as usize];
+        file_ranges.push(VromAddr(0)..VromAddr(code_end));
+        file_ranges.push(VromAddr(overlay_start)..VromAddr(overlay_start + 0x100));
+        (FileTable::new(file_ranges), data)
+    }
+
+    #[test]
+    fn finds_actor_overlay_files() {
+        let (file_table, data) = actor_files();
+        let vrom = Vrom(&data);
+        let overlay_file = |id| get_actor_overlay_file(&file_table, vrom, ActorId(id)).unwrap();
+        assert_eq!(overlay_file(0), None);
+        assert_eq!(overlay_file(1), None);
+        assert_eq!(overlay_file(2), Some(FileIndex(CODE_FILE_INDEX.0 + 1)));
+        assert_eq!(overlay_file(0x1d7), None);
+    }
+
+    #[test]
+    fn finds_actor_inits() {
+        let (file_table, data) = actor_files();
+        let vrom = Vrom(&data);
+        let init = |id| get_actor_init(&file_table, vrom, ActorId(id)).unwrap();
+
+        let player = init(0).unwrap();
+        assert_eq!(player.addr(), VromAddr(0x100));
+        assert!(player.category(vrom) == ActorCategory::PLAYER);
+        let overlay = file_table
+            .file_vrom_range(FileIndex(CODE_FILE_INDEX.0 + 1))
+            .unwrap();
+        let test = init(2).unwrap();
+        assert_eq!(test.addr(), overlay.start + 0x40);
+        assert!(test.id(vrom) == ActorId(2));
+        assert!(test.category(vrom) == ActorCategory::NPC);
+        assert!(init(1).is_none());
+        assert!(init(0x1d7).is_none());
+    }
+
+    #[test]
+    fn finds_room_object_bases() {
+        let code_end = OBJECT_TABLE_OFFSET + OBJECT_TABLE_COUNT * 8;
+        let room_addr = VromAddr(code_end);
+        let mut data = vec![0; code_end as usize + 0x18];
+        let kusa_entry = (OBJECT_TABLE_OFFSET + 0x12b * 8) as usize;
+        data[kusa_entry..kusa_entry + 8]
+            .copy_from_slice(&[0, 0x01, 0x20, 0x00, 0, 0x01, 0x28, 0x40]);
+        // An object list header for three objects, then the end header and the list.
+        data[code_end as usize..].copy_from_slice(&[
+            0x0b, 3, 0, 0, 0x03, 0, 0, 0x10, 0x14, 0, 0, 0, 0, 0, 0, 0, 0x01, 0x2b, 0, 0x05, 0x10,
+            0x00, 0, 0,
+        ]);
+        let vrom = Vrom(&data);
+        let mut file_ranges = vec![VromAddr(0)..VromAddr(0); CODE_FILE_INDEX.0 as usize];
+        file_ranges.push(VromAddr(0)..room_addr);
+        let file_table = FileTable::new(file_ranges);
+        let segment_table = SegmentTable::new().with(Segment::ROOM, room_addr);
+        let room = Room::from_vrom(vrom, room_addr).unwrap();
+
+        let bases: Vec<_> = get_room_object_bases(&file_table, vrom, &segment_table, room)
+            .unwrap()
+            .into_iter()
+            .map(|(object_id, base)| (object_id.0, base))
+            .collect();
+        assert_eq!(bases, [(0x12b, VromAddr(0x12000))]);
+        assert_eq!(
+            summarize(
+                &file_table,
+                vrom,
+                OBJECT_TABLE_ENTRY_DESC,
+                VromAddr(kusa_entry as u32),
+            )
+            .as_deref(),
+            Some("object_kusa"),
+        );
+    }
+
+    #[test]
+    fn finds_object_files() {
+        let data = [
+            0, 0x01, 0x20, 0x00, 0, 0x01, 0x28, 0x40, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        let vrom = Vrom(&data);
+        let table = Slice::<ObjectTableEntry>::new(VromAddr(0), 2);
+        assert_eq!(
+            table.get(vrom, 0).unwrap().vrom_range(vrom),
+            Some(VromAddr(0x12000)..VromAddr(0x12840)),
+        );
+        assert_eq!(table.get(vrom, 1).unwrap().vrom_range(vrom), None);
+        assert_eq!(object_display_name(ObjectId(0x12b)), Some("object_kusa"));
+        assert_eq!(object_display_name(ObjectId(0x192)), None);
+    }
+}
@@ -0,0 +1,88 @@
+use std::fmt::{self, Debug, Display, Formatter};
+
+/// A fixed-point 0.16 number.
+#[derive(Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Qu0_16(pub u16);
+
+impl Debug for Qu0_16 {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Qu0_16({})", self.as_f32())
+    }
+}
+
+impl Display for Qu0_16 {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_f64())
+    }
+}
+
+impl Qu0_16 {
+    /// The number of steps per unit.
+    pub const SCALE: f64 = 65536.0;
+
+    pub fn as_f32(self) -> f32 {
+        self.0 as f32 / Self::SCALE as f32
+    }
+
+    pub fn as_f64(self) -> f64 {
+        self.0 as f64 / Self::SCALE
+    }
+}
+
+/// A fixed-point 10.2 number.
+#[derive(Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Qu10_2(pub u16);
+
+impl Debug for Qu10_2 {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Qu10_2({})", self.as_f32())
+    }
+}
+
+impl Display for Qu10_2 {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_f64())
+    }
+}
+
+impl Qu10_2 {
+    /// The number of steps per unit.
+    pub const SCALE: f64 = 4.0;
+
+    pub fn as_f32(self) -> f32 {
+        (self.0 & 0x0fff) as f32 / Self::SCALE as f32
+    }
+
+    pub fn as_f64(self) -> f64 {
+        (self.0 & 0x0fff) as f64 / Self::SCALE
+    }
+}
+
+/// A fixed-point 1.11 number.
+#[derive(Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Qu1_11(pub u16);
+
+impl Debug for Qu1_11 {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Qu1_11({})", self.as_f32())
+    }
+}
+
+impl Display for Qu1_11 {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_f64())
+    }
+}
+
+impl Qu1_11 {
+    /// The number of steps per unit.
+    pub const SCALE: f64 = 2048.0;
+
+    pub fn as_f32(self) -> f32 {
+        (self.0 & 0x0fff) as f32 / Self::SCALE as f32
+    }
+
+    pub fn as_f64(self) -> f64 {
+        (self.0 & 0x0fff) as f64 / Self::SCALE
+    }
+}
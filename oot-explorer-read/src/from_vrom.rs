@@ -3,7 +3,7 @@ use oot_explorer_rom::RomAddr;
 use oot_explorer_segment::SegmentAddr;
 use oot_explorer_vrom::{Vrom, VromAddr};
 
-use crate::ReadError;
+use crate::{Qu0_16, Qu10_2, Qu1_11, ReadError, Vec3f, Vec3s};
 
 /// Types that can be constructed with VROM data and an address.
 pub trait FromVrom: Sized {
@@ -52,6 +52,56 @@ impl FromVrom for i32 {
     }
 }
 
+impl FromVrom for u64 {
+    fn from_vrom(vrom: Vrom<'_>, addr: VromAddr) -> Result<Self, ReadError> {
+        Ok(vrom.slice(addr..addr + 8)?.read_u64::<BigEndian>().unwrap())
+    }
+}
+
+impl FromVrom for f32 {
+    fn from_vrom(vrom: Vrom<'_>, addr: VromAddr) -> Result<Self, ReadError> {
+        Ok(vrom.slice(addr..addr + 4)?.read_f32::<BigEndian>().unwrap())
+    }
+}
+
+impl FromVrom for Qu0_16 {
+    fn from_vrom(vrom: Vrom<'_>, addr: VromAddr) -> Result<Self, ReadError> {
+        Ok(Qu0_16(u16::from_vrom(vrom, addr)?))
+    }
+}
+
+impl FromVrom for Qu10_2 {
+    fn from_vrom(vrom: Vrom<'_>, addr: VromAddr) -> Result<Self, ReadError> {
+        Ok(Qu10_2(u16::from_vrom(vrom, addr)?))
+    }
+}
+
+impl FromVrom for Qu1_11 {
+    fn from_vrom(vrom: Vrom<'_>, addr: VromAddr) -> Result<Self, ReadError> {
+        Ok(Qu1_11(u16::from_vrom(vrom, addr)?))
+    }
+}
+
+impl FromVrom for Vec3s {
+    fn from_vrom(vrom: Vrom<'_>, addr: VromAddr) -> Result<Self, ReadError> {
+        Ok(Vec3s {
+            x: i16::from_vrom(vrom, addr)?,
+            y: i16::from_vrom(vrom, addr + 2)?,
+            z: i16::from_vrom(vrom, addr + 4)?,
+        })
+    }
+}
+
+impl FromVrom for Vec3f {
+    fn from_vrom(vrom: Vrom<'_>, addr: VromAddr) -> Result<Self, ReadError> {
+        Ok(Vec3f {
+            x: f32::from_vrom(vrom, addr)?,
+            y: f32::from_vrom(vrom, addr + 4)?,
+            z: f32::from_vrom(vrom, addr + 8)?,
+        })
+    }
+}
+
 impl FromVrom for RomAddr {
     fn from_vrom(vrom: Vrom<'_>, addr: VromAddr) -> Result<Self, ReadError> {
         Ok(RomAddr(u32::from_vrom(vrom, addr)?))
@@ -0,0 +1,147 @@
+use oot_explorer_vrom::VromAddr;
+use std::iter::FromIterator;
+
+/// The most elements an inferred length may ever yield.
+///
+/// Inferences are heuristics over data that may be garbage. This cap keeps a bad guess from turning
+/// into an enormous read.
+pub const MAX_INFERRED_LEN: u32 = 0x1000;
+
+/// Strategies for inferring the length of a list whose element count isn't stored anywhere.
+pub trait InferLen {
+    /// Infers the number of elements of `element_size` bytes each in a list starting at `addr`.
+    fn infer_len(&self, addr: VromAddr, element_size: u32) -> u32;
+}
+
+/// A known length.
+impl InferLen for u32 {
+    fn infer_len(&self, _addr: VromAddr, _element_size: u32) -> u32 {
+        *self
+    }
+}
+
+impl<T> InferLen for &T
+where
+    T: InferLen + ?Sized,
+{
+    fn infer_len(&self, addr: VromAddr, element_size: u32) -> u32 {
+        (**self).infer_len(addr, element_size)
+    }
+}
+
+/// Infers the length as one more than the largest index referenced elsewhere.
+///
+/// Collect one from an iterator of indices, or from an iterator of `Result`s of indices.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct MaxIndex(pub Option<u32>);
+
+impl InferLen for MaxIndex {
+    fn infer_len(&self, _addr: VromAddr, _element_size: u32) -> u32 {
+        match self.0 {
+            Some(index) => index.saturating_add(1),
+            None => 0,
+        }
+    }
+}
+
+impl FromIterator<u32> for MaxIndex {
+    fn from_iter<I: IntoIterator<Item = u32>>(iter: I) -> Self {
+        MaxIndex(iter.into_iter().max())
+    }
+}
+
+/// Infers the length as the number of whole elements that fit before a known end address.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct UntilAddr(pub VromAddr);
+
+impl InferLen for UntilAddr {
+    fn infer_len(&self, addr: VromAddr, element_size: u32) -> u32 {
+        let UntilAddr(end) = *self;
+        end.0.saturating_sub(addr.0) / element_size
+    }
+}
+
+/// Infers the length as the number of whole elements that fit before the next known structure.
+///
+/// Addresses at or before the start of the list are ignored. If no known address follows the list,
+/// the inferred length is zero.
+#[derive(Clone, Copy, Debug)]
+pub struct UntilNext<'a>(pub &'a [VromAddr]);
+
+impl<'a> InferLen for UntilNext<'a> {
+    fn infer_len(&self, addr: VromAddr, element_size: u32) -> u32 {
+        match self.0.iter().copied().filter(|&next| next > addr).min() {
+            Some(next) => UntilAddr(next).infer_len(addr, element_size),
+            None => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_len_ignores_element_size() {
+        assert_eq!(5.infer_len(VromAddr(0x100), 0x10), 5);
+        assert_eq!((&&5).infer_len(VromAddr(0x100), 0x10), 5);
+    }
+
+    #[test]
+    fn max_index_is_one_past_largest_index() {
+        let max_index: MaxIndex = vec![2, 7, 0].into_iter().collect();
+        assert_eq!(max_index, MaxIndex(Some(7)));
+        assert_eq!(max_index.infer_len(VromAddr(0), 4), 8);
+        assert_eq!(MaxIndex(Some(u32::MAX)).infer_len(VromAddr(0), 4), u32::MAX);
+    }
+
+    #[test]
+    fn max_index_of_nothing_is_empty() {
+        let max_index: MaxIndex = Vec::new().into_iter().collect();
+        assert_eq!(max_index, MaxIndex(None));
+        assert_eq!(max_index.infer_len(VromAddr(0), 4), 0);
+    }
+
+    #[test]
+    fn max_index_collects_results() {
+        let max_index: Result<MaxIndex, ()> = vec![Ok(3), Ok(1)].into_iter().collect();
+        assert_eq!(max_index, Ok(MaxIndex(Some(3))));
+        let max_index: Result<MaxIndex, ()> = vec![Ok(3), Err(())].into_iter().collect();
+        assert_eq!(max_index, Err(()));
+    }
+
+    #[test]
+    fn until_addr_counts_whole_elements() {
+        assert_eq!(
+            UntilAddr(VromAddr(0x130)).infer_len(VromAddr(0x100), 0x10),
+            3
+        );
+        assert_eq!(
+            UntilAddr(VromAddr(0x138)).infer_len(VromAddr(0x100), 0x10),
+            3
+        );
+        assert_eq!(
+            UntilAddr(VromAddr(0x80)).infer_len(VromAddr(0x100), 0x10),
+            0
+        );
+    }
+
+    #[test]
+    fn until_next_uses_nearest_following_addr() {
+        let known = [
+            VromAddr(0x200),
+            VromAddr(0x100),
+            VromAddr(0x80),
+            VromAddr(0x140),
+        ];
+        assert_eq!(UntilNext(&known).infer_len(VromAddr(0x100), 0x10), 4);
+        assert_eq!(UntilNext(&known).infer_len(VromAddr(0x140), 0x10), 0xc);
+    }
+
+    #[test]
+    fn until_next_without_following_addr_is_empty() {
+        let known = [VromAddr(0x80), VromAddr(0x100)];
+        assert_eq!(UntilNext(&known).infer_len(VromAddr(0x100), 0x10), 0);
+        assert_eq!(UntilNext(&[]).infer_len(VromAddr(0x100), 0x10), 0);
+    }
+}
@@ -2,7 +2,7 @@ use oot_explorer_rom::RomAddr;
 use oot_explorer_segment::SegmentAddr;
 use oot_explorer_vrom::{Vrom, VromAddr};
 
-use crate::ReadError;
+use crate::{Qu0_16, Qu10_2, Qu1_11, ReadError, Vec3f, Vec3s};
 
 /// Types that read a value of statically known size and alignment.
 pub trait Layout {
@@ -38,6 +38,34 @@ impl Layout for i32 {
     const SIZE: u32 = 4;
 }
 
+impl Layout for u64 {
+    const SIZE: u32 = 8;
+}
+
+impl Layout for f32 {
+    const SIZE: u32 = 4;
+}
+
+impl Layout for Qu0_16 {
+    const SIZE: u32 = 2;
+}
+
+impl Layout for Qu10_2 {
+    const SIZE: u32 = 2;
+}
+
+impl Layout for Qu1_11 {
+    const SIZE: u32 = 2;
+}
+
+impl Layout for Vec3s {
+    const SIZE: u32 = 6;
+}
+
+impl Layout for Vec3f {
+    const SIZE: u32 = 12;
+}
+
 impl Layout for RomAddr {
     const SIZE: u32 = 4;
 }
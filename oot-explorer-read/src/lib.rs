@@ -1,13 +1,19 @@
 mod error;
+mod fixed_point;
 mod from_vrom;
+mod infer_len;
 mod layout;
 mod sentinel;
 mod slice;
+mod vec3;
 mod vrom_proxy;
 
 pub use error::ReadError;
+pub use fixed_point::{Qu0_16, Qu10_2, Qu1_11};
 pub use from_vrom::FromVrom;
+pub use infer_len::{InferLen, MaxIndex, UntilAddr, UntilNext, MAX_INFERRED_LEN};
 pub use layout::{aligned_data, check_alignment, Layout};
 pub use sentinel::{is_end, Sentinel, SentinelIter};
 pub use slice::{Slice, SliceIter};
+pub use vec3::{Vec3f, Vec3s};
 pub use vrom_proxy::VromProxy;
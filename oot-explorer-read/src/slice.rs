@@ -2,7 +2,7 @@ use oot_explorer_vrom::{Vrom, VromAddr};
 use std::iter::FusedIterator;
 use std::marker::PhantomData;
 
-use crate::{FromVrom, Layout, ReadError};
+use crate::{FromVrom, InferLen, Layout, ReadError, MAX_INFERRED_LEN};
 
 /// A contiguous slice of sized values in VROM.
 #[derive(Clone, Copy)]
@@ -27,6 +27,20 @@ where
         }
     }
 
+    /// Creates a slice whose length isn't stored in VROM, but inferred by some other means.
+    ///
+    /// The length is capped at [`MAX_INFERRED_LEN`].
+    pub fn with_inferred_len(addr: VromAddr, inference: impl InferLen) -> Self {
+        Self::new(
+            addr,
+            inference.infer_len(addr, T::SIZE).min(MAX_INFERRED_LEN),
+        )
+    }
+
+    pub fn addr(self) -> VromAddr {
+        self.addr
+    }
+
     pub fn len(self) -> u32 {
         self.len
     }
@@ -0,0 +1,29 @@
+use std::fmt::{self, Display, Formatter};
+
+/// A vector of three signed 16-bit integers, packed as x, y, z.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Vec3s {
+    pub x: i16,
+    pub y: i16,
+    pub z: i16,
+}
+
+/// A vector of three single-precision floats, packed as x, y, z.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Vec3f {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+impl Display for Vec3s {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+impl Display for Vec3f {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
@@ -0,0 +1,597 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+
+use crate::{Value, ValueKind};
+
+/// One difference between two value trees.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Difference {
+    /// The path to the value relative to the roots, like `.headers[2].actor_list[3].pos_x`.
+    ///
+    /// Indices are into the new tree, except for removed elements, which are indexed into the old
+    /// tree.
+    pub path: String,
+    #[serde(flatten)]
+    pub kind: DifferenceKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "change", rename_all = "camelCase")]
+pub enum DifferenceKind {
+    Added { new: Value },
+    Removed { old: Value },
+    Changed { old: Value, new: Value },
+}
+
+/// Compares two value trees structurally.
+///
+/// Struct fields are aligned by name. Unions are compared variant-wise when their discriminants
+/// match. Pointers are compared by what they point to, so data that only moved isn't reported.
+/// Slice and list elements are aligned by a longest common subsequence, where union elements
+/// correspond when their discriminants match and other elements correspond when they're equal;
+/// runs of unaligned elements are compared pairwise, and any excess is added or removed. Very long
+/// lists are only aligned by their common prefix and suffix.
+///
+/// Repeated structs and unions are equal if their first occurrences are at the same path in each
+/// tree.
+pub fn diff(old: &Value, new: &Value) -> Vec<Difference> {
+    let differ = Differ {
+        old_origins: origins(old),
+        new_origins: origins(new),
+    };
+    let mut differences = vec![];
+    differ.diff_into(&mut differences, &mut String::new(), old, new);
+    differences
+}
+
+/// Lists with more pairs of elements than this are aligned positionally, after their common
+/// prefix and suffix, rather than by a longest common subsequence.
+const MAX_ALIGNED_PAIRS: usize = 0x10_0000;
+
+/// The paths to the first occurrences of the structs and unions in a tree, by address and type
+/// name, which is how [`ValueKind::Reference`] stubs refer to them.
+type Origins<'a> = HashMap<(u32, &'a str), String>;
+
+fn origins(root: &Value) -> Origins<'_> {
+    fn visit<'a>(origins: &mut Origins<'a>, path: &mut String, value: &'a Value) {
+        match &value.kind {
+            ValueKind::Struct { fields } => {
+                origins
+                    .entry((value.addr, &value.type_name))
+                    .or_insert_with(|| path.clone());
+                for field in fields {
+                    let len = path.len();
+                    path.push('.');
+                    path.push_str(field.name);
+                    visit(origins, path, &field.value);
+                    path.truncate(len);
+                }
+            }
+            ValueKind::Union { variant, .. } => {
+                origins
+                    .entry((value.addr, &value.type_name))
+                    .or_insert_with(|| path.clone());
+                if let Some(variant) = variant {
+                    visit(origins, path, variant);
+                }
+            }
+            ValueKind::Pointer {
+                target: Some(target),
+                ..
+            } => visit(origins, path, target),
+            ValueKind::Slice { elements, .. } | ValueKind::List { elements } => {
+                for (index, element) in elements.iter().enumerate() {
+                    let len = path.len();
+                    path.push_str(&format!("[{}]", index));
+                    visit(origins, path, element);
+                    path.truncate(len);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    let mut origins = HashMap::new();
+    visit(&mut origins, &mut String::new(), root);
+    origins
+}
+
+struct Differ<'a> {
+    old_origins: Origins<'a>,
+    new_origins: Origins<'a>,
+}
+
+impl<'a> Differ<'a> {
+    fn diff_into(
+        &self,
+        differences: &mut Vec<Difference>,
+        path: &mut String,
+        old: &Value,
+        new: &Value,
+    ) {
+        let changed = |differences: &mut Vec<Difference>, path: &str| {
+            differences.push(Difference {
+                path: path.to_string(),
+                kind: DifferenceKind::Changed {
+                    old: old.clone(),
+                    new: new.clone(),
+                },
+            })
+        };
+
+        if old.type_name != new.type_name {
+            return changed(differences, path);
+        }
+
+        match (&old.kind, &new.kind) {
+            (
+                ValueKind::Struct { fields: old_fields },
+                ValueKind::Struct { fields: new_fields },
+            ) => {
+                for old_field in old_fields {
+                    let len = path.len();
+                    path.push('.');
+                    path.push_str(old_field.name);
+                    match new_fields.iter().find(|field| field.name == old_field.name) {
+                        Some(new_field) => {
+                            self.diff_into(differences, path, &old_field.value, &new_field.value)
+                        }
+                        None => differences.push(Difference {
+                            path: path.clone(),
+                            kind: DifferenceKind::Removed {
+                                old: old_field.value.clone(),
+                            },
+                        }),
+                    }
+                    path.truncate(len);
+                }
+                for new_field in new_fields {
+                    if !old_fields.iter().any(|field| field.name == new_field.name) {
+                        differences.push(Difference {
+                            path: format!("{}.{}", path, new_field.name),
+                            kind: DifferenceKind::Added {
+                                new: new_field.value.clone(),
+                            },
+                        });
+                    }
+                }
+            }
+
+            (
+                ValueKind::Union {
+                    discriminant: old_discriminant,
+                    variant: old_variant,
+                },
+                ValueKind::Union {
+                    discriminant: new_discriminant,
+                    variant: new_variant,
+                },
+            ) => match (old_variant, new_variant) {
+                (Some(old_variant), Some(new_variant))
+                    if self.same(old_discriminant, new_discriminant) =>
+                {
+                    self.diff_into(differences, path, old_variant, new_variant)
+                }
+                _ if self.same(old, new) => (),
+                _ => changed(differences, path),
+            },
+
+            (
+                ValueKind::Pointer {
+                    target: old_target, ..
+                },
+                ValueKind::Pointer {
+                    target: new_target, ..
+                },
+            ) => match (old_target, new_target) {
+                (Some(old_target), Some(new_target)) => {
+                    self.diff_into(differences, path, old_target, new_target)
+                }
+                (None, None) => (),
+                _ => changed(differences, path),
+            },
+
+            (
+                ValueKind::Slice {
+                    elements: old_elements,
+                    ..
+                },
+                ValueKind::Slice {
+                    elements: new_elements,
+                    ..
+                },
+            )
+            | (
+                ValueKind::List {
+                    elements: old_elements,
+                },
+                ValueKind::List {
+                    elements: new_elements,
+                },
+            ) => self.diff_elements(differences, path, old_elements, new_elements),
+
+            _ if self.same(old, new) => (),
+            _ => changed(differences, path),
+        }
+    }
+
+    fn diff_elements(
+        &self,
+        differences: &mut Vec<Difference>,
+        path: &mut String,
+        old: &[Value],
+        new: &[Value],
+    ) {
+        let mut old_index = 0;
+        let mut new_index = 0;
+        let anchors = self.align(old, new);
+        for &(old_anchor, new_anchor) in anchors.iter().chain(Some(&(old.len(), new.len()))) {
+            // Compare the unaligned run before this anchor pairwise.
+            while old_index < old_anchor || new_index < new_anchor {
+                let len = path.len();
+                if old_index < old_anchor && new_index < new_anchor {
+                    path.push_str(&format!("[{}]", new_index));
+                    self.diff_into(differences, path, &old[old_index], &new[new_index]);
+                    old_index += 1;
+                    new_index += 1;
+                } else if old_index < old_anchor {
+                    path.push_str(&format!("[{}]", old_index));
+                    differences.push(Difference {
+                        path: path.clone(),
+                        kind: DifferenceKind::Removed {
+                            old: old[old_index].clone(),
+                        },
+                    });
+                    old_index += 1;
+                } else {
+                    path.push_str(&format!("[{}]", new_index));
+                    differences.push(Difference {
+                        path: path.clone(),
+                        kind: DifferenceKind::Added {
+                            new: new[new_index].clone(),
+                        },
+                    });
+                    new_index += 1;
+                }
+                path.truncate(len);
+            }
+
+            // Compare the anchor itself.
+            if old_anchor < old.len() && new_anchor < new.len() {
+                let len = path.len();
+                path.push_str(&format!("[{}]", new_anchor));
+                self.diff_into(differences, path, &old[old_anchor], &new[new_anchor]);
+                path.truncate(len);
+                old_index = old_anchor + 1;
+                new_index = new_anchor + 1;
+            }
+        }
+    }
+
+    /// Finds a longest common subsequence of corresponding elements, as pairs of indices.
+    ///
+    /// Only the elements between the common prefix and suffix are searched. If there are too many
+    /// of them, they're left unaligned to be compared positionally.
+    fn align(&self, old: &[Value], new: &[Value]) -> Vec<(usize, usize)> {
+        let prefix = old
+            .iter()
+            .zip(new)
+            .take_while(|(a, b)| self.corresponds(a, b))
+            .count();
+        let suffix = old[prefix..]
+            .iter()
+            .rev()
+            .zip(new[prefix..].iter().rev())
+            .take_while(|(a, b)| self.corresponds(a, b))
+            .count();
+        let old_middle = &old[prefix..old.len() - suffix];
+        let new_middle = &new[prefix..new.len() - suffix];
+
+        let mut anchors: Vec<_> = (0..prefix).map(|i| (i, i)).collect();
+        if old_middle.len().saturating_mul(new_middle.len()) <= MAX_ALIGNED_PAIRS {
+            anchors.extend(
+                self.align_lcs(old_middle, new_middle)
+                    .into_iter()
+                    .map(|(i, j)| (prefix + i, prefix + j)),
+            );
+        }
+        anchors.extend(
+            (0..suffix)
+                .rev()
+                .map(|k| (old.len() - 1 - k, new.len() - 1 - k)),
+        );
+        anchors
+    }
+
+    fn align_lcs(&self, old: &[Value], new: &[Value]) -> Vec<(usize, usize)> {
+        // lengths[i][j] is the length of the LCS of old[i..] and new[j..].
+        let mut lengths = vec![vec![0u32; new.len() + 1]; old.len() + 1];
+        for i in (0..old.len()).rev() {
+            for j in (0..new.len()).rev() {
+                lengths[i][j] = if self.corresponds(&old[i], &new[j]) {
+                    lengths[i + 1][j + 1] + 1
+                } else {
+                    lengths[i + 1][j].max(lengths[i][j + 1])
+                };
+            }
+        }
+
+        let mut anchors = vec![];
+        let (mut i, mut j) = (0, 0);
+        while i < old.len() && j < new.len() {
+            if self.corresponds(&old[i], &new[j]) {
+                anchors.push((i, j));
+                i += 1;
+                j += 1;
+            } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        anchors
+    }
+
+    /// Whether two list elements should be aligned with each other.
+    fn corresponds(&self, old: &Value, new: &Value) -> bool {
+        match (&old.kind, &new.kind) {
+            (
+                ValueKind::Union {
+                    discriminant: old_discriminant,
+                    ..
+                },
+                ValueKind::Union {
+                    discriminant: new_discriminant,
+                    ..
+                },
+            ) => old.type_name == new.type_name && self.same(old_discriminant, new_discriminant),
+            _ => self.same(old, new),
+        }
+    }
+
+    /// Whether two values are structurally equal, ignoring addresses.
+    fn same(&self, old: &Value, new: &Value) -> bool {
+        if old.type_name != new.type_name {
+            return false;
+        }
+        match (&old.kind, &new.kind) {
+            (
+                ValueKind::Struct { fields: old_fields },
+                ValueKind::Struct { fields: new_fields },
+            ) => {
+                old_fields.len() == new_fields.len()
+                    && old_fields
+                        .iter()
+                        .zip(new_fields)
+                        .all(|(a, b)| a.name == b.name && self.same(&a.value, &b.value))
+            }
+            (
+                ValueKind::Union {
+                    discriminant: old_discriminant,
+                    variant: old_variant,
+                },
+                ValueKind::Union {
+                    discriminant: new_discriminant,
+                    variant: new_variant,
+                },
+            ) => {
+                self.same(old_discriminant, new_discriminant)
+                    && self.same_option(old_variant, new_variant)
+            }
+            (
+                ValueKind::Pointer {
+                    target: old_target, ..
+                },
+                ValueKind::Pointer {
+                    target: new_target, ..
+                },
+            ) => self.same_option(old_target, new_target),
+            (
+                ValueKind::Slice {
+                    elements: old_elements,
+                    ..
+                },
+                ValueKind::Slice {
+                    elements: new_elements,
+                    ..
+                },
+            )
+            | (
+                ValueKind::List {
+                    elements: old_elements,
+                },
+                ValueKind::List {
+                    elements: new_elements,
+                },
+            ) => {
+                old_elements.len() == new_elements.len()
+                    && old_elements
+                        .iter()
+                        .zip(new_elements)
+                        .all(|(a, b)| self.same(a, b))
+            }
+            // Repeated values are the same if they repeat the same place in each tree.
+            (ValueKind::Reference, ValueKind::Reference) => {
+                let old_origin = self.old_origins.get(&(old.addr, old.type_name.as_str()));
+                let new_origin = self.new_origins.get(&(new.addr, new.type_name.as_str()));
+                old_origin.is_some() && old_origin == new_origin
+            }
+            (old_kind, new_kind) => old_kind == new_kind,
+        }
+    }
+
+    fn same_option(&self, old: &Option<Box<Value>>, new: &Option<Box<Value>>) -> bool {
+        match (old, new) {
+            (Some(old), Some(new)) => self.same(old, new),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
+impl Display for Difference {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let path = if self.path.is_empty() {
+            "."
+        } else {
+            &self.path
+        };
+        match &self.kind {
+            DifferenceKind::Added { new } => write!(f, "+ {}: {}", path, Summary(new)),
+            DifferenceKind::Removed { old } => write!(f, "- {}: {}", path, Summary(old)),
+            DifferenceKind::Changed { old, new } => {
+                write!(f, "~ {}: {} -> {}", path, Summary(old), Summary(new))
+            }
+        }
+    }
+}
+
+/// Formats a value on one line, omitting the contents of compound values.
+struct Summary<'a>(&'a Value);
+
+impl<'a> Display for Summary<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let value = self.0;
+        match &value.kind {
+            ValueKind::Primitive { value } => write!(f, "{}", value),
+            ValueKind::Enum {
+                name: Some(name), ..
+            } => write!(f, "{}", name),
+            ValueKind::Enum { value, name: None } | ValueKind::Bitfield { value, .. } => {
+                write!(f, "0x{:x}", value)
+            }
+            ValueKind::Union {
+                discriminant,
+                variant,
+            } => match variant {
+                Some(variant) => write!(f, "({}) {}", value.type_name, variant.type_name),
+                None => write!(f, "({}) {}", value.type_name, Summary(discriminant)),
+            },
+            ValueKind::Pointer { target: None, .. } => write!(f, "({}) NULL", value.type_name),
+            ValueKind::Slice { elements, .. } | ValueKind::List { elements } => {
+                write!(f, "({}) {} elements", value.type_name, elements.len())
+            }
+            ValueKind::UnknownVariant { discriminant, .. } => {
+                write!(f, "unknown variant 0x{:02x}", discriminant)
+            }
+            ValueKind::UnresolvedPointer { message, .. } | ValueKind::Error { message } => {
+                write!(f, "error: {}", message)
+            }
+            ValueKind::Struct { .. } | ValueKind::Pointer { .. } => {
+                write!(f, "({})", value.type_name)
+            }
+            ValueKind::Reference => {
+                write!(f, "({}) repeated 0x{:08x}", value.type_name, value.addr)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Field, PrimitiveValue};
+
+    fn int(addr: u32, x: i64) -> Value {
+        Value {
+            addr,
+            type_name: "i16".to_string(),
+            kind: ValueKind::Primitive {
+                value: PrimitiveValue::Int(x),
+            },
+        }
+    }
+
+    fn point(addr: u32, x: i64, y: i64) -> Value {
+        Value {
+            addr,
+            type_name: "Point".to_string(),
+            kind: ValueKind::Struct {
+                fields: vec![
+                    Field {
+                        name: "x",
+                        value: int(addr, x),
+                    },
+                    Field {
+                        name: "y",
+                        value: int(addr + 2, y),
+                    },
+                ],
+            },
+        }
+    }
+
+    fn list(addr: u32, elements: Vec<Value>) -> Value {
+        Value {
+            addr,
+            type_name: "Point[..]".to_string(),
+            kind: ValueKind::List { elements },
+        }
+    }
+
+    fn paths(differences: &[Difference]) -> Vec<String> {
+        differences.iter().map(|d| d.to_string()).collect()
+    }
+
+    #[test]
+    fn ignores_addresses() {
+        assert_eq!(diff(&point(0, 1, 2), &point(0x100, 1, 2)), vec![]);
+    }
+
+    #[test]
+    fn reports_changed_fields() {
+        assert_eq!(
+            paths(&diff(&point(0, 1, 2), &point(0, 1, 3))),
+            vec!["~ .y: 2 -> 3"],
+        );
+    }
+
+    #[test]
+    fn aligns_inserted_elements() {
+        let old = list(0, vec![point(0, 1, 1), point(4, 2, 2)]);
+        let new = list(0, vec![point(0, 1, 1), point(4, 9, 9), point(8, 2, 2)]);
+        assert_eq!(paths(&diff(&old, &new)), vec!["+ [1]: (Point)"]);
+    }
+
+    #[test]
+    fn pairs_unaligned_elements() {
+        let old = list(0, vec![point(0, 1, 1), point(4, 2, 2), point(8, 3, 3)]);
+        let new = list(0, vec![point(0, 1, 1), point(4, 2, 5)]);
+        assert_eq!(
+            paths(&diff(&old, &new)),
+            vec!["~ [1].y: 2 -> 5", "- [2]: (Point)"],
+        );
+    }
+
+    #[test]
+    fn compares_repeated_values_by_what_they_repeat() {
+        let reference = |addr| Value {
+            addr,
+            type_name: "Point".to_string(),
+            kind: ValueKind::Reference,
+        };
+        let old = list(0, vec![point(0, 1, 1), point(4, 2, 2), reference(0)]);
+        let new = list(0, vec![point(0, 1, 1), point(4, 2, 2), reference(4)]);
+        assert_eq!(
+            paths(&diff(&old, &new)),
+            vec!["~ [2]: (Point) repeated 0x00000000 -> (Point) repeated 0x00000004"],
+        );
+        assert_eq!(diff(&old, &old), vec![]);
+    }
+
+    #[test]
+    fn aligns_long_lists_around_their_common_ends() {
+        let old: Vec<_> = (0..0x1000).map(|i| point(i * 4, i.into(), 0)).collect();
+        let mut new = old.clone();
+        new.insert(0x800, point(0, -1, -1));
+        assert_eq!(
+            paths(&diff(&list(0, old.clone()), &list(0, new))),
+            vec!["+ [2048]: (Point)"],
+        );
+
+        // Without common ends, lists that are too long to align are compared positionally.
+        let new: Vec<_> = (0..0x1000).map(|i| point(i * 4, i.into(), 1)).collect();
+        assert_eq!(diff(&list(0, old), &list(0, new)).len(), 0x1000);
+    }
+}
@@ -14,3 +14,14 @@ impl EnumDescriptor {
         self.underlying.read_as_u32(vrom, addr)
     }
 }
+
+/// Pairs names with their indices, for enums whose values are named by a table indexed by value.
+pub const fn indexed_names<const N: usize>(names: [&'static str; N]) -> [(u32, &'static str); N] {
+    let mut values = [(0, ""); N];
+    let mut i = 0;
+    while i < N {
+        values[i] = (i as u32, names[i]);
+        i += 1;
+    }
+    values
+}
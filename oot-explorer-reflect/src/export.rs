@@ -0,0 +1,330 @@
+//! Generators that describe types in other languages, for use outside of this project.
+
+use std::collections::HashSet;
+
+use crate::{StructDescriptor, StructFieldLocation, TypeDescriptor};
+
+mod c;
+mod kaitai;
+
+pub use c::c_header;
+pub use kaitai::kaitai;
+
+/// Lists every named type reachable from `roots`, each after the types it contains by value.
+pub fn collect_types(roots: &[TypeDescriptor]) -> Vec<TypeDescriptor> {
+    fn visit(
+        visited: &mut HashSet<&'static str>,
+        types: &mut Vec<TypeDescriptor>,
+        desc: TypeDescriptor,
+    ) {
+        if let TypeDescriptor::Pointer(desc) = desc {
+            return visit(visited, types, desc.target());
+        }
+        if let TypeDescriptor::Primitive(_) = desc {
+            return;
+        }
+        if !visited.insert(desc.name()) {
+            return;
+        }
+        match desc {
+            TypeDescriptor::Struct(desc) => {
+                for field in desc.fields {
+                    visit(visited, types, field.desc);
+                }
+            }
+            TypeDescriptor::Union(desc) => {
+                visit(visited, types, desc.discriminant_desc);
+                for &(_, variant) in desc.variants {
+                    visit(visited, types, variant);
+                }
+                if let Some(fallback) = desc.fallback {
+                    visit(visited, types, fallback);
+                }
+            }
+            TypeDescriptor::Bitfield(desc) => {
+                // Spans without named values are plain numbers.
+                for span in desc
+                    .fields
+                    .iter()
+                    .filter(|span| !span.desc.values.is_empty())
+                {
+                    visit(visited, types, TypeDescriptor::Enum(span.desc));
+                }
+            }
+            TypeDescriptor::Enum(_) | TypeDescriptor::Primitive(_) | TypeDescriptor::Pointer(_) => {
+            }
+        }
+        types.push(desc);
+    }
+
+    let mut visited = HashSet::new();
+    let mut types = vec![];
+    for &root in roots {
+        visit(&mut visited, &mut types, root);
+    }
+    types
+}
+
+/// One piece of a struct's layout.
+struct Member {
+    offset: u32,
+    name: String,
+    kind: MemberKind,
+}
+
+enum MemberKind {
+    /// A value stored in place.
+    Value(TypeDescriptor),
+    /// A segment address of a `target`, or of `count` consecutive `target`s.
+    SegmentAddr {
+        target: TypeDescriptor,
+        count: Option<String>,
+    },
+    /// Consecutive values stored in place, ending with one that's recognized as the end.
+    DelimitedList(TypeDescriptor),
+    /// Consecutive values stored in place, as many as the member named `count` says.
+    InlineSlice {
+        element: TypeDescriptor,
+        count: String,
+    },
+}
+
+impl Member {
+    /// The size of the member, or of the first element of an inline list.
+    fn size(&self) -> u32 {
+        match self.kind {
+            MemberKind::Value(desc)
+            | MemberKind::DelimitedList(desc)
+            | MemberKind::InlineSlice { element: desc, .. } => desc.size().unwrap_or(0),
+            MemberKind::SegmentAddr { .. } => 4,
+        }
+    }
+}
+
+/// Lists a struct's members in order of offset.
+fn members(desc: &StructDescriptor) -> Vec<Member> {
+    let mut members = vec![];
+    for field in desc.fields {
+        match field.location {
+            StructFieldLocation::Simple { offset } => members.push(Member {
+                offset,
+                name: field.name.to_string(),
+                kind: match field.desc {
+                    TypeDescriptor::Pointer(pointer) => MemberKind::SegmentAddr {
+                        target: pointer.target(),
+                        count: None,
+                    },
+                    desc => MemberKind::Value(desc),
+                },
+            }),
+            StructFieldLocation::Slice {
+                count_offset,
+                count_desc,
+                count_span,
+                ptr_offset,
+            } => {
+                // A count that shares its bits is part of a member that's already described.
+                let count_name = match count_span {
+                    Some(_) => "?".to_string(),
+                    None => {
+                        let count_name = format!("{}_count", field.name);
+                        members.push(Member {
+                            offset: count_offset,
+                            name: count_name.clone(),
+                            kind: MemberKind::Value(TypeDescriptor::Primitive(count_desc)),
+                        });
+                        count_name
+                    }
+                };
+                members.push(Member {
+                    offset: ptr_offset,
+                    name: field.name.to_string(),
+                    kind: MemberKind::SegmentAddr {
+                        target: field.desc,
+                        count: Some(count_name),
+                    },
+                });
+            }
+            // The count isn't stored, so there's nothing to name.
+            StructFieldLocation::InferredSlice { ptr_offset, .. } => members.push(Member {
+                offset: ptr_offset,
+                name: field.name.to_string(),
+                kind: MemberKind::SegmentAddr {
+                    target: field.desc,
+                    count: Some("?".to_string()),
+                },
+            }),
+            StructFieldLocation::InlineDelimitedList { offset } => members.push(Member {
+                offset,
+                name: field.name.to_string(),
+                kind: MemberKind::DelimitedList(field.desc),
+            }),
+            StructFieldLocation::InlineSlice {
+                count_offset,
+                count_desc,
+                offset,
+            } => {
+                let count_name = format!("{}_count", field.name);
+                members.push(Member {
+                    offset: count_offset,
+                    name: count_name.clone(),
+                    kind: MemberKind::Value(TypeDescriptor::Primitive(count_desc)),
+                });
+                members.push(Member {
+                    offset,
+                    name: field.name.to_string(),
+                    kind: MemberKind::InlineSlice {
+                        element: field.desc,
+                        count: count_name,
+                    },
+                });
+            }
+        }
+    }
+    members.sort_by_key(|member| member.offset);
+    members
+}
+
+/// Converts `SceneHeaderType` to `scene_header_type`.
+pub(crate) fn snake_case(s: &str) -> String {
+    let mut result = String::new();
+    for (i, c) in s.chars().enumerate() {
+        if i > 0 && c.is_ascii_uppercase() {
+            result.push('_');
+        }
+        result.push(c.to_ascii_lowercase());
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FieldDescriptor, UnionDescriptor, U16_DESC, U32_DESC, U8_DESC};
+    use oot_explorer_vrom::{Vrom, VromAddr};
+
+    const INNER: StructDescriptor = StructDescriptor {
+        name: "Inner",
+        size: Some(4),
+        is_end: None,
+        fields: &[FieldDescriptor {
+            name: "value",
+            location: StructFieldLocation::Simple { offset: 2 },
+            desc: U16_DESC,
+        }],
+    };
+
+    const OUTER: StructDescriptor = StructDescriptor {
+        name: "OuterThing",
+        size: Some(8),
+        is_end: None,
+        fields: &[
+            FieldDescriptor {
+                name: "inner",
+                location: StructFieldLocation::Simple { offset: 4 },
+                desc: TypeDescriptor::Struct(&INNER),
+            },
+            FieldDescriptor {
+                name: "flag",
+                location: StructFieldLocation::Simple { offset: 0 },
+                desc: U8_DESC,
+            },
+        ],
+    };
+
+    #[test]
+    fn collects_contained_types_first() {
+        let names: Vec<_> = collect_types(&[TypeDescriptor::Struct(&OUTER)])
+            .into_iter()
+            .map(|desc| desc.name())
+            .collect();
+        assert_eq!(names, ["Inner", "OuterThing"]);
+    }
+
+    #[test]
+    fn pads_members_to_their_offsets() {
+        let header = c_header(&[TypeDescriptor::Struct(&OUTER)], "TEST_H");
+        assert!(header.contains(
+            "typedef struct OuterThing {\n    uint8_t flag;\n    uint8_t unk_0x1[0x3];\n    \
+             Inner inner;\n} OuterThing;\n"
+        ));
+        assert!(header.contains("_Static_assert(offsetof(OuterThing, inner) == 0x4"));
+
+        let ksy = kaitai(&[TypeDescriptor::Struct(&OUTER)], "test");
+        assert!(ksy.contains("  outer_thing:\n    seq:\n      - id: flag\n        type: u1\n"));
+        assert!(ksy.contains("      - id: inner\n        type: inner\n        size: 0x4\n"));
+    }
+
+    fn is_zero(vrom: Vrom<'_>, addr: VromAddr) -> bool {
+        vrom.slice(addr..addr + 1)
+            .map_or(true, |bytes| bytes[0] == 0)
+    }
+
+    const COMMAND: UnionDescriptor = UnionDescriptor {
+        name: "Command",
+        size: Some(4),
+        is_end: Some(is_zero),
+        end_discriminant: Some(0),
+        discriminant_offset: 0,
+        discriminant_desc: U8_DESC,
+        variants: &[(0, U32_DESC), (1, U32_DESC)],
+        fallback: None,
+    };
+
+    const SCRIPT: StructDescriptor = StructDescriptor {
+        name: "Script",
+        size: None,
+        is_end: None,
+        fields: &[FieldDescriptor {
+            name: "commands",
+            location: StructFieldLocation::InlineDelimitedList { offset: 0 },
+            desc: TypeDescriptor::Union(&COMMAND),
+        }],
+    };
+
+    #[test]
+    fn repeats_delimited_lists_until_the_end_discriminant() {
+        let ksy = kaitai(&[TypeDescriptor::Struct(&SCRIPT)], "test");
+        assert!(ksy.contains(
+            "      - id: commands\n        type: command\n        size: 0x4\n        \
+             repeat: until\n        repeat-until: '_.discriminant == 0x0'\n"
+        ));
+
+        const OPAQUE_COMMAND: UnionDescriptor = UnionDescriptor {
+            end_discriminant: None,
+            ..COMMAND
+        };
+        const OPAQUE_SCRIPT: StructDescriptor = StructDescriptor {
+            fields: &[FieldDescriptor {
+                name: "commands",
+                location: StructFieldLocation::InlineDelimitedList { offset: 0 },
+                desc: TypeDescriptor::Union(&OPAQUE_COMMAND),
+            }],
+            ..SCRIPT
+        };
+        let ksy = kaitai(&[TypeDescriptor::Struct(&OPAQUE_SCRIPT)], "test");
+        assert!(ksy.contains("  script:\n    seq:\n      # 0x0: commands ends at an element"));
+        assert!(!ksy.contains("- id: commands"));
+    }
+
+    #[test]
+    fn leaves_out_discriminants_before_unions() {
+        const PARAMS: UnionDescriptor = UnionDescriptor {
+            name: "Params",
+            size: Some(4),
+            is_end: None,
+            end_discriminant: None,
+            discriminant_offset: -4,
+            discriminant_desc: U8_DESC,
+            variants: &[(1, U32_DESC)],
+            fallback: Some(U32_DESC),
+        };
+        let header = c_header(&[TypeDescriptor::Union(&PARAMS)], "TEST_H");
+        assert!(header.contains("typedef union Params {\n    // discriminant: uint8_t at -0x4"));
+        assert!(!header.contains("discriminant.value"));
+
+        let ksy = kaitai(&[TypeDescriptor::Union(&PARAMS)], "test");
+        assert!(ksy.contains("  params:\n    seq:\n      - id: fallback\n        type: u4\n"));
+        assert!(!ksy.contains("switch-on"));
+    }
+}
@@ -0,0 +1,278 @@
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use super::{collect_types, members, snake_case, MemberKind};
+use crate::{
+    BitfieldDescriptor, EnumDescriptor, PrimitiveType, StructDescriptor, TypeDescriptor,
+    UnionDescriptor,
+};
+
+/// Generates a C header declaring every type reachable from `roots`.
+///
+/// Structs are packed and padded so that every field lands at the same offset as in VROM, and
+/// static assertions check each offset and size. Pointers are declared as 32-bit segment addresses.
+pub fn c_header(roots: &[TypeDescriptor], guard: &str) -> String {
+    let mut out = String::new();
+    writeln!(
+        out,
+        "// Generated by oot-explorer from its type descriptors."
+    )
+    .unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "#ifndef {}", guard).unwrap();
+    writeln!(out, "#define {}", guard).unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "#include <stddef.h>").unwrap();
+    writeln!(out, "#include <stdint.h>").unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "#pragma pack(push, 1)").unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "typedef struct Vec3s {{ int16_t x, y, z; }} Vec3s;").unwrap();
+    writeln!(out, "typedef struct Vec3f {{ float x, y, z; }} Vec3f;").unwrap();
+    write_size_assert(&mut out, "Vec3s", PrimitiveType::Vec3s.size());
+    write_size_assert(&mut out, "Vec3f", PrimitiveType::Vec3f.size());
+
+    for desc in collect_types(roots) {
+        writeln!(out).unwrap();
+        match desc {
+            TypeDescriptor::Struct(desc) => write_struct(&mut out, desc),
+            TypeDescriptor::Union(desc) => write_union(&mut out, desc),
+            TypeDescriptor::Enum(desc) => write_enum(&mut out, desc),
+            TypeDescriptor::Bitfield(desc) => write_bitfield(&mut out, desc),
+            TypeDescriptor::Primitive(_) | TypeDescriptor::Pointer(_) => unreachable!(),
+        }
+    }
+
+    writeln!(out).unwrap();
+    writeln!(out, "#pragma pack(pop)").unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "#endif // {}", guard).unwrap();
+    out
+}
+
+fn primitive_type(primitive: PrimitiveType) -> &'static str {
+    match primitive {
+        PrimitiveType::Bool | PrimitiveType::U8 => "uint8_t",
+        PrimitiveType::I8 => "int8_t",
+        PrimitiveType::U16 => "uint16_t",
+        PrimitiveType::I16 => "int16_t",
+        PrimitiveType::U32 | PrimitiveType::VromAddr | PrimitiveType::SegmentAddr => "uint32_t",
+        PrimitiveType::I32 => "int32_t",
+        PrimitiveType::U64 => "uint64_t",
+        PrimitiveType::F32 => "float",
+        PrimitiveType::Qu10_2 | PrimitiveType::Qu1_11 | PrimitiveType::Qu0_16 => "uint16_t",
+        PrimitiveType::Vec3s => "Vec3s",
+        PrimitiveType::Vec3f => "Vec3f",
+    }
+}
+
+fn type_name(desc: TypeDescriptor) -> &'static str {
+    match desc {
+        TypeDescriptor::Primitive(primitive) => primitive_type(primitive),
+        TypeDescriptor::Pointer(_) => "uint32_t",
+        desc => desc.name(),
+    }
+}
+
+fn write_struct(out: &mut String, desc: &StructDescriptor) {
+    writeln!(out, "typedef struct {} {{", desc.name).unwrap();
+    let mut asserts = vec![];
+    let mut cursor = 0;
+    for member in members(desc) {
+        if member.offset < cursor {
+            writeln!(
+                out,
+                "    // 0x{:x}: {} overlaps the previous field",
+                member.offset, member.name,
+            )
+            .unwrap();
+            continue;
+        }
+        write_padding(out, cursor, member.offset);
+        match &member.kind {
+            MemberKind::Value(field_desc) => {
+                writeln!(out, "    {} {};", type_name(*field_desc), member.name).unwrap()
+            }
+            MemberKind::SegmentAddr {
+                target,
+                count: None,
+            } => writeln!(out, "    uint32_t {}; // {}*", member.name, target.name()).unwrap(),
+            MemberKind::SegmentAddr {
+                target,
+                count: Some(count),
+            } => writeln!(
+                out,
+                "    uint32_t {}; // {}[{}]*",
+                member.name,
+                target.name(),
+                count,
+            )
+            .unwrap(),
+            MemberKind::DelimitedList(element_desc) => writeln!(
+                out,
+                "    {} {}[1]; // Continues until an element marks the end.",
+                type_name(*element_desc),
+                member.name,
+            )
+            .unwrap(),
+            MemberKind::InlineSlice { element, count } => writeln!(
+                out,
+                "    {} {}[1]; // [{}]",
+                type_name(*element),
+                member.name,
+                count,
+            )
+            .unwrap(),
+        }
+        asserts.push((member.name.clone(), member.offset));
+        cursor = member.offset + member.size();
+    }
+    let size = match desc.size {
+        Some(size) if size >= cursor => {
+            write_padding(out, cursor, size);
+            Some(size)
+        }
+        _ => None,
+    };
+    writeln!(out, "}} {};", desc.name).unwrap();
+
+    for (name, offset) in asserts {
+        writeln!(
+            out,
+            "_Static_assert(offsetof({0}, {1}) == 0x{2:x}, \"{0}.{1}\");",
+            desc.name, name, offset,
+        )
+        .unwrap();
+    }
+    if let Some(size) = size {
+        write_size_assert(out, desc.name, size);
+    }
+}
+
+fn write_union(out: &mut String, desc: &UnionDescriptor) {
+    writeln!(out, "typedef union {} {{", desc.name).unwrap();
+    if desc.discriminant_offset < 0 {
+        writeln!(
+            out,
+            "    // discriminant: {} at -0x{:x}, before the union",
+            type_name(desc.discriminant_desc),
+            desc.discriminant_offset.unsigned_abs(),
+        )
+        .unwrap();
+    } else {
+        writeln!(out, "    struct {{").unwrap();
+        if desc.discriminant_offset > 0 {
+            writeln!(
+                out,
+                "        uint8_t unk_0x0[0x{:x}];",
+                desc.discriminant_offset
+            )
+            .unwrap();
+        }
+        writeln!(out, "        {} value;", type_name(desc.discriminant_desc)).unwrap();
+        writeln!(out, "    }} discriminant;").unwrap();
+    }
+    let mut member_names = HashSet::new();
+    for &(value, variant) in desc.variants {
+        // Variants that extend past the end of the union would change its size, so they're only
+        // noted in a comment.
+        if matches!((variant.size(), desc.size), (Some(a), Some(b)) if a > b) {
+            writeln!(
+                out,
+                "    // discriminant 0x{:x}: {} extends past the end",
+                value,
+                variant.name(),
+            )
+            .unwrap();
+            continue;
+        }
+        let mut member_name = snake_case(variant.name());
+        if !member_names.insert(member_name.clone()) {
+            member_name = format!("{}_0x{:x}", member_name, value);
+        }
+        writeln!(
+            out,
+            "    {} {}; // discriminant 0x{:x}",
+            type_name(variant),
+            member_name,
+            value,
+        )
+        .unwrap();
+    }
+    if let Some(fallback) = desc.fallback {
+        writeln!(
+            out,
+            "    {} fallback; // any other discriminant",
+            type_name(fallback),
+        )
+        .unwrap();
+    }
+    if let Some(size) = desc.size {
+        writeln!(out, "    uint8_t bytes[0x{:x}];", size).unwrap();
+    }
+    writeln!(out, "}} {};", desc.name).unwrap();
+
+    if desc.discriminant_offset >= 0 {
+        writeln!(
+            out,
+            "_Static_assert(offsetof({0}, discriminant.value) == 0x{1:x}, \"{0}.discriminant\");",
+            desc.name, desc.discriminant_offset,
+        )
+        .unwrap();
+    }
+    if let Some(size) = desc.size {
+        write_size_assert(out, desc.name, size);
+    }
+}
+
+fn write_enum(out: &mut String, desc: &EnumDescriptor) {
+    writeln!(
+        out,
+        "typedef {} {};",
+        primitive_type(desc.underlying),
+        desc.name,
+    )
+    .unwrap();
+    writeln!(out, "enum {{").unwrap();
+    let prefix = snake_case(desc.name).to_ascii_uppercase();
+    for &(value, name) in desc.values {
+        writeln!(out, "    {}_{} = 0x{:x},", prefix, name, value).unwrap();
+    }
+    writeln!(out, "}};").unwrap();
+}
+
+fn write_bitfield(out: &mut String, desc: &BitfieldDescriptor) {
+    writeln!(
+        out,
+        "typedef {} {};",
+        primitive_type(desc.underlying),
+        desc.name,
+    )
+    .unwrap();
+    let prefix = snake_case(desc.name).to_ascii_uppercase();
+    for span in desc.fields {
+        let span_name = snake_case(span.desc.name).to_ascii_uppercase();
+        writeln!(out, "#define {}_{}_SHIFT {}", prefix, span_name, span.shift).unwrap();
+        writeln!(
+            out,
+            "#define {}_{}_MASK 0x{:x}",
+            prefix, span_name, span.mask
+        )
+        .unwrap();
+    }
+}
+
+fn write_padding(out: &mut String, from: u32, to: u32) {
+    if to > from {
+        writeln!(out, "    uint8_t unk_0x{:x}[0x{:x}];", from, to - from).unwrap();
+    }
+}
+
+fn write_size_assert(out: &mut String, name: &str, size: u32) {
+    writeln!(
+        out,
+        "_Static_assert(sizeof({0}) == 0x{1:x}, \"sizeof({0})\");",
+        name, size,
+    )
+    .unwrap();
+}
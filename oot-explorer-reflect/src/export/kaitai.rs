@@ -0,0 +1,284 @@
+use std::fmt::Write;
+
+use oot_explorer_read::{Qu0_16, Qu10_2, Qu1_11};
+
+use super::{collect_types, members, snake_case, MemberKind};
+use crate::{EnumDescriptor, PrimitiveType, StructDescriptor, TypeDescriptor, UnionDescriptor};
+
+/// Generates a Kaitai Struct definition declaring every type reachable from `roots`.
+///
+/// Segment addresses are read as plain `u4` values, since resolving them requires a segment table.
+/// Unions read their discriminant and switch on it to parse the variant from the union's start.
+pub fn kaitai(roots: &[TypeDescriptor], id: &str) -> String {
+    let types = collect_types(roots);
+
+    let mut out = String::new();
+    writeln!(
+        out,
+        "# Generated by oot-explorer from its type descriptors."
+    )
+    .unwrap();
+    writeln!(out, "meta:").unwrap();
+    writeln!(out, "  id: {}", id).unwrap();
+    writeln!(out, "  endian: be").unwrap();
+
+    writeln!(out, "types:").unwrap();
+    for (name, component) in [("vec3s", "s2"), ("vec3f", "f4")].iter() {
+        writeln!(out, "  {}:", name).unwrap();
+        writeln!(out, "    seq:").unwrap();
+        for axis in ["x", "y", "z"].iter() {
+            writeln!(out, "      - id: {}", axis).unwrap();
+            writeln!(out, "        type: {}", component).unwrap();
+        }
+    }
+    for &desc in &types {
+        match desc {
+            TypeDescriptor::Struct(desc) => write_struct(&mut out, desc),
+            TypeDescriptor::Union(desc) => write_union(&mut out, desc),
+            TypeDescriptor::Enum(_) | TypeDescriptor::Bitfield(_) => {}
+            TypeDescriptor::Primitive(_) | TypeDescriptor::Pointer(_) => unreachable!(),
+        }
+    }
+
+    writeln!(out, "enums:").unwrap();
+    for &desc in &types {
+        if let TypeDescriptor::Enum(desc) = desc {
+            write_enum(&mut out, desc);
+        }
+    }
+    out
+}
+
+fn primitive_type(primitive: PrimitiveType) -> &'static str {
+    match primitive {
+        PrimitiveType::Bool | PrimitiveType::U8 => "u1",
+        PrimitiveType::I8 => "s1",
+        PrimitiveType::U16 => "u2",
+        PrimitiveType::I16 => "s2",
+        PrimitiveType::U32 | PrimitiveType::VromAddr | PrimitiveType::SegmentAddr => "u4",
+        PrimitiveType::I32 => "s4",
+        PrimitiveType::U64 => "u8",
+        PrimitiveType::F32 => "f4",
+        PrimitiveType::Qu10_2 | PrimitiveType::Qu1_11 | PrimitiveType::Qu0_16 => "u2",
+        PrimitiveType::Vec3s => "vec3s",
+        PrimitiveType::Vec3f => "vec3f",
+    }
+}
+
+fn fixed_point_scale(primitive: PrimitiveType) -> Option<f64> {
+    match primitive {
+        PrimitiveType::Qu10_2 => Some(Qu10_2::SCALE),
+        PrimitiveType::Qu1_11 => Some(Qu1_11::SCALE),
+        PrimitiveType::Qu0_16 => Some(Qu0_16::SCALE),
+        _ => None,
+    }
+}
+
+/// Writes the attributes that follow `id` for a value of the described type.
+fn write_value_attrs(out: &mut String, desc: TypeDescriptor) {
+    match desc {
+        TypeDescriptor::Primitive(primitive) => {
+            writeln!(out, "        type: {}", primitive_type(primitive)).unwrap();
+            if let Some(scale) = fixed_point_scale(primitive) {
+                writeln!(
+                    out,
+                    "        doc: {} fixed-point, in units of 1/{}",
+                    primitive.name(),
+                    scale
+                )
+                .unwrap();
+            }
+        }
+        TypeDescriptor::Pointer(pointer) => {
+            writeln!(out, "        type: u4").unwrap();
+            writeln!(
+                out,
+                "        doc: Segment address of {}",
+                pointer.target().name()
+            )
+            .unwrap();
+        }
+        TypeDescriptor::Enum(enum_desc) => {
+            writeln!(
+                out,
+                "        type: {}",
+                primitive_type(enum_desc.underlying)
+            )
+            .unwrap();
+            writeln!(out, "        enum: {}", snake_case(enum_desc.name)).unwrap();
+        }
+        TypeDescriptor::Bitfield(bitfield_desc) => {
+            writeln!(
+                out,
+                "        type: {}",
+                primitive_type(bitfield_desc.underlying)
+            )
+            .unwrap();
+            writeln!(out, "        doc: Bitfield {}", bitfield_desc.name).unwrap();
+        }
+        TypeDescriptor::Struct(_) | TypeDescriptor::Union(_) => {
+            writeln!(out, "        type: {}", snake_case(desc.name())).unwrap();
+            if let Some(size) = desc.size() {
+                writeln!(out, "        size: 0x{:x}", size).unwrap();
+            }
+        }
+    }
+}
+
+fn write_struct(out: &mut String, desc: &StructDescriptor) {
+    writeln!(out, "  {}:", snake_case(desc.name)).unwrap();
+    writeln!(out, "    seq:").unwrap();
+    let mut cursor = 0;
+    for member in members(desc) {
+        if member.offset < cursor {
+            writeln!(
+                out,
+                "      # 0x{:x}: {} overlaps the previous field",
+                member.offset, member.name,
+            )
+            .unwrap();
+            continue;
+        }
+        let until = match member.kind {
+            MemberKind::DelimitedList(element_desc) => match repeat_until(element_desc) {
+                Some(until) => Some(until),
+                None => {
+                    writeln!(
+                        out,
+                        "      # 0x{:x}: {} ends at an element Kaitai can't recognize",
+                        member.offset, member.name,
+                    )
+                    .unwrap();
+                    continue;
+                }
+            },
+            _ => None,
+        };
+        write_padding(out, cursor, member.offset);
+        writeln!(out, "      - id: {}", member.name).unwrap();
+        match &member.kind {
+            MemberKind::Value(field_desc) => write_value_attrs(out, *field_desc),
+            MemberKind::SegmentAddr {
+                target,
+                count: None,
+            } => {
+                writeln!(out, "        type: u4").unwrap();
+                writeln!(out, "        doc: Segment address of {}", target.name()).unwrap();
+            }
+            MemberKind::SegmentAddr {
+                target,
+                count: Some(count),
+            } => {
+                writeln!(out, "        type: u4").unwrap();
+                writeln!(
+                    out,
+                    "        doc: Segment address of {} {}",
+                    count,
+                    target.name(),
+                )
+                .unwrap();
+            }
+            MemberKind::DelimitedList(element_desc) => {
+                write_value_attrs(out, *element_desc);
+                if let Some(until) = &until {
+                    writeln!(out, "        repeat: until").unwrap();
+                    writeln!(out, "        repeat-until: '{}'", until).unwrap();
+                }
+            }
+            MemberKind::InlineSlice { element, count } => {
+                write_value_attrs(out, *element);
+                writeln!(out, "        repeat: expr").unwrap();
+                writeln!(out, "        repeat-expr: {}", count).unwrap();
+            }
+        }
+        cursor = member.offset + member.size();
+    }
+    if let Some(size) = desc.size {
+        write_padding(out, cursor, size);
+    }
+}
+
+/// Makes a `repeat-until` condition that recognizes the last element of a delimited list of
+/// `element_desc`, if it's a union whose end is marked by its discriminant alone.
+fn repeat_until(element_desc: TypeDescriptor) -> Option<String> {
+    let desc = match element_desc {
+        TypeDescriptor::Union(desc) => desc,
+        _ => return None,
+    };
+    let end = desc.end_discriminant?;
+    let value = match desc.discriminant_desc {
+        TypeDescriptor::Enum(enum_desc) => format!(
+            "{}::{}",
+            snake_case(enum_desc.name),
+            enum_value_name(enum_desc, end)?,
+        ),
+        _ => format!("0x{:x}", end),
+    };
+    Some(format!("_.discriminant == {}", value))
+}
+
+fn write_union(out: &mut String, desc: &UnionDescriptor) {
+    writeln!(out, "  {}:", snake_case(desc.name)).unwrap();
+    writeln!(out, "    seq:").unwrap();
+    if desc.discriminant_offset < 0 {
+        // Types can't read before their own start, so the variant can't be chosen.
+        write_unswitched_union(out, desc);
+        return;
+    }
+    write_padding(out, 0, desc.discriminant_offset as u32);
+    writeln!(out, "      - id: discriminant").unwrap();
+    write_value_attrs(out, desc.discriminant_desc);
+
+    writeln!(out, "    instances:").unwrap();
+    writeln!(out, "      variant:").unwrap();
+    writeln!(out, "        pos: 0").unwrap();
+    writeln!(out, "        type:").unwrap();
+    writeln!(out, "          switch-on: discriminant").unwrap();
+    writeln!(out, "          cases:").unwrap();
+    for &(value, variant) in desc.variants {
+        let case = match desc.discriminant_desc {
+            TypeDescriptor::Enum(enum_desc) => match enum_value_name(enum_desc, value) {
+                Some(name) => format!("'{}::{}'", snake_case(enum_desc.name), name),
+                None => continue,
+            },
+            _ => format!("0x{:x}", value),
+        };
+        writeln!(out, "            {}: {}", case, snake_case(variant.name())).unwrap();
+    }
+    if let Some(fallback) = desc.fallback {
+        writeln!(out, "            _: {}", snake_case(fallback.name())).unwrap();
+    }
+}
+
+/// Writes the body of a union whose discriminant is stored before it, as its fallback variant or
+/// as opaque bytes.
+fn write_unswitched_union(out: &mut String, desc: &UnionDescriptor) {
+    match desc.fallback {
+        Some(fallback) => {
+            writeln!(out, "      - id: fallback").unwrap();
+            write_value_attrs(out, fallback);
+        }
+        None => write_padding(out, 0, desc.size.unwrap_or(0)),
+    }
+}
+
+fn write_enum(out: &mut String, desc: &EnumDescriptor) {
+    writeln!(out, "  {}:", snake_case(desc.name)).unwrap();
+    for &(value, name) in desc.values {
+        writeln!(out, "    0x{:x}: {}", value, name.to_ascii_lowercase()).unwrap();
+    }
+}
+
+fn enum_value_name(desc: &EnumDescriptor, value: u32) -> Option<String> {
+    desc.values
+        .iter()
+        .find(|&&(v, _)| v == value)
+        .map(|&(_, name)| name.to_ascii_lowercase())
+}
+
+fn write_padding(out: &mut String, from: u32, to: u32) {
+    if to > from {
+        writeln!(out, "      - id: unk_0x{:x}", from).unwrap();
+        writeln!(out, "        size: 0x{:x}", to - from).unwrap();
+    }
+}
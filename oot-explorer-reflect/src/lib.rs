@@ -1,20 +1,36 @@
 mod bitfield;
+mod diff;
 mod enum_;
+pub mod export;
 mod pointer;
 mod primitive;
+mod pseudo;
+mod query;
+mod search;
 mod sourced;
 mod struct_;
 mod type_;
+mod value;
+mod walk;
+mod write;
+mod xref;
 
 pub use bitfield::{BitfieldDescriptor, BitfieldSpan};
-pub use enum_::EnumDescriptor;
+pub use diff::{diff, Difference, DifferenceKind};
+pub use enum_::{indexed_names, EnumDescriptor};
 pub use pointer::PointerDescriptor;
 pub use primitive::PrimitiveType;
+pub use pseudo::PseudoItem;
+pub use query::{Query, QueryError, QueryStep, QueryTarget};
+pub use search::{find_type, search, SearchHit};
 pub use sourced::{RangeSourced, Sourced};
 pub use struct_::{
-    FieldDescriptor, IsEndFn, StructDescriptor, StructFieldLocation, UnionDescriptor,
+    FieldDescriptor, InferLenFn, IsEndFn, StructDescriptor, StructFieldLocation, UnionDescriptor,
 };
 pub use type_::TypeDescriptor;
+pub use value::{BitfieldSpanValue, Field, PrimitiveValue, Value, ValueKind};
+pub use write::{is_editable, write_field, write_location, write_value, WriteError};
+pub use xref::{Reference, XrefIndex};
 
 pub const BOOL_DESC: TypeDescriptor = TypeDescriptor::Primitive(PrimitiveType::Bool);
 pub const U8_DESC: TypeDescriptor = TypeDescriptor::Primitive(PrimitiveType::U8);
@@ -23,5 +39,12 @@ pub const U16_DESC: TypeDescriptor = TypeDescriptor::Primitive(PrimitiveType::U1
 pub const I16_DESC: TypeDescriptor = TypeDescriptor::Primitive(PrimitiveType::I16);
 pub const U32_DESC: TypeDescriptor = TypeDescriptor::Primitive(PrimitiveType::U32);
 pub const I32_DESC: TypeDescriptor = TypeDescriptor::Primitive(PrimitiveType::I32);
+pub const U64_DESC: TypeDescriptor = TypeDescriptor::Primitive(PrimitiveType::U64);
+pub const F32_DESC: TypeDescriptor = TypeDescriptor::Primitive(PrimitiveType::F32);
+pub const QU10_2_DESC: TypeDescriptor = TypeDescriptor::Primitive(PrimitiveType::Qu10_2);
+pub const QU1_11_DESC: TypeDescriptor = TypeDescriptor::Primitive(PrimitiveType::Qu1_11);
+pub const QU0_16_DESC: TypeDescriptor = TypeDescriptor::Primitive(PrimitiveType::Qu0_16);
+pub const VEC3S_DESC: TypeDescriptor = TypeDescriptor::Primitive(PrimitiveType::Vec3s);
+pub const VEC3F_DESC: TypeDescriptor = TypeDescriptor::Primitive(PrimitiveType::Vec3f);
 pub const VROM_ADDR_DESC: TypeDescriptor = TypeDescriptor::Primitive(PrimitiveType::VromAddr);
 pub const SEGMENT_ADDR_DESC: TypeDescriptor = TypeDescriptor::Primitive(PrimitiveType::SegmentAddr);
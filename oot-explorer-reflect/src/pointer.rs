@@ -2,5 +2,15 @@ use crate::TypeDescriptor;
 
 pub struct PointerDescriptor {
     pub name: &'static str,
-    pub target: TypeDescriptor,
+    /// Returns the descriptor of the pointed-to type. This is a function so that types can point
+    /// to types that contain them, which constants can't refer to directly.
+    pub target: fn() -> TypeDescriptor,
+    /// Whether a NULL value is expected and meaningful, rather than an error.
+    pub nullable: bool,
+}
+
+impl PointerDescriptor {
+    pub fn target(&self) -> TypeDescriptor {
+        (self.target)()
+    }
 }
@@ -10,6 +10,18 @@ pub enum PrimitiveType {
     I16,
     U32,
     I32,
+    U64,
+    F32,
+    /// An unsigned fixed-point number with 10 integer bits and 2 fractional bits, stored as the
+    /// low 12 bits of a `u16`.
+    Qu10_2,
+    /// An unsigned fixed-point number with 1 integer bit and 11 fractional bits, stored as the
+    /// low 12 bits of a `u16`.
+    Qu1_11,
+    /// An unsigned fixed-point number with 16 fractional bits.
+    Qu0_16,
+    Vec3s,
+    Vec3f,
     VromAddr,
     SegmentAddr,
 }
@@ -24,6 +36,13 @@ impl PrimitiveType {
             PrimitiveType::I16 => "i16",
             PrimitiveType::U32 => "u32",
             PrimitiveType::I32 => "i32",
+            PrimitiveType::U64 => "u64",
+            PrimitiveType::F32 => "f32",
+            PrimitiveType::Qu10_2 => "Qu10_2",
+            PrimitiveType::Qu1_11 => "Qu1_11",
+            PrimitiveType::Qu0_16 => "Qu0_16",
+            PrimitiveType::Vec3s => "Vec3s",
+            PrimitiveType::Vec3f => "Vec3f",
             PrimitiveType::VromAddr => "VromAddr",
             PrimitiveType::SegmentAddr => "SegmentAddr",
         }
@@ -32,14 +51,38 @@ impl PrimitiveType {
     pub fn size(self) -> u32 {
         match self {
             PrimitiveType::Bool | PrimitiveType::U8 | PrimitiveType::I8 => 1,
-            PrimitiveType::U16 | PrimitiveType::I16 => 2,
+            PrimitiveType::U16
+            | PrimitiveType::I16
+            | PrimitiveType::Qu10_2
+            | PrimitiveType::Qu1_11
+            | PrimitiveType::Qu0_16 => 2,
             PrimitiveType::U32
             | PrimitiveType::I32
+            | PrimitiveType::F32
             | PrimitiveType::VromAddr
             | PrimitiveType::SegmentAddr => 4,
+            PrimitiveType::Vec3s => 6,
+            PrimitiveType::U64 => 8,
+            PrimitiveType::Vec3f => 12,
         }
     }
 
+    /// Whether values are integers, and so can serve as enum values, bitfields, counts, and
+    /// discriminants.
+    pub fn is_integer(self) -> bool {
+        !matches!(
+            self,
+            PrimitiveType::F32
+                | PrimitiveType::Qu10_2
+                | PrimitiveType::Qu1_11
+                | PrimitiveType::Qu0_16
+                | PrimitiveType::Vec3s
+                | PrimitiveType::Vec3f
+        )
+    }
+
+    /// Reads the value's raw bits. A `u64` is truncated to its low 32 bits, and a vector reads as
+    /// the bits of its x component.
     pub fn read_as_u32(self, vrom: Vrom<'_>, addr: VromAddr) -> Result<u32, ReadError> {
         Ok(match self {
             PrimitiveType::Bool => bool::from_vrom(vrom, addr)? as u32,
@@ -51,6 +94,12 @@ impl PrimitiveType {
                 u32::from_vrom(vrom, addr)?
             }
             PrimitiveType::I32 => i32::from_vrom(vrom, addr)? as u32,
+            PrimitiveType::U64 => u64::from_vrom(vrom, addr)? as u32,
+            PrimitiveType::F32 | PrimitiveType::Vec3f => f32::from_vrom(vrom, addr)?.to_bits(),
+            PrimitiveType::Qu10_2 | PrimitiveType::Qu1_11 | PrimitiveType::Qu0_16 => {
+                u16::from_vrom(vrom, addr)? as u32
+            }
+            PrimitiveType::Vec3s => i16::from_vrom(vrom, addr)? as u32,
         })
     }
 }
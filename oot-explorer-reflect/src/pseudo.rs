@@ -0,0 +1,274 @@
+use oot_explorer_read::{FromVrom, Layout};
+use oot_explorer_segment::{SegmentAddr, SegmentTable};
+use oot_explorer_vrom::{Vrom, VromAddr};
+use std::fmt::{self, Display, Formatter};
+use thiserror::Error;
+
+use crate::{PointerDescriptor, TypeDescriptor, UnionDescriptor};
+
+/// A placeholder for data that couldn't be interpreted with its descriptor.
+///
+/// Reflection shows these in place of the value that would otherwise be there, so that broken or
+/// not-yet-understood data is visible rather than silently missing.
+#[derive(Clone, Debug, Error, PartialEq)]
+pub enum PseudoItem {
+    /// A union whose discriminant doesn't select any known variant.
+    #[error("unknown variant 0x{discriminant:02x} ({})", HexBytes(.bytes))]
+    UnknownVariant {
+        addr: VromAddr,
+        discriminant: u32,
+        /// The raw bytes of the union, if it has a known size and they could be read.
+        bytes: Vec<u8>,
+    },
+
+    /// A pointer that couldn't be followed.
+    #[error("{message} ({segment_addr:?})")]
+    UnresolvedPointer {
+        addr: VromAddr,
+        segment_addr: SegmentAddr,
+        message: String,
+    },
+
+    /// Data that couldn't be read at all.
+    #[error("{message}")]
+    Inaccessible { addr: VromAddr, message: String },
+}
+
+impl PseudoItem {
+    /// The VROM address of the data this item stands in for.
+    pub fn addr(&self) -> VromAddr {
+        match *self {
+            PseudoItem::UnknownVariant { addr, .. }
+            | PseudoItem::UnresolvedPointer { addr, .. }
+            | PseudoItem::Inaccessible { addr, .. } => addr,
+        }
+    }
+
+    /// The number of bytes of VROM this item covers.
+    pub fn size(&self) -> u32 {
+        match self {
+            PseudoItem::UnknownVariant { bytes, .. } => (bytes.len() as u32).max(1),
+            PseudoItem::UnresolvedPointer { .. } => SegmentAddr::SIZE,
+            PseudoItem::Inaccessible { .. } => 1,
+        }
+    }
+}
+
+impl UnionDescriptor {
+    /// The address of the discriminant of the union at `addr`.
+    pub fn discriminant_addr(&self, addr: VromAddr) -> VromAddr {
+        VromAddr(addr.0.wrapping_add(self.discriminant_offset as u32))
+    }
+
+    /// Selects the variant of the union at `addr` according to its discriminant, or the fallback
+    /// variant if the discriminant isn't listed.
+    ///
+    /// A discriminant that can't be read, or whose type isn't an integer or enum, makes the union
+    /// [`Inaccessible`](PseudoItem::Inaccessible).
+    pub fn variant_at(&self, vrom: Vrom<'_>, addr: VromAddr) -> Result<TypeDescriptor, PseudoItem> {
+        let discriminant_addr = self.discriminant_addr(addr);
+        let inaccessible = |message: String| PseudoItem::Inaccessible {
+            addr: discriminant_addr,
+            message,
+        };
+        let discriminant = match self.discriminant_desc.read_as_u32(vrom, discriminant_addr) {
+            Some(Ok(discriminant)) => discriminant,
+            Some(Err(e)) => return Err(inaccessible(e.to_string())),
+            None => {
+                return Err(inaccessible(format!(
+                    "{} can't be a union discriminant",
+                    self.discriminant_desc.name()
+                )))
+            }
+        };
+        match self
+            .variants
+            .binary_search_by_key(&discriminant, |&(x, _)| x)
+        {
+            Ok(index) => Ok(self.variants[index].1),
+            Err(_) => self.fallback.ok_or_else(|| PseudoItem::UnknownVariant {
+                addr,
+                discriminant,
+                bytes: self
+                    .size
+                    .and_then(|size| vrom.slice(addr..addr + size).ok())
+                    .map(|bytes| bytes.to_vec())
+                    .unwrap_or_default(),
+            }),
+        }
+    }
+}
+
+impl PointerDescriptor {
+    /// Follows the pointer at `addr`. Returns `None` for a NULL pointer that's nullable.
+    pub fn target_at(
+        &self,
+        vrom: Vrom<'_>,
+        segment_table: &SegmentTable,
+        addr: VromAddr,
+    ) -> Result<Option<VromAddr>, PseudoItem> {
+        let segment_addr =
+            SegmentAddr::from_vrom(vrom, addr).map_err(|e| PseudoItem::Inaccessible {
+                addr,
+                message: e.to_string(),
+            })?;
+        if self.nullable && segment_addr.is_null() {
+            return Ok(None);
+        }
+        segment_table
+            .resolve(segment_addr)
+            .map(Some)
+            .map_err(|e| PseudoItem::UnresolvedPointer {
+                addr,
+                segment_addr,
+                message: e.to_string(),
+            })
+    }
+}
+
+struct HexBytes<'a>(&'a [u8]);
+
+impl<'a> Display for HexBytes<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (i, byte) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{StructDescriptor, U16_DESC, U32_DESC, U8_DESC};
+    use oot_explorer_segment::Segment;
+
+    const EMPTY: StructDescriptor = StructDescriptor {
+        name: "Empty",
+        size: Some(4),
+        is_end: None,
+        fields: &[],
+    };
+
+    const UNION: UnionDescriptor = UnionDescriptor {
+        name: "Union",
+        size: Some(4),
+        is_end: None,
+        end_discriminant: None,
+        discriminant_offset: 1,
+        discriminant_desc: U8_DESC,
+        variants: &[(1, U16_DESC), (2, U32_DESC)],
+        fallback: None,
+    };
+
+    const PTR: PointerDescriptor = PointerDescriptor {
+        name: "u32*",
+        target: || U32_DESC,
+        nullable: true,
+    };
+
+    fn variant_error(desc: &UnionDescriptor, data: &[u8]) -> PseudoItem {
+        match desc.variant_at(Vrom(data), VromAddr(0)) {
+            Ok(variant) => panic!("selected {}", variant.name()),
+            Err(item) => item,
+        }
+    }
+
+    #[test]
+    fn selects_listed_variants() {
+        let data = [0, 2, 0, 0];
+        let variant = UNION.variant_at(Vrom(&data), VromAddr(0)).unwrap();
+        assert_eq!(variant.name(), "u32");
+    }
+
+    #[test]
+    fn reports_unknown_variants_with_their_bytes() {
+        let data = [0xaa, 3, 0xbb, 0xcc];
+        let item = variant_error(&UNION, &data);
+        assert_eq!(
+            item,
+            PseudoItem::UnknownVariant {
+                addr: VromAddr(0),
+                discriminant: 3,
+                bytes: vec![0xaa, 3, 0xbb, 0xcc],
+            },
+        );
+        assert_eq!(item.size(), 4);
+        assert_eq!(item.to_string(), "unknown variant 0x03 (aa 03 bb cc)");
+    }
+
+    #[test]
+    fn unknown_variants_past_the_end_have_no_bytes() {
+        let data = [0xaa, 3];
+        let item = variant_error(&UNION, &data);
+        assert!(matches!(&item, PseudoItem::UnknownVariant { bytes, .. } if bytes.is_empty()));
+        assert_eq!(item.size(), 1);
+        assert_eq!(item.to_string(), "unknown variant 0x03 ()");
+    }
+
+    #[test]
+    fn unreadable_discriminants_are_inaccessible() {
+        let data = [0];
+        let item = variant_error(&UNION, &data);
+        assert!(matches!(item, PseudoItem::Inaccessible { .. }));
+        assert_eq!(item.addr(), VromAddr(1));
+        assert_eq!(item.size(), 1);
+
+        const BAD_UNION: UnionDescriptor = UnionDescriptor {
+            discriminant_desc: TypeDescriptor::Struct(&EMPTY),
+            ..UNION
+        };
+        let data = [0, 1, 0, 0];
+        let item = variant_error(&BAD_UNION, &data);
+        assert!(matches!(item, PseudoItem::Inaccessible { .. }));
+    }
+
+    #[test]
+    fn follows_pointers() {
+        let data = [0x02, 0, 0, 4, 0, 0, 0, 0];
+        let segment_table = SegmentTable::new().with(Segment::SCENE, VromAddr(0x100));
+        assert_eq!(
+            PTR.target_at(Vrom(&data), &segment_table, VromAddr(0)),
+            Ok(Some(VromAddr(0x104))),
+        );
+        assert_eq!(
+            PTR.target_at(Vrom(&data), &segment_table, VromAddr(4)),
+            Ok(None),
+        );
+    }
+
+    #[test]
+    fn reports_unresolved_pointers() {
+        let data = [0x05, 0, 0, 4, 0, 0, 0, 0];
+        let segment_table = SegmentTable::new();
+        let item = PTR
+            .target_at(Vrom(&data), &segment_table, VromAddr(0))
+            .unwrap_err();
+        assert!(matches!(
+            item,
+            PseudoItem::UnresolvedPointer {
+                addr: VromAddr(0),
+                segment_addr: SegmentAddr(0x0500_0004),
+                ..
+            }
+        ));
+        assert_eq!(item.size(), SegmentAddr::SIZE);
+
+        const NON_NULL: PointerDescriptor = PointerDescriptor {
+            nullable: false,
+            ..PTR
+        };
+        let item = NON_NULL
+            .target_at(Vrom(&data), &segment_table, VromAddr(4))
+            .unwrap_err();
+        assert_eq!(item.addr(), VromAddr(4));
+
+        let item = PTR
+            .target_at(Vrom(&data), &segment_table, VromAddr(6))
+            .unwrap_err();
+        assert!(matches!(item, PseudoItem::Inaccessible { .. }));
+    }
+}
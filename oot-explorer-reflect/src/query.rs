@@ -0,0 +1,508 @@
+use oot_explorer_read::{FromVrom, ReadError};
+use oot_explorer_segment::{SegmentAddr, SegmentError, SegmentTable};
+use oot_explorer_vrom::{Vrom, VromAddr};
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+use thiserror::Error;
+
+use crate::{PseudoItem, StructFieldLocation, TypeDescriptor, UnionDescriptor, Value};
+
+/// A path through reflected data, like `scene[0x55].room[2].headers[ActorList].actor_list[3].pos_x`.
+///
+/// A query is a sequence of steps. Field steps name a struct field, following pointers and
+/// selecting union variants along the way. A union also has a `discriminant` pseudo-field. Index
+/// steps select an element of a slice or delimited list, either by position or, for lists of
+/// unions, by the name of the first variant that matches.
+///
+/// The leading steps name a root, which is resolved by the caller (see [`Query::resolve`]).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Query {
+    pub steps: Vec<QueryStep>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum QueryStep {
+    /// `.name`, or the bare name that begins a query.
+    Field(String),
+    /// `[3]` or `[0x3]`.
+    Index(u32),
+    /// `[ActorList]`, matching either the discriminant's enum value name (`ACTOR_LIST`, also
+    /// written in camel case) or the variant's type name (`ActorListHeader`).
+    Variant(String),
+}
+
+/// A place in VROM that a query resolved to.
+#[derive(Clone)]
+pub struct QueryTarget {
+    pub base_addr: VromAddr,
+    pub location: StructFieldLocation,
+    pub desc: TypeDescriptor,
+}
+
+#[derive(Debug, Error)]
+pub enum QueryError {
+    #[error("syntax error at offset {offset}: {message}")]
+    Syntax {
+        offset: usize,
+        message: &'static str,
+    },
+
+    #[error("unknown root: {0}")]
+    UnknownRoot(String),
+
+    #[error("{type_name} has no field named {field}")]
+    NoSuchField {
+        type_name: &'static str,
+        field: String,
+    },
+
+    #[error("{0} can't be indexed")]
+    NotIndexable(&'static str),
+
+    #[error("index {index} is out of range for length {len}")]
+    IndexOutOfRange { index: u32, len: u32 },
+
+    #[error("slice element {0} has no size")]
+    UnsizedElement(&'static str),
+
+    #[error("no element matches variant {0}")]
+    NoSuchVariant(String),
+
+    #[error("{0}")]
+    PseudoItem(#[from] PseudoItem),
+
+    #[error("NULL pointer to {0}")]
+    NullPointer(&'static str),
+
+    #[error("{0}")]
+    ReadError(#[from] ReadError),
+
+    #[error("{0}")]
+    SegmentError(#[from] SegmentError),
+}
+
+impl QueryTarget {
+    /// A target for a whole value of type `desc` at `addr`.
+    pub fn root(desc: TypeDescriptor, addr: VromAddr) -> Self {
+        QueryTarget {
+            base_addr: addr,
+            location: StructFieldLocation::Simple { offset: 0 },
+            desc,
+        }
+    }
+
+    /// The VROM address of the target. For slice fields, this is the address of the pointer.
+    pub fn addr(&self) -> VromAddr {
+        match self.location {
+            StructFieldLocation::Simple { offset } => self.base_addr + offset,
+            StructFieldLocation::Slice { ptr_offset, .. }
+            | StructFieldLocation::InferredSlice { ptr_offset, .. } => self.base_addr + ptr_offset,
+            StructFieldLocation::InlineDelimitedList { offset }
+            | StructFieldLocation::InlineSlice { offset, .. } => self.base_addr + offset,
+        }
+    }
+
+    /// Reads the target's value.
+    pub fn value(&self, vrom: Vrom<'_>, segment_table: &SegmentTable) -> Value {
+        Value::read_location(
+            vrom,
+            segment_table,
+            self.desc,
+            &self.location,
+            self.base_addr,
+        )
+    }
+}
+
+impl Query {
+    pub fn parse(s: &str) -> Result<Query, QueryError> {
+        Parser { s, offset: 0 }.parse()
+    }
+
+    /// Resolves `steps` starting from `target`.
+    pub fn resolve(
+        vrom: Vrom<'_>,
+        segment_table: &SegmentTable,
+        mut target: QueryTarget,
+        steps: &[QueryStep],
+    ) -> Result<QueryTarget, QueryError> {
+        for step in steps {
+            target = match step {
+                QueryStep::Field(name) => resolve_field(vrom, segment_table, &target, name)?,
+                QueryStep::Index(index) => {
+                    let (desc, addrs) = elements(vrom, segment_table, &target)?;
+                    let len = addrs.len() as u32;
+                    match addrs.get(*index as usize) {
+                        Some(&addr) => QueryTarget::root(desc, addr),
+                        None => return Err(QueryError::IndexOutOfRange { index: *index, len }),
+                    }
+                }
+                QueryStep::Variant(name) => {
+                    let (desc, addrs) = elements(vrom, segment_table, &target)?;
+                    let union_desc = match desc {
+                        TypeDescriptor::Union(union_desc) => union_desc,
+                        _ => return Err(QueryError::NoSuchVariant(name.clone())),
+                    };
+                    let mut found = None;
+                    for addr in addrs {
+                        if variant_matches(vrom, union_desc, addr, name)? {
+                            found = Some(addr);
+                            break;
+                        }
+                    }
+                    match found {
+                        Some(addr) => QueryTarget::root(desc, addr),
+                        None => return Err(QueryError::NoSuchVariant(name.clone())),
+                    }
+                }
+            };
+        }
+        Ok(target)
+    }
+}
+
+impl FromStr for Query {
+    type Err = QueryError;
+
+    fn from_str(s: &str) -> Result<Query, QueryError> {
+        Query::parse(s)
+    }
+}
+
+impl Display for Query {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (i, step) in self.steps.iter().enumerate() {
+            match step {
+                QueryStep::Field(name) if i == 0 => write!(f, "{}", name)?,
+                QueryStep::Field(name) => write!(f, ".{}", name)?,
+                QueryStep::Index(index) => write!(f, "[{}]", index)?,
+                QueryStep::Variant(name) => write!(f, "[{}]", name)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Follows pointers from the value of type `desc` at `addr`.
+///
+/// Returns the final descriptor and its address.
+fn deref(
+    vrom: Vrom<'_>,
+    segment_table: &SegmentTable,
+    mut desc: TypeDescriptor,
+    mut addr: VromAddr,
+) -> Result<(TypeDescriptor, VromAddr), QueryError> {
+    while let TypeDescriptor::Pointer(ptr_desc) = desc {
+        addr = ptr_desc
+            .target_at(vrom, segment_table, addr)?
+            .ok_or_else(|| QueryError::NullPointer(ptr_desc.target().name()))?;
+        desc = ptr_desc.target();
+    }
+    Ok((desc, addr))
+}
+
+fn resolve_field(
+    vrom: Vrom<'_>,
+    segment_table: &SegmentTable,
+    target: &QueryTarget,
+    name: &str,
+) -> Result<QueryTarget, QueryError> {
+    let no_such_field = |type_name| QueryError::NoSuchField {
+        type_name,
+        field: name.to_string(),
+    };
+
+    let addr = match target.location {
+        StructFieldLocation::Simple { offset } => target.base_addr + offset,
+        _ => return Err(no_such_field(target.desc.name())),
+    };
+    let (mut desc, addr) = deref(vrom, segment_table, target.desc, addr)?;
+    loop {
+        match desc {
+            TypeDescriptor::Struct(struct_desc) => {
+                return match struct_desc.fields.iter().find(|field| field.name == name) {
+                    Some(field) => Ok(QueryTarget {
+                        base_addr: addr,
+                        location: field.location.clone(),
+                        desc: field.desc,
+                    }),
+                    None => Err(no_such_field(struct_desc.name)),
+                };
+            }
+            TypeDescriptor::Union(union_desc) => {
+                if name == "discriminant" {
+                    return Ok(QueryTarget::root(
+                        union_desc.discriminant_desc,
+                        union_desc.discriminant_addr(addr),
+                    ));
+                }
+                desc = union_desc.variant_at(vrom, addr)?;
+            }
+            _ => return Err(no_such_field(desc.name())),
+        }
+    }
+}
+
+fn variant_matches(
+    vrom: Vrom<'_>,
+    desc: &'static UnionDescriptor,
+    addr: VromAddr,
+    name: &str,
+) -> Result<bool, QueryError> {
+    let discriminant_addr = desc.discriminant_addr(addr);
+    let discriminant = match desc.discriminant_desc.read_as_u32(vrom, discriminant_addr) {
+        Some(discriminant) => discriminant?,
+        None => {
+            return Err(QueryError::PseudoItem(PseudoItem::Inaccessible {
+                addr: discriminant_addr,
+                message: format!(
+                    "{} can't be a union discriminant",
+                    desc.discriminant_desc.name()
+                ),
+            }))
+        }
+    };
+    if let TypeDescriptor::Enum(enum_desc) = desc.discriminant_desc {
+        if let Ok(index) = enum_desc
+            .values
+            .binary_search_by_key(&discriminant, |&(x, _)| x)
+        {
+            let value_name = enum_desc.values[index].1;
+            if value_name == name || value_name == camel_to_snake_upper(name) {
+                return Ok(true);
+            }
+        }
+    }
+    Ok(desc
+        .variants
+        .binary_search_by_key(&discriminant, |&(x, _)| x)
+        .map(|index| desc.variants[index].1.name() == name)
+        .unwrap_or(false))
+}
+
+/// Lists the element descriptor and element addresses of a slice or delimited list.
+pub(crate) fn elements(
+    vrom: Vrom<'_>,
+    segment_table: &SegmentTable,
+    target: &QueryTarget,
+) -> Result<(TypeDescriptor, Vec<VromAddr>), QueryError> {
+    let desc = target.desc;
+    let mut addrs = vec![];
+    match target.location {
+        StructFieldLocation::Simple { .. } => return Err(QueryError::NotIndexable(desc.name())),
+        StructFieldLocation::Slice { ptr_offset, .. }
+        | StructFieldLocation::InferredSlice { ptr_offset, .. } => {
+            let count = target
+                .location
+                .slice_count(vrom, segment_table, target.base_addr)?;
+            let segment_addr = SegmentAddr::from_vrom(vrom, target.base_addr + ptr_offset)?;
+            if !segment_addr.is_null() {
+                let mut addr = segment_table.resolve(segment_addr)?;
+                let size = desc
+                    .size()
+                    .ok_or_else(|| QueryError::UnsizedElement(desc.name()))?;
+                for _ in 0..count {
+                    addrs.push(addr);
+                    addr += size;
+                }
+            }
+        }
+        StructFieldLocation::InlineDelimitedList { .. }
+        | StructFieldLocation::InlineSlice { .. } => {
+            addrs = target
+                .location
+                .inline_elements(vrom, desc, target.base_addr)?
+                .0;
+        }
+    }
+    Ok((desc, addrs))
+}
+
+/// Converts `ActorList` to `ACTOR_LIST`.
+fn camel_to_snake_upper(s: &str) -> String {
+    let mut result = String::new();
+    for (i, c) in s.chars().enumerate() {
+        if i > 0 && c.is_ascii_uppercase() {
+            result.push('_');
+        }
+        result.push(c.to_ascii_uppercase());
+    }
+    result
+}
+
+struct Parser<'a> {
+    s: &'a str,
+    offset: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn parse(mut self) -> Result<Query, QueryError> {
+        let mut steps = vec![QueryStep::Field(self.ident()?)];
+        while let Some(c) = self.peek() {
+            self.offset += 1;
+            match c {
+                '.' => steps.push(QueryStep::Field(self.ident()?)),
+                '[' => {
+                    steps.push(match self.peek() {
+                        Some(c) if c.is_ascii_digit() => QueryStep::Index(self.number()?),
+                        _ => QueryStep::Variant(self.ident()?),
+                    });
+                    self.close_bracket()?;
+                }
+                _ => return Err(self.error(self.offset - 1, "expected '.' or '['")),
+            }
+        }
+        Ok(Query { steps })
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.s[self.offset..].chars().next()
+    }
+
+    fn take_while(&mut self, f: impl Fn(char) -> bool) -> &'a str {
+        let rest = &self.s[self.offset..];
+        let len = rest.find(|c| !f(c)).unwrap_or(rest.len());
+        self.offset += len;
+        &rest[..len]
+    }
+
+    fn ident(&mut self) -> Result<String, QueryError> {
+        let start = self.offset;
+        match self.peek() {
+            Some(c) if c == '_' || c.is_ascii_alphabetic() => (),
+            _ => return Err(self.error(start, "expected an identifier")),
+        }
+        Ok(self
+            .take_while(|c| c == '_' || c.is_ascii_alphanumeric())
+            .to_string())
+    }
+
+    fn number(&mut self) -> Result<u32, QueryError> {
+        let start = self.offset;
+        let text = self.take_while(|c| c.is_ascii_alphanumeric());
+        let result = if let Some(hex) = text.strip_prefix("0x") {
+            u32::from_str_radix(hex, 16)
+        } else {
+            text.parse()
+        };
+        result.map_err(|_| self.error(start, "expected a number"))
+    }
+
+    fn close_bracket(&mut self) -> Result<(), QueryError> {
+        if self.peek() == Some(']') {
+            self.offset += 1;
+            Ok(())
+        } else {
+            Err(self.error(self.offset, "expected ']'"))
+        }
+    }
+
+    fn error(&self, offset: usize, message: &'static str) -> QueryError {
+        QueryError::Syntax { offset, message }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PrimitiveType, StructDescriptor};
+    use oot_explorer_segment::Segment;
+
+    #[test]
+    fn parses_steps() {
+        let query = Query::parse("scene[0x55].headers[ActorList].actor_list[3].pos_x").unwrap();
+        assert_eq!(
+            query.steps,
+            vec![
+                QueryStep::Field("scene".to_string()),
+                QueryStep::Index(0x55),
+                QueryStep::Field("headers".to_string()),
+                QueryStep::Variant("ActorList".to_string()),
+                QueryStep::Field("actor_list".to_string()),
+                QueryStep::Index(3),
+                QueryStep::Field("pos_x".to_string()),
+            ],
+        );
+    }
+
+    #[test]
+    fn display_round_trips() {
+        let text = "scene[85].room[2].headers[ACTOR_LIST].actor_list[3]";
+        assert_eq!(Query::parse(text).unwrap().to_string(), text);
+    }
+
+    #[test]
+    fn rejects_bad_syntax() {
+        for text in &[
+            "",
+            ".scene",
+            "scene[",
+            "scene[1",
+            "scene[0xzz]",
+            "scene..x",
+            "scene x",
+        ] {
+            assert!(
+                matches!(Query::parse(text), Err(QueryError::Syntax { .. })),
+                "{:?}",
+                text,
+            );
+        }
+    }
+
+    #[test]
+    fn converts_camel_case() {
+        assert_eq!(camel_to_snake_upper("ActorList"), "ACTOR_LIST");
+        assert_eq!(camel_to_snake_upper("Mesh"), "MESH");
+    }
+
+    #[test]
+    fn rejects_unsized_slice_elements() {
+        const UNSIZED: StructDescriptor = StructDescriptor {
+            name: "Unsized",
+            size: None,
+            is_end: None,
+            fields: &[],
+        };
+        let target = QueryTarget {
+            base_addr: VromAddr(0),
+            location: StructFieldLocation::Slice {
+                count_offset: 0,
+                count_desc: PrimitiveType::U16,
+                count_span: None,
+                ptr_offset: 4,
+            },
+            desc: TypeDescriptor::Struct(&UNSIZED),
+        };
+        let data = [0, 1, 0, 0, 0x02, 0, 0, 0];
+        let segment_table = SegmentTable::new().with(Segment::SCENE, VromAddr(0));
+        assert!(matches!(
+            elements(Vrom(&data), &segment_table, &target),
+            Err(QueryError::UnsizedElement("Unsized")),
+        ));
+    }
+
+    #[test]
+    fn rejects_non_integer_discriminants() {
+        const BAD: UnionDescriptor = UnionDescriptor {
+            name: "Bad",
+            size: Some(4),
+            is_end: None,
+            end_discriminant: None,
+            discriminant_offset: 0,
+            discriminant_desc: TypeDescriptor::Struct(&StructDescriptor {
+                name: "Empty",
+                size: Some(0),
+                is_end: None,
+                fields: &[],
+            }),
+            variants: &[],
+            fallback: None,
+        };
+        let data = [0; 4];
+        assert!(matches!(
+            variant_matches(Vrom(&data), &BAD, VromAddr(0), "Empty"),
+            Err(QueryError::PseudoItem(PseudoItem::Inaccessible { .. })),
+        ));
+    }
+}
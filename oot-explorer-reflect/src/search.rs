@@ -0,0 +1,231 @@
+use oot_explorer_segment::SegmentTable;
+use oot_explorer_vrom::{Vrom, VromAddr};
+use std::collections::HashSet;
+
+use crate::walk::{walk, Visitor};
+use crate::{Query, QueryStep, QueryTarget, TypeDescriptor, Value};
+
+/// An instance of a searched-for type.
+#[derive(Clone, Debug)]
+pub struct SearchHit {
+    /// A query that resolves to the instance, or to the pointer field that points to it.
+    pub query: Query,
+    pub addr: VromAddr,
+    pub value: Value,
+}
+
+/// Finds every instance of `desc` reachable from `root`, whose query is `root_query`, for which
+/// `predicate` returns true.
+///
+/// The search follows struct fields, union variants, pointers, slices, and delimited lists. Data
+/// that fails to read is skipped. Types are matched by name.
+pub fn search<P>(
+    vrom: Vrom<'_>,
+    segment_table: &SegmentTable,
+    root_query: &Query,
+    root: &QueryTarget,
+    desc: TypeDescriptor,
+    predicate: P,
+) -> Vec<SearchHit>
+where
+    P: FnMut(&Value) -> bool,
+{
+    let mut search = Search {
+        vrom,
+        segment_table,
+        desc,
+        predicate,
+        hits: vec![],
+    };
+    walk(vrom, segment_table, &root_query.steps, root, &mut search);
+    search.hits
+}
+
+/// Finds a type by name among the types reachable from `roots`.
+pub fn find_type(roots: &[TypeDescriptor], name: &str) -> Option<TypeDescriptor> {
+    fn find(
+        visited: &mut HashSet<&'static str>,
+        desc: TypeDescriptor,
+        name: &str,
+    ) -> Option<TypeDescriptor> {
+        if desc.name() == name {
+            return Some(desc);
+        }
+        if !visited.insert(desc.name()) {
+            return None;
+        }
+        match desc {
+            TypeDescriptor::Struct(desc) => desc
+                .fields
+                .iter()
+                .find_map(|field| find(visited, field.desc, name)),
+            TypeDescriptor::Union(desc) => {
+                find(visited, desc.discriminant_desc, name).or_else(|| {
+                    desc.variants
+                        .iter()
+                        .find_map(|&(_, variant)| find(visited, variant, name))
+                })
+            }
+            TypeDescriptor::Pointer(desc) => find(visited, desc.target(), name),
+            TypeDescriptor::Enum(_)
+            | TypeDescriptor::Bitfield(_)
+            | TypeDescriptor::Primitive(_) => None,
+        }
+    }
+
+    let mut visited = HashSet::new();
+    roots
+        .iter()
+        .find_map(|&root| find(&mut visited, root, name))
+}
+
+struct Search<'a, P> {
+    vrom: Vrom<'a>,
+    segment_table: &'a SegmentTable,
+    desc: TypeDescriptor,
+    predicate: P,
+    hits: Vec<SearchHit>,
+}
+
+impl<'a, P> Visitor for Search<'a, P>
+where
+    P: FnMut(&Value) -> bool,
+{
+    fn value(&mut self, steps: &[QueryStep], desc: TypeDescriptor, addr: VromAddr) {
+        if desc.name() == self.desc.name() {
+            let value = Value::read(self.vrom, self.segment_table, desc, addr);
+            if (self.predicate)(&value) {
+                self.hits.push(SearchHit {
+                    query: Query {
+                        steps: steps.to_vec(),
+                    },
+                    addr,
+                    value,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        FieldDescriptor, PointerDescriptor, PrimitiveType, StructDescriptor, StructFieldLocation,
+        U16_DESC,
+    };
+    use oot_explorer_segment::Segment;
+
+    const ITEM: StructDescriptor = StructDescriptor {
+        name: "Item",
+        size: Some(4),
+        is_end: None,
+        fields: &[
+            FieldDescriptor {
+                name: "kind",
+                location: StructFieldLocation::Simple { offset: 0 },
+                desc: U16_DESC,
+            },
+            FieldDescriptor {
+                name: "value",
+                location: StructFieldLocation::Simple { offset: 2 },
+                desc: U16_DESC,
+            },
+        ],
+    };
+
+    const ITEM_PTR: PointerDescriptor = PointerDescriptor {
+        name: "Item*",
+        target: || TypeDescriptor::Struct(&ITEM),
+        nullable: true,
+    };
+
+    /// A list of items and a pointer to a favorite one.
+    const BAG: StructDescriptor = StructDescriptor {
+        name: "Bag",
+        size: Some(0xc),
+        is_end: None,
+        fields: &[
+            FieldDescriptor {
+                name: "items",
+                location: StructFieldLocation::Slice {
+                    count_offset: 0,
+                    count_desc: PrimitiveType::U16,
+                    count_span: None,
+                    ptr_offset: 4,
+                },
+                desc: TypeDescriptor::Struct(&ITEM),
+            },
+            FieldDescriptor {
+                name: "favorite",
+                location: StructFieldLocation::Simple { offset: 8 },
+                desc: TypeDescriptor::Pointer(&ITEM_PTR),
+            },
+        ],
+    };
+
+    const DATA: [u8; 0x18] = [
+        0, 3, 0, 0, 0x02, 0, 0, 0xc, 0x02, 0, 0, 0x10, //
+        0, 1, 0, 5, 0, 2, 0, 6, 0, 1, 0, 7,
+    ];
+
+    fn search_bag<P>(data: &[u8], predicate: P) -> Vec<SearchHit>
+    where
+        P: FnMut(&Value) -> bool,
+    {
+        let segment_table = SegmentTable::new().with(Segment::SCENE, VromAddr(0));
+        search(
+            Vrom(data),
+            &segment_table,
+            &Query::parse("bag").unwrap(),
+            &QueryTarget::root(TypeDescriptor::Struct(&BAG), VromAddr(0)),
+            TypeDescriptor::Struct(&ITEM),
+            predicate,
+        )
+    }
+
+    #[test]
+    fn finds_matching_instances_once() {
+        let hits = search_bag(&DATA, |value| {
+            value.field("kind").unwrap().as_i64() == Some(1)
+        });
+        let found: Vec<_> = hits
+            .iter()
+            .map(|hit| (hit.addr, hit.query.to_string()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (VromAddr(0xc), "bag.items[0]".to_string()),
+                (VromAddr(0x14), "bag.items[2]".to_string()),
+            ],
+        );
+        assert_eq!(hits[1].value.field("value").unwrap().as_i64(), Some(7));
+    }
+
+    #[test]
+    fn skips_unresolvable_slices() {
+        let mut data = DATA;
+        data[4] = 0x05;
+        let hits = search_bag(&data, |_| true);
+        let found: Vec<_> = hits
+            .iter()
+            .map(|hit| (hit.addr, hit.query.to_string()))
+            .collect();
+        assert_eq!(found, [(VromAddr(0x10), "bag.favorite".to_string())]);
+    }
+
+    #[test]
+    fn finds_types_by_name() {
+        let roots = [U16_DESC, TypeDescriptor::Struct(&BAG)];
+        assert_eq!(
+            find_type(&roots, "Item").map(|desc| desc.name()),
+            Some("Item")
+        );
+        assert_eq!(
+            find_type(&roots, "Item*").map(|desc| desc.name()),
+            Some("Item*")
+        );
+        assert!(find_type(&roots, "Missing").is_none());
+    }
+}
@@ -1,3 +1,5 @@
+use oot_explorer_read::{FromVrom, Layout, ReadError, MAX_INFERRED_LEN};
+use oot_explorer_segment::{SegmentAddr, SegmentTable};
 use oot_explorer_vrom::{Vrom, VromAddr};
 
 use crate::{PrimitiveType, TypeDescriptor};
@@ -11,6 +13,9 @@ pub struct StructDescriptor {
 
 pub type IsEndFn = fn(Vrom<'_>, VromAddr) -> bool;
 
+/// Infers the number of elements in a list at a VROM address, for lists whose count isn't stored.
+pub type InferLenFn = fn(Vrom<'_>, &SegmentTable, VromAddr) -> u32;
+
 pub struct FieldDescriptor {
     pub name: &'static str,
     pub location: StructFieldLocation,
@@ -25,18 +30,338 @@ pub enum StructFieldLocation {
     Slice {
         count_offset: u32,
         count_desc: PrimitiveType,
+        /// The shift and mask of the bits that hold the count, if it shares `count_desc` with
+        /// other values.
+        count_span: Option<(u32, u32)>,
         ptr_offset: u32,
     },
     InlineDelimitedList {
         offset: u32,
     },
+    /// Consecutive values stored in place, with their count stored elsewhere in the struct. If the
+    /// values have an `is_end`, the slice also ends at the first value it detects.
+    InlineSlice {
+        count_offset: u32,
+        count_desc: PrimitiveType,
+        offset: u32,
+    },
+    /// A segment address of a list whose count isn't stored and has to be inferred.
+    InferredSlice {
+        ptr_offset: u32,
+        infer_len: InferLenFn,
+    },
+}
+
+impl StructFieldLocation {
+    /// Reads the element count of a slice or inline slice field of the struct at `base_addr`.
+    ///
+    /// Inferred counts are capped at [`MAX_INFERRED_LEN`]. A NULL or unresolvable inferred slice
+    /// has no elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the location isn't a slice.
+    pub fn slice_count(
+        &self,
+        vrom: Vrom<'_>,
+        segment_table: &SegmentTable,
+        base_addr: VromAddr,
+    ) -> Result<u32, ReadError> {
+        match *self {
+            StructFieldLocation::Slice {
+                count_offset,
+                count_desc,
+                count_span,
+                ..
+            } => {
+                let count = count_desc.read_as_u32(vrom, base_addr + count_offset)?;
+                Ok(match count_span {
+                    Some((shift, mask)) => (count >> shift) & mask,
+                    None => count,
+                })
+            }
+            StructFieldLocation::InlineSlice {
+                count_offset,
+                count_desc,
+                ..
+            } => count_desc.read_as_u32(vrom, base_addr + count_offset),
+            StructFieldLocation::InferredSlice {
+                ptr_offset,
+                infer_len,
+            } => {
+                let segment_addr = SegmentAddr::from_vrom(vrom, base_addr + ptr_offset)?;
+                if segment_addr.is_null() {
+                    return Ok(0);
+                }
+                Ok(match segment_table.resolve(segment_addr) {
+                    Ok(addr) => infer_len(vrom, segment_table, addr).min(MAX_INFERRED_LEN),
+                    Err(_) => 0,
+                })
+            }
+            StructFieldLocation::Simple { .. }
+            | StructFieldLocation::InlineDelimitedList { .. } => {
+                panic!("field location is not a slice")
+            }
+        }
+    }
+
+    /// Lists the addresses of the elements of an inline list field of the struct at `base_addr`,
+    /// along with the address just past the last element.
+    ///
+    /// Elements without a fixed size are measured with [`TypeDescriptor::size_at`]. The list ends
+    /// early at an element whose size can't be determined or that runs past the end of VROM.
+    /// Stored counts are capped at [`MAX_INFERRED_LEN`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the location isn't an inline list, or if the elements of a delimited list have
+    /// no `is_end`.
+    pub fn inline_elements(
+        &self,
+        vrom: Vrom<'_>,
+        desc: TypeDescriptor,
+        base_addr: VromAddr,
+    ) -> Result<(Vec<VromAddr>, VromAddr), ReadError> {
+        let (offset, count, is_end) = match *self {
+            StructFieldLocation::InlineDelimitedList { offset } => {
+                let is_end = desc.is_end().unwrap_or_else(|| {
+                    panic!("delimited list element {} has no is_end", desc.name())
+                });
+                (offset, None, Some(is_end))
+            }
+            StructFieldLocation::InlineSlice {
+                count_offset,
+                count_desc,
+                offset,
+            } => (
+                offset,
+                Some(
+                    count_desc
+                        .read_as_u32(vrom, base_addr + count_offset)?
+                        .min(MAX_INFERRED_LEN),
+                ),
+                desc.is_end(),
+            ),
+            StructFieldLocation::Simple { .. }
+            | StructFieldLocation::Slice { .. }
+            | StructFieldLocation::InferredSlice { .. } => {
+                panic!("field location is not an inline list")
+            }
+        };
+
+        let mut addrs = vec![];
+        let mut addr = base_addr + offset;
+        loop {
+            if count.is_some_and(|count| addrs.len() as u32 >= count) {
+                break;
+            }
+            let size = match desc.size_at(vrom, addr) {
+                Some(size) if size > 0 && vrom.slice(addr..addr + size).is_ok() => size,
+                _ => break,
+            };
+            addrs.push(addr);
+            let is_last = is_end.is_some_and(|is_end| is_end(vrom, addr));
+            addr += size;
+            if is_last {
+                break;
+            }
+        }
+        Ok((addrs, addr))
+    }
+}
+
+impl FieldDescriptor {
+    /// The offset just past the end of the field in the struct at `base_addr`, including any
+    /// stored count and the elements of inline lists.
+    fn end_at(&self, vrom: Vrom<'_>, base_addr: VromAddr) -> Option<u32> {
+        Some(match self.location {
+            StructFieldLocation::Simple { offset } => {
+                offset + self.desc.size_at(vrom, base_addr + offset)?
+            }
+            StructFieldLocation::Slice {
+                count_offset,
+                count_desc,
+                ptr_offset,
+                ..
+            } => (count_offset + count_desc.size()).max(ptr_offset + SegmentAddr::SIZE),
+            StructFieldLocation::InferredSlice { ptr_offset, .. } => ptr_offset + SegmentAddr::SIZE,
+            StructFieldLocation::InlineDelimitedList { .. } => {
+                let (_, end) = self
+                    .location
+                    .inline_elements(vrom, self.desc, base_addr)
+                    .ok()?;
+                end - base_addr
+            }
+            StructFieldLocation::InlineSlice {
+                count_offset,
+                count_desc,
+                ..
+            } => {
+                let (_, end) = self
+                    .location
+                    .inline_elements(vrom, self.desc, base_addr)
+                    .ok()?;
+                (end - base_addr).max(count_offset + count_desc.size())
+            }
+        })
+    }
+}
+
+impl StructDescriptor {
+    /// The size of the struct at `addr`, measured as the furthest extent of its fields if it
+    /// doesn't have a fixed size.
+    pub(crate) fn size_at(&self, vrom: Vrom<'_>, addr: VromAddr) -> Option<u32> {
+        if let Some(size) = self.size {
+            return Some(size);
+        }
+        self.fields
+            .iter()
+            .try_fold(0, |size, field| Some(size.max(field.end_at(vrom, addr)?)))
+    }
 }
 
 pub struct UnionDescriptor {
     pub name: &'static str,
     pub size: Option<u32>,
     pub is_end: Option<IsEndFn>,
-    pub discriminant_offset: u32,
+    /// The discriminant that `is_end` detects, if it looks at nothing else. Unlike `is_end`, this
+    /// can be described in other languages.
+    pub end_discriminant: Option<u32>,
+    /// Where the discriminant is relative to the union. It's negative if the discriminant is
+    /// stored before the union, like a field earlier in an enclosing struct.
+    pub discriminant_offset: i32,
     pub discriminant_desc: TypeDescriptor,
     pub variants: &'static [(u32, TypeDescriptor)],
+    /// The variant for discriminants that aren't listed in `variants`, if there is one.
+    pub fallback: Option<TypeDescriptor>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{U16_DESC, U32_DESC};
+
+    /// A count followed by that many values.
+    const RUN: StructDescriptor = StructDescriptor {
+        name: "Run",
+        size: None,
+        is_end: Some(is_empty_run),
+        fields: &[FieldDescriptor {
+            name: "values",
+            location: StructFieldLocation::InlineSlice {
+                count_offset: 0,
+                count_desc: PrimitiveType::U16,
+                offset: 2,
+            },
+            desc: U16_DESC,
+        }],
+    };
+
+    const RUNS: StructDescriptor = StructDescriptor {
+        name: "Runs",
+        size: None,
+        is_end: None,
+        fields: &[FieldDescriptor {
+            name: "runs",
+            location: StructFieldLocation::InlineDelimitedList { offset: 0 },
+            desc: TypeDescriptor::Struct(&RUN),
+        }],
+    };
+
+    fn is_empty_run(vrom: Vrom<'_>, addr: VromAddr) -> bool {
+        u16::from_vrom(vrom, addr).map_or(true, |count| count == 0)
+    }
+
+    #[test]
+    fn measures_inline_lists() {
+        let data = [0, 2, 0, 1, 0, 2, 0, 1, 0, 3, 0, 0, 0xff, 0xff];
+        let vrom = Vrom(&data);
+        let runs = &RUNS.fields[0];
+
+        let (addrs, end) = runs
+            .location
+            .inline_elements(vrom, runs.desc, VromAddr(0))
+            .unwrap();
+        assert_eq!(addrs, [VromAddr(0), VromAddr(6), VromAddr(10)]);
+        assert_eq!(end, VromAddr(12));
+        assert_eq!(
+            TypeDescriptor::Struct(&RUN).size_at(vrom, VromAddr(6)),
+            Some(4)
+        );
+        assert_eq!(
+            TypeDescriptor::Struct(&RUNS).size_at(vrom, VromAddr(0)),
+            Some(12)
+        );
+    }
+
+    #[test]
+    fn stops_at_the_end_of_vrom() {
+        let data = [0xff, 0xff, 0, 1];
+        let values = &RUN.fields[0];
+        let (addrs, _) = values
+            .location
+            .inline_elements(Vrom(&data), values.desc, VromAddr(0))
+            .unwrap();
+        assert_eq!(addrs, [VromAddr(2)]);
+    }
+
+    #[test]
+    fn inline_slices_stop_at_their_end() {
+        const COUNTED_RUNS: StructDescriptor = StructDescriptor {
+            name: "CountedRuns",
+            size: None,
+            is_end: None,
+            fields: &[FieldDescriptor {
+                name: "runs",
+                location: StructFieldLocation::InlineSlice {
+                    count_offset: 0,
+                    count_desc: PrimitiveType::U16,
+                    offset: 2,
+                },
+                desc: TypeDescriptor::Struct(&RUN),
+            }],
+        };
+        let data = [0, 3, 0, 1, 0, 5, 0, 0, 0, 1, 0, 6];
+        let runs = &COUNTED_RUNS.fields[0];
+        let (addrs, end) = runs
+            .location
+            .inline_elements(Vrom(&data), runs.desc, VromAddr(0))
+            .unwrap();
+        assert_eq!(addrs, [VromAddr(2), VromAddr(6)]);
+        assert_eq!(end, VromAddr(8));
+    }
+
+    #[test]
+    fn caps_inline_slice_counts() {
+        let mut data = vec![0; 2 + 2 * (MAX_INFERRED_LEN as usize + 1)];
+        data[..2].copy_from_slice(&u16::MAX.to_be_bytes());
+        let values = &RUN.fields[0];
+        let (addrs, _) = values
+            .location
+            .inline_elements(Vrom(&data), values.desc, VromAddr(0))
+            .unwrap();
+        assert_eq!(addrs.len() as u32, MAX_INFERRED_LEN);
+    }
+
+    #[test]
+    fn unlisted_discriminants_select_the_fallback() {
+        const UNION: UnionDescriptor = UnionDescriptor {
+            name: "Union",
+            size: None,
+            is_end: None,
+            end_discriminant: None,
+            discriminant_offset: 0,
+            discriminant_desc: U16_DESC,
+            variants: &[(1, U32_DESC)],
+            fallback: Some(TypeDescriptor::Struct(&RUN)),
+        };
+        let data = [0, 1, 0, 0, 0, 2, 0, 7, 0, 8];
+        let vrom = Vrom(&data);
+        assert_eq!(UNION.variant_at(vrom, VromAddr(0)).unwrap().name(), "u32");
+        assert_eq!(UNION.variant_at(vrom, VromAddr(4)).unwrap().name(), "Run");
+        assert_eq!(
+            TypeDescriptor::Union(&UNION).size_at(vrom, VromAddr(4)),
+            Some(6)
+        );
+    }
 }
@@ -32,13 +32,29 @@ impl TypeDescriptor {
         match self {
             TypeDescriptor::Struct(desc) => desc.size,
             TypeDescriptor::Union(desc) => desc.size,
-            TypeDescriptor::Enum(_) => None,
-            TypeDescriptor::Bitfield(_) => None,
+            TypeDescriptor::Enum(desc) => Some(desc.underlying.size()),
+            TypeDescriptor::Bitfield(desc) => Some(desc.underlying.size()),
             TypeDescriptor::Primitive(desc) => Some(desc.size()),
             TypeDescriptor::Pointer(_) => Some(4),
         }
     }
 
+    /// The size of the value at `addr`. Unlike [`size`](TypeDescriptor::size), this also measures
+    /// values whose size depends on their contents: a struct without a fixed size extends to the
+    /// end of its furthest field, and a union without a fixed size is the size of its variant.
+    ///
+    /// Returns `None` if the size can't be determined from VROM.
+    pub fn size_at(&self, vrom: Vrom<'_>, addr: VromAddr) -> Option<u32> {
+        match self {
+            TypeDescriptor::Struct(desc) => desc.size_at(vrom, addr),
+            TypeDescriptor::Union(desc) => match desc.size {
+                Some(size) => Some(size),
+                None => desc.variant_at(vrom, addr).ok()?.size_at(vrom, addr),
+            },
+            desc => desc.size(),
+        }
+    }
+
     pub fn is_end(&self) -> Option<IsEndFn> {
         match self {
             TypeDescriptor::Struct(desc) => desc.is_end,
@@ -0,0 +1,551 @@
+use std::collections::HashSet;
+use std::fmt::{self, Display, Formatter};
+
+use oot_explorer_read::{FromVrom, Qu0_16, Qu10_2, Qu1_11, Vec3f, Vec3s};
+use oot_explorer_segment::{SegmentAddr, SegmentTable};
+use oot_explorer_vrom::{Vrom, VromAddr};
+use serde::Serialize;
+
+use crate::{
+    BitfieldDescriptor, EnumDescriptor, FieldDescriptor, PointerDescriptor, PrimitiveType,
+    PseudoItem, StructDescriptor, StructFieldLocation, TypeDescriptor, UnionDescriptor,
+};
+
+/// A value read from VROM according to a `TypeDescriptor`, along with everything it contains.
+///
+/// Pointers and slices are followed, so a tree rooted at a scene holds all of the scene's typed
+/// data. Anything that fails to read becomes a [`ValueKind::Error`] node in place of the value.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Value {
+    /// The VROM address of the value. For slice fields, this is the address of the pointer.
+    pub addr: u32,
+    pub type_name: String,
+    #[serde(flatten)]
+    pub kind: ValueKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum ValueKind {
+    Struct {
+        fields: Vec<Field>,
+    },
+    Union {
+        discriminant: Box<Value>,
+        /// The value interpreted as the variant selected by the discriminant, or an
+        /// [`UnknownVariant`](ValueKind::UnknownVariant) if the discriminant isn't recognized.
+        variant: Option<Box<Value>>,
+    },
+    Enum {
+        value: u32,
+        name: Option<&'static str>,
+    },
+    Bitfield {
+        value: u32,
+        spans: Vec<BitfieldSpanValue>,
+    },
+    Primitive {
+        value: PrimitiveValue,
+    },
+    #[serde(rename_all = "camelCase")]
+    Pointer {
+        segment_addr: u32,
+        /// The pointed-to value, or `None` for a NULL pointer.
+        target: Option<Box<Value>>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Slice {
+        count: u32,
+        segment_addr: u32,
+        elements: Vec<Value>,
+    },
+    List {
+        elements: Vec<Value>,
+    },
+    /// A union variant that isn't known. See [`PseudoItem::UnknownVariant`].
+    UnknownVariant {
+        discriminant: u32,
+        bytes: Vec<u8>,
+    },
+    /// A pointer that couldn't be followed. See [`PseudoItem::UnresolvedPointer`].
+    #[serde(rename_all = "camelCase")]
+    UnresolvedPointer {
+        segment_addr: u32,
+        message: String,
+    },
+    /// A struct or union that was already read elsewhere in the same tree, at the same address
+    /// and with the same type name.
+    Reference,
+    Error {
+        message: String,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Field {
+    pub name: &'static str,
+    pub value: Value,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct BitfieldSpanValue {
+    pub value: u32,
+    pub name: Option<&'static str>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum PrimitiveValue {
+    Bool(bool),
+    Int(i64),
+    U64(u64),
+    /// A floating-point or fixed-point number, in natural units.
+    Float(f64),
+    /// A vector's x, y, and z components.
+    Vec3([f64; 3]),
+}
+
+impl Display for PrimitiveValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            PrimitiveValue::Bool(x) => write!(f, "{}", x),
+            PrimitiveValue::Int(x) => write!(f, "{}", x),
+            PrimitiveValue::U64(x) => write!(f, "{}", x),
+            PrimitiveValue::Float(x) => write!(f, "{}", x),
+            PrimitiveValue::Vec3([x, y, z]) => write!(f, "({}, {}, {})", x, y, z),
+        }
+    }
+}
+
+impl Value {
+    /// Reads a value of the described type at `addr`.
+    ///
+    /// A struct or union that appears more than once in the tree, such as a display list that
+    /// calls itself, is only read the first time. Later occurrences become
+    /// [`ValueKind::Reference`] stubs.
+    pub fn read(
+        vrom: Vrom<'_>,
+        segment_table: &SegmentTable,
+        desc: TypeDescriptor,
+        addr: VromAddr,
+    ) -> Value {
+        Reader::new(vrom, segment_table).read(desc, addr)
+    }
+
+    /// Reads a struct field from the struct at `base_addr`.
+    pub fn read_field(
+        vrom: Vrom<'_>,
+        segment_table: &SegmentTable,
+        field: &FieldDescriptor,
+        base_addr: VromAddr,
+    ) -> Value {
+        Value::read_location(vrom, segment_table, field.desc, &field.location, base_addr)
+    }
+
+    /// Reads a value of the described type, stored in a field at `location` relative to
+    /// `base_addr`.
+    pub fn read_location(
+        vrom: Vrom<'_>,
+        segment_table: &SegmentTable,
+        desc: TypeDescriptor,
+        location: &StructFieldLocation,
+        base_addr: VromAddr,
+    ) -> Value {
+        Reader::new(vrom, segment_table).read_location(desc, location, base_addr)
+    }
+
+    /// Looks up a struct field by name, looking through union variants and pointers.
+    pub fn field(&self, name: &str) -> Option<&Value> {
+        match &self.kind {
+            ValueKind::Struct { fields } => fields
+                .iter()
+                .find(|field| field.name == name)
+                .map(|field| &field.value),
+            ValueKind::Union { variant, .. } => variant.as_ref()?.field(name),
+            ValueKind::Pointer { target, .. } => target.as_ref()?.field(name),
+            _ => None,
+        }
+    }
+
+    /// Returns the numeric value of a primitive, enum, or bitfield.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self.kind {
+            ValueKind::Enum { value, .. } | ValueKind::Bitfield { value, .. } => Some(value.into()),
+            ValueKind::Primitive {
+                value: PrimitiveValue::Int(value),
+            } => Some(value),
+            ValueKind::Primitive {
+                value: PrimitiveValue::Bool(value),
+            } => Some(value.into()),
+            _ => None,
+        }
+    }
+}
+
+fn error(e: impl ToString) -> ValueKind {
+    ValueKind::Error {
+        message: e.to_string(),
+    }
+}
+
+/// Makes a value that stands in for a value of type `type_name`.
+fn pseudo_value(type_name: &str, item: PseudoItem) -> Value {
+    let addr = item.addr().0;
+    let kind = match item {
+        PseudoItem::UnknownVariant {
+            discriminant,
+            bytes,
+            ..
+        } => ValueKind::UnknownVariant {
+            discriminant,
+            bytes,
+        },
+        PseudoItem::UnresolvedPointer {
+            segment_addr,
+            message,
+            ..
+        } => ValueKind::UnresolvedPointer {
+            segment_addr: segment_addr.0,
+            message,
+        },
+        PseudoItem::Inaccessible { message, .. } => ValueKind::Error { message },
+    };
+    Value {
+        addr,
+        type_name: type_name.to_string(),
+        kind,
+    }
+}
+
+/// Reads one value tree, remembering which structs and unions it has already read.
+struct Reader<'a> {
+    vrom: Vrom<'a>,
+    segment_table: &'a SegmentTable,
+    visited: HashSet<(VromAddr, &'static str)>,
+}
+
+impl<'a> Reader<'a> {
+    fn new(vrom: Vrom<'a>, segment_table: &'a SegmentTable) -> Self {
+        Reader {
+            vrom,
+            segment_table,
+            visited: HashSet::new(),
+        }
+    }
+
+    fn read(&mut self, desc: TypeDescriptor, addr: VromAddr) -> Value {
+        let vrom = self.vrom;
+        let kind = match desc {
+            // Only compound types can lead back to themselves, and primitives are often read
+            // twice at one address, like a union's discriminant and its variant's first field.
+            TypeDescriptor::Struct(_) | TypeDescriptor::Union(_)
+                if !self.visited.insert((addr, desc.name())) =>
+            {
+                ValueKind::Reference
+            }
+            TypeDescriptor::Struct(desc) => self.read_struct(desc, addr),
+            TypeDescriptor::Union(desc) => self.read_union(desc, addr),
+            TypeDescriptor::Enum(desc) => read_enum(vrom, desc, addr),
+            TypeDescriptor::Bitfield(desc) => read_bitfield(vrom, desc, addr),
+            TypeDescriptor::Primitive(desc) => read_primitive(vrom, desc, addr),
+            TypeDescriptor::Pointer(desc) => self.read_pointer(desc, addr),
+        };
+        Value {
+            addr: addr.0,
+            type_name: desc.name().to_string(),
+            kind,
+        }
+    }
+
+    fn read_location(
+        &mut self,
+        desc: TypeDescriptor,
+        location: &StructFieldLocation,
+        base_addr: VromAddr,
+    ) -> Value {
+        let vrom = self.vrom;
+        match *location {
+            StructFieldLocation::Simple { offset } => self.read(desc, base_addr + offset),
+            StructFieldLocation::Slice { ptr_offset, .. }
+            | StructFieldLocation::InferredSlice { ptr_offset, .. } => {
+                let ptr_addr = base_addr + ptr_offset;
+                Value {
+                    addr: ptr_addr.0,
+                    type_name: format!("{}[]*", desc.name()),
+                    kind: match location.slice_count(vrom, self.segment_table, base_addr) {
+                        Ok(count) => self.read_slice(desc, count, ptr_addr),
+                        Err(e) => error(e),
+                    },
+                }
+            }
+            StructFieldLocation::InlineDelimitedList { offset }
+            | StructFieldLocation::InlineSlice { offset, .. } => {
+                let addr = base_addr + offset;
+                let suffix = match location {
+                    StructFieldLocation::InlineSlice { .. } => "[]",
+                    _ => "[..]",
+                };
+                Value {
+                    addr: addr.0,
+                    type_name: format!("{}{}", desc.name(), suffix),
+                    kind: match location.inline_elements(vrom, desc, base_addr) {
+                        Ok((addrs, _)) => ValueKind::List {
+                            elements: addrs
+                                .into_iter()
+                                .map(|addr| self.read(desc, addr))
+                                .collect(),
+                        },
+                        Err(e) => error(e),
+                    },
+                }
+            }
+        }
+    }
+
+    fn read_struct(&mut self, desc: &'static StructDescriptor, addr: VromAddr) -> ValueKind {
+        ValueKind::Struct {
+            fields: desc
+                .fields
+                .iter()
+                .map(|field| Field {
+                    name: field.name,
+                    value: self.read_location(field.desc, &field.location, addr),
+                })
+                .collect(),
+        }
+    }
+
+    fn read_union(&mut self, desc: &'static UnionDescriptor, addr: VromAddr) -> ValueKind {
+        let discriminant = self.read(desc.discriminant_desc, desc.discriminant_addr(addr));
+        let variant = match desc.variant_at(self.vrom, addr) {
+            Ok(variant_desc) => Some(self.read(variant_desc, addr)),
+            Err(item @ PseudoItem::UnknownVariant { .. }) => Some(pseudo_value(desc.name, item)),
+            // The discriminant value already holds the error.
+            Err(_) => None,
+        };
+        ValueKind::Union {
+            discriminant: Box::new(discriminant),
+            variant: variant.map(Box::new),
+        }
+    }
+
+    fn read_pointer(&mut self, desc: &'static PointerDescriptor, addr: VromAddr) -> ValueKind {
+        let segment_addr = match SegmentAddr::from_vrom(self.vrom, addr) {
+            Ok(segment_addr) => segment_addr,
+            Err(e) => return error(e),
+        };
+        let target = match desc.target_at(self.vrom, self.segment_table, addr) {
+            Ok(Some(vrom_addr)) => Some(self.read(desc.target(), vrom_addr)),
+            Ok(None) => None,
+            Err(item) => Some(pseudo_value(desc.target().name(), item)),
+        };
+        ValueKind::Pointer {
+            segment_addr: segment_addr.0,
+            target: target.map(Box::new),
+        }
+    }
+
+    fn read_slice(&mut self, desc: TypeDescriptor, count: u32, ptr_addr: VromAddr) -> ValueKind {
+        let segment_addr = match SegmentAddr::from_vrom(self.vrom, ptr_addr) {
+            Ok(segment_addr) => segment_addr,
+            Err(e) => return error(e),
+        };
+
+        let mut elements = vec![];
+        if !segment_addr.is_null() {
+            let mut addr = match self.segment_table.resolve(segment_addr) {
+                Ok(addr) => addr,
+                Err(e) => return error(e),
+            };
+            let size = match desc.size() {
+                Some(size) => size,
+                None => return error(format!("slice element {} has no size", desc.name())),
+            };
+            for _ in 0..count {
+                elements.push(self.read(desc, addr));
+                addr += size;
+            }
+        }
+
+        ValueKind::Slice {
+            count,
+            segment_addr: segment_addr.0,
+            elements,
+        }
+    }
+}
+
+fn read_enum(vrom: Vrom<'_>, desc: &'static EnumDescriptor, addr: VromAddr) -> ValueKind {
+    match desc.read_as_u32(vrom, addr) {
+        Ok(value) => ValueKind::Enum {
+            value,
+            name: enum_value_name(desc, value),
+        },
+        Err(e) => error(e),
+    }
+}
+
+fn read_bitfield(vrom: Vrom<'_>, desc: &'static BitfieldDescriptor, addr: VromAddr) -> ValueKind {
+    match desc.underlying.read_as_u32(vrom, addr) {
+        Ok(value) => ValueKind::Bitfield {
+            value,
+            spans: desc
+                .fields
+                .iter()
+                .map(|span| {
+                    let value = (value >> span.shift) & span.mask;
+                    BitfieldSpanValue {
+                        value,
+                        name: enum_value_name(span.desc, value),
+                    }
+                })
+                .collect(),
+        },
+        Err(e) => error(e),
+    }
+}
+
+fn read_primitive(vrom: Vrom<'_>, desc: PrimitiveType, addr: VromAddr) -> ValueKind {
+    let value = match desc {
+        PrimitiveType::Bool => bool::from_vrom(vrom, addr).map(PrimitiveValue::Bool),
+        PrimitiveType::U8 => u8::from_vrom(vrom, addr).map(|x| PrimitiveValue::Int(x.into())),
+        PrimitiveType::I8 => i8::from_vrom(vrom, addr).map(|x| PrimitiveValue::Int(x.into())),
+        PrimitiveType::U16 => u16::from_vrom(vrom, addr).map(|x| PrimitiveValue::Int(x.into())),
+        PrimitiveType::I16 => i16::from_vrom(vrom, addr).map(|x| PrimitiveValue::Int(x.into())),
+        PrimitiveType::U32 => u32::from_vrom(vrom, addr).map(|x| PrimitiveValue::Int(x.into())),
+        PrimitiveType::I32 => i32::from_vrom(vrom, addr).map(|x| PrimitiveValue::Int(x.into())),
+        PrimitiveType::U64 => u64::from_vrom(vrom, addr).map(PrimitiveValue::U64),
+        PrimitiveType::F32 => f32::from_vrom(vrom, addr).map(|x| PrimitiveValue::Float(x.into())),
+        PrimitiveType::Qu10_2 => {
+            Qu10_2::from_vrom(vrom, addr).map(|x| PrimitiveValue::Float(x.as_f64()))
+        }
+        PrimitiveType::Qu1_11 => {
+            Qu1_11::from_vrom(vrom, addr).map(|x| PrimitiveValue::Float(x.as_f64()))
+        }
+        PrimitiveType::Qu0_16 => {
+            Qu0_16::from_vrom(vrom, addr).map(|x| PrimitiveValue::Float(x.as_f64()))
+        }
+        PrimitiveType::Vec3s => Vec3s::from_vrom(vrom, addr)
+            .map(|v| PrimitiveValue::Vec3([v.x.into(), v.y.into(), v.z.into()])),
+        PrimitiveType::Vec3f => Vec3f::from_vrom(vrom, addr)
+            .map(|v| PrimitiveValue::Vec3([v.x.into(), v.y.into(), v.z.into()])),
+        PrimitiveType::VromAddr => {
+            VromAddr::from_vrom(vrom, addr).map(|x| PrimitiveValue::Int(x.0.into()))
+        }
+        PrimitiveType::SegmentAddr => {
+            SegmentAddr::from_vrom(vrom, addr).map(|x| PrimitiveValue::Int(x.0.into()))
+        }
+    };
+    match value {
+        Ok(value) => ValueKind::Primitive { value },
+        Err(e) => error(e),
+    }
+}
+
+fn enum_value_name(desc: &'static EnumDescriptor, value: u32) -> Option<&'static str> {
+    desc.values
+        .binary_search_by_key(&value, |&(x, _)| x)
+        .ok()
+        .map(|index| desc.values[index].1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{StructDescriptor, U16_DESC, U32_DESC};
+    use oot_explorer_segment::Segment;
+
+    /// A linked list node, which may point back at an earlier node.
+    static NODE: StructDescriptor = StructDescriptor {
+        name: "Node",
+        size: Some(8),
+        is_end: None,
+        fields: &[
+            FieldDescriptor {
+                name: "value",
+                location: StructFieldLocation::Simple { offset: 0 },
+                desc: U32_DESC,
+            },
+            FieldDescriptor {
+                name: "next",
+                location: StructFieldLocation::Simple { offset: 4 },
+                desc: TypeDescriptor::Pointer(&NODE_PTR),
+            },
+        ],
+    };
+
+    static NODE_PTR: PointerDescriptor = PointerDescriptor {
+        name: "Node*",
+        target: || TypeDescriptor::Struct(&NODE),
+        nullable: true,
+    };
+
+    fn target(value: &Value) -> &Value {
+        match &value.kind {
+            ValueKind::Pointer {
+                target: Some(target),
+                ..
+            } => target,
+            kind => panic!("expected a pointer, got {:?}", kind),
+        }
+    }
+
+    #[test]
+    fn stubs_out_cycles() {
+        let data = [
+            0, 0, 0, 1, 0x02, 0, 0, 8, //
+            0, 0, 0, 2, 0x02, 0, 0, 0,
+        ];
+        let segment_table = SegmentTable::new().with(Segment::SCENE, VromAddr(0));
+        let root = Value::read(
+            Vrom(&data),
+            &segment_table,
+            TypeDescriptor::Struct(&NODE),
+            VromAddr(0),
+        );
+
+        let second = target(root.field("next").unwrap());
+        assert_eq!(second.addr, 8);
+        assert_eq!(second.field("value").unwrap().as_i64(), Some(2));
+        let again = target(second.field("next").unwrap());
+        assert_eq!(again.addr, 0);
+        assert_eq!(again.type_name, "Node");
+        assert_eq!(again.kind, ValueKind::Reference);
+    }
+
+    #[test]
+    fn rejects_unsized_slice_elements() {
+        const UNSIZED: StructDescriptor = StructDescriptor {
+            name: "Unsized",
+            size: None,
+            is_end: None,
+            fields: &[],
+        };
+        let location = StructFieldLocation::Slice {
+            count_offset: 0,
+            count_desc: PrimitiveType::U16,
+            count_span: None,
+            ptr_offset: 4,
+        };
+        let data = [0, 1, 0, 0, 0x02, 0, 0, 0];
+        let segment_table = SegmentTable::new().with(Segment::SCENE, VromAddr(0));
+        let value = Value::read_location(
+            Vrom(&data),
+            &segment_table,
+            TypeDescriptor::Struct(&UNSIZED),
+            &location,
+            VromAddr(0),
+        );
+        assert!(matches!(value.kind, ValueKind::Error { .. }));
+
+        let value = Value::read_location(
+            Vrom(&data),
+            &segment_table,
+            U16_DESC,
+            &location,
+            VromAddr(0),
+        );
+        assert!(matches!(value.kind, ValueKind::Slice { count: 1, .. }));
+    }
+}
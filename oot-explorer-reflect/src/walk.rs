@@ -0,0 +1,150 @@
+use oot_explorer_read::FromVrom;
+use oot_explorer_segment::{SegmentAddr, SegmentTable};
+use oot_explorer_vrom::{Vrom, VromAddr};
+use std::collections::HashSet;
+
+use crate::query::elements;
+use crate::{QueryStep, QueryTarget, StructFieldLocation, TypeDescriptor};
+
+/// Receives everything a [`walk`] reaches.
+pub(crate) trait Visitor {
+    /// Called once for each distinct value, identified by address and type name.
+    fn value(&mut self, steps: &[QueryStep], desc: TypeDescriptor, addr: VromAddr);
+
+    /// Called for each pointer or slice that was followed. `steps` names the pointer or slice
+    /// field, `owner` is the type that contains it, and `addr` is the address of the segment
+    /// address. `count` is the number of elements for slices, or `None` for plain pointers.
+    fn reference(
+        &mut self,
+        _steps: &[QueryStep],
+        _owner: &'static str,
+        _addr: VromAddr,
+        _target_desc: TypeDescriptor,
+        _target_addr: VromAddr,
+        _count: Option<u32>,
+    ) {
+    }
+}
+
+/// Walks everything reachable from `root`, whose query is `root_steps`, following struct fields,
+/// union variants, pointers, slices, and delimited lists. Data that fails to read is skipped.
+pub(crate) fn walk<V: Visitor>(
+    vrom: Vrom<'_>,
+    segment_table: &SegmentTable,
+    root_steps: &[QueryStep],
+    root: &QueryTarget,
+    visitor: &mut V,
+) {
+    let mut walker = Walker {
+        vrom,
+        segment_table,
+        visitor,
+        visited: HashSet::new(),
+    };
+    let mut steps = root_steps.to_vec();
+    walker.visit_location(
+        &mut steps,
+        root.desc.name(),
+        root.desc,
+        &root.location,
+        root.base_addr,
+    );
+}
+
+struct Walker<'a, V> {
+    vrom: Vrom<'a>,
+    segment_table: &'a SegmentTable,
+    visitor: &'a mut V,
+    visited: HashSet<(VromAddr, &'static str)>,
+}
+
+impl<'a, V: Visitor> Walker<'a, V> {
+    fn visit_location(
+        &mut self,
+        steps: &mut Vec<QueryStep>,
+        owner: &'static str,
+        desc: TypeDescriptor,
+        location: &StructFieldLocation,
+        base_addr: VromAddr,
+    ) {
+        if let StructFieldLocation::Simple { offset } = *location {
+            return self.visit(steps, owner, desc, base_addr + offset);
+        }
+
+        let target = QueryTarget {
+            base_addr,
+            location: location.clone(),
+            desc,
+        };
+        if let Ok((desc, addrs)) = elements(self.vrom, self.segment_table, &target) {
+            if let (
+                StructFieldLocation::Slice { ptr_offset, .. }
+                | StructFieldLocation::InferredSlice { ptr_offset, .. },
+                Some(&first),
+            ) = (location, addrs.first())
+            {
+                self.visitor.reference(
+                    steps,
+                    owner,
+                    base_addr + *ptr_offset,
+                    desc,
+                    first,
+                    Some(addrs.len() as u32),
+                );
+            }
+            for (index, addr) in addrs.into_iter().enumerate() {
+                steps.push(QueryStep::Index(index as u32));
+                self.visit(steps, owner, desc, addr);
+                steps.pop();
+            }
+        }
+    }
+
+    fn visit(
+        &mut self,
+        steps: &mut Vec<QueryStep>,
+        owner: &'static str,
+        desc: TypeDescriptor,
+        addr: VromAddr,
+    ) {
+        if !self.visited.insert((addr, desc.name())) {
+            return;
+        }
+        self.visitor.value(steps, desc, addr);
+
+        match desc {
+            TypeDescriptor::Struct(struct_desc) => {
+                for field in struct_desc.fields {
+                    steps.push(QueryStep::Field(field.name.to_string()));
+                    self.visit_location(steps, struct_desc.name, field.desc, &field.location, addr);
+                    steps.pop();
+                }
+            }
+            TypeDescriptor::Union(union_desc) => {
+                if let Ok(variant) = union_desc.variant_at(self.vrom, addr) {
+                    self.visit(steps, union_desc.name, variant, addr);
+                }
+            }
+            TypeDescriptor::Pointer(ptr_desc) => {
+                let segment_addr = match SegmentAddr::from_vrom(self.vrom, addr) {
+                    Ok(segment_addr) if !segment_addr.is_null() => segment_addr,
+                    _ => return,
+                };
+                if let Ok(target_addr) = self.segment_table.resolve(segment_addr) {
+                    self.visitor.reference(
+                        steps,
+                        owner,
+                        addr,
+                        ptr_desc.target(),
+                        target_addr,
+                        None,
+                    );
+                    self.visit(steps, owner, ptr_desc.target(), target_addr);
+                }
+            }
+            TypeDescriptor::Enum(_)
+            | TypeDescriptor::Bitfield(_)
+            | TypeDescriptor::Primitive(_) => {}
+        }
+    }
+}
@@ -0,0 +1,284 @@
+use oot_explorer_read::{Qu0_16, Qu10_2, Qu1_11};
+use oot_explorer_vrom::{VromAddr, VromError, VromMut};
+use thiserror::Error;
+
+use crate::{FieldDescriptor, PrimitiveType, QueryTarget, StructFieldLocation, TypeDescriptor};
+
+#[derive(Debug, Error)]
+pub enum WriteError {
+    #[error("{0} can't be edited")]
+    NotEditable(String),
+
+    #[error("{text:?} is not a valid {type_name}")]
+    BadValue {
+        type_name: &'static str,
+        text: String,
+    },
+
+    #[error("{0}")]
+    VromError(#[from] VromError),
+}
+
+/// Parses `text` as a value of the described type and writes it at `addr`.
+///
+/// Integer primitives accept decimal or `0x` hexadecimal integers, and `true` or `false` for
+/// booleans. Floats and fixed-point numbers accept decimal numbers, and vectors accept three
+/// comma-separated components.
+/// Enums additionally accept value names. Bitfields and pointers are written as their raw
+/// underlying values. Structs and unions can't be written as a whole.
+pub fn write_value(
+    vrom: VromMut<'_>,
+    desc: TypeDescriptor,
+    addr: VromAddr,
+    text: &str,
+) -> Result<(), WriteError> {
+    let text = text.trim();
+    let bytes = match desc {
+        TypeDescriptor::Enum(enum_desc) => {
+            match enum_desc.values.iter().find(|&&(_, name)| name == text) {
+                Some(&(value, _)) => Some(int_bytes(enum_desc.underlying, value)),
+                None => parse_bytes(enum_desc.underlying, text),
+            }
+        }
+        TypeDescriptor::Bitfield(bitfield_desc) => parse_bytes(bitfield_desc.underlying, text),
+        TypeDescriptor::Primitive(primitive) => parse_bytes(primitive, text),
+        TypeDescriptor::Pointer(_) => parse_bytes(PrimitiveType::SegmentAddr, text),
+        TypeDescriptor::Struct(_) | TypeDescriptor::Union(_) => {
+            return Err(WriteError::NotEditable(desc.name().to_string()))
+        }
+    };
+    let bytes = bytes.ok_or_else(|| WriteError::BadValue {
+        type_name: desc.name(),
+        text: text.to_string(),
+    })?;
+
+    let mask = match desc {
+        TypeDescriptor::Primitive(primitive) => value_mask(primitive),
+        _ => None,
+    };
+    let dest = vrom.slice_mut(addr..addr + bytes.len() as u32)?;
+    match mask {
+        // Only the masked bits hold the value. The rest belong to neighbouring fields.
+        Some(mask) => {
+            for ((dest, byte), mask) in dest.iter_mut().zip(bytes).zip(mask.to_be_bytes()) {
+                *dest = (*dest & !mask) | (byte & mask);
+            }
+        }
+        None => dest.copy_from_slice(&bytes),
+    }
+    Ok(())
+}
+
+/// Parses `text` and writes it into a field stored at `location` relative to `base_addr`.
+///
+/// Only simple fields can be written. Slices and inline lists are edited element by element.
+pub fn write_location(
+    vrom: VromMut<'_>,
+    desc: TypeDescriptor,
+    location: &StructFieldLocation,
+    base_addr: VromAddr,
+    text: &str,
+) -> Result<(), WriteError> {
+    match *location {
+        StructFieldLocation::Simple { offset } => write_value(vrom, desc, base_addr + offset, text),
+        StructFieldLocation::Slice { .. } | StructFieldLocation::InferredSlice { .. } => {
+            Err(WriteError::NotEditable(format!("{}[]*", desc.name())))
+        }
+        StructFieldLocation::InlineDelimitedList { .. } => {
+            Err(WriteError::NotEditable(format!("{}[..]", desc.name())))
+        }
+        StructFieldLocation::InlineSlice { .. } => {
+            Err(WriteError::NotEditable(format!("{}[]", desc.name())))
+        }
+    }
+}
+
+/// Parses `text` and writes it into a struct field of the struct at `base_addr`.
+pub fn write_field(
+    vrom: VromMut<'_>,
+    field: &FieldDescriptor,
+    base_addr: VromAddr,
+    text: &str,
+) -> Result<(), WriteError> {
+    write_location(vrom, field.desc, &field.location, base_addr, text)
+}
+
+impl QueryTarget {
+    /// Parses `text` and writes it to the target.
+    pub fn write(&self, vrom: VromMut<'_>, text: &str) -> Result<(), WriteError> {
+        write_location(vrom, self.desc, &self.location, self.base_addr, text)
+    }
+}
+
+/// Whether a value of the described type at `location` can be written with [`write_location`].
+pub fn is_editable(desc: TypeDescriptor, location: &StructFieldLocation) -> bool {
+    matches!(location, StructFieldLocation::Simple { .. })
+        && !matches!(desc, TypeDescriptor::Struct(_) | TypeDescriptor::Union(_))
+}
+
+/// Parses a primitive value into its big-endian representation.
+///
+/// Floats and fixed-point numbers are written in natural units, and vectors as three
+/// comma-separated components, optionally in parentheses.
+fn parse_bytes(primitive: PrimitiveType, text: &str) -> Option<Vec<u8>> {
+    Some(match primitive {
+        PrimitiveType::U64 => {
+            let value = match text.strip_prefix("0x") {
+                Some(hex) => u64::from_str_radix(hex, 16).ok()?,
+                None => text.parse::<u64>().ok()?,
+            };
+            value.to_be_bytes().to_vec()
+        }
+        PrimitiveType::F32 => text.parse::<f32>().ok()?.to_be_bytes().to_vec(),
+        PrimitiveType::Qu10_2 => parse_fixed_point(text, Qu10_2::SCALE, value_mask(primitive)?)?,
+        PrimitiveType::Qu1_11 => parse_fixed_point(text, Qu1_11::SCALE, value_mask(primitive)?)?,
+        PrimitiveType::Qu0_16 => parse_fixed_point(text, Qu0_16::SCALE, 0xffff)?,
+        PrimitiveType::Vec3s => parse_components(text, |x| {
+            Some((parse_primitive(PrimitiveType::I16, x)? as u16).to_be_bytes())
+        })?,
+        PrimitiveType::Vec3f => {
+            parse_components(text, |x| Some(x.parse::<f32>().ok()?.to_be_bytes()))?
+        }
+        _ => int_bytes(primitive, parse_primitive(primitive, text)?),
+    })
+}
+
+/// The bits of a 16-bit primitive that hold its value, for primitives that share their bits with
+/// other fields. Reads ignore the other bits, so writes leave them alone.
+fn value_mask(primitive: PrimitiveType) -> Option<u16> {
+    match primitive {
+        PrimitiveType::Qu10_2 | PrimitiveType::Qu1_11 => Some(0x0fff),
+        _ => None,
+    }
+}
+
+/// Converts an integer's bits to its big-endian representation.
+fn int_bytes(primitive: PrimitiveType, value: u32) -> Vec<u8> {
+    value.to_be_bytes()[(4 - primitive.size() as usize)..].to_vec()
+}
+
+fn parse_fixed_point(text: &str, scale: f64, max: u16) -> Option<Vec<u8>> {
+    let steps = (text.parse::<f64>().ok()? * scale).round();
+    if !(0.0..=max.into()).contains(&steps) {
+        return None;
+    }
+    Some((steps as u16).to_be_bytes().to_vec())
+}
+
+fn parse_components<const N: usize>(
+    text: &str,
+    parse: impl Fn(&str) -> Option<[u8; N]>,
+) -> Option<Vec<u8>> {
+    let text = text.trim_start_matches('(').trim_end_matches(')');
+    let components: Vec<&str> = text.split(',').map(str::trim).collect();
+    if components.len() != 3 {
+        return None;
+    }
+    let mut bytes = vec![];
+    for component in components {
+        bytes.extend_from_slice(&parse(component)?);
+    }
+    Some(bytes)
+}
+
+/// Parses an integer primitive value, returning its bits zero-extended to 32 bits.
+fn parse_primitive(primitive: PrimitiveType, text: &str) -> Option<u32> {
+    if !primitive.is_integer() || primitive.size() > 4 {
+        return None;
+    }
+    let (min, max): (i64, i64) = match primitive {
+        PrimitiveType::Bool => match text {
+            "true" => return Some(1),
+            "false" => return Some(0),
+            _ => (0, 1),
+        },
+        PrimitiveType::U8 => (0, u8::MAX.into()),
+        PrimitiveType::I8 => (i8::MIN.into(), i8::MAX.into()),
+        PrimitiveType::U16 => (0, u16::MAX.into()),
+        PrimitiveType::I16 => (i16::MIN.into(), i16::MAX.into()),
+        PrimitiveType::U32 | PrimitiveType::VromAddr | PrimitiveType::SegmentAddr => {
+            (0, u32::MAX.into())
+        }
+        PrimitiveType::I32 => (i32::MIN.into(), i32::MAX.into()),
+        _ => unreachable!(),
+    };
+    let value = parse_int(text)?;
+    if value < min || value > max {
+        return None;
+    }
+    let mask = match primitive.size() {
+        1 => 0xff,
+        2 => 0xffff,
+        _ => 0xffff_ffff,
+    };
+    Some((value as u32) & mask)
+}
+
+fn parse_int(text: &str) -> Option<i64> {
+    let (negative, text) = match text.strip_prefix('-') {
+        Some(text) => (true, text),
+        None => (false, text),
+    };
+    let magnitude = match text.strip_prefix("0x") {
+        Some(hex) => i64::from_str_radix(hex, 16).ok()?,
+        None => text.parse::<i64>().ok()?,
+    };
+    Some(if negative { -magnitude } else { magnitude })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{I16_DESC, U8_DESC};
+
+    #[test]
+    fn parses_primitives() {
+        assert_eq!(parse_primitive(PrimitiveType::U8, "0xff"), Some(0xff));
+        assert_eq!(parse_primitive(PrimitiveType::U8, "256"), None);
+        assert_eq!(parse_primitive(PrimitiveType::I16, "-1"), Some(0xffff));
+        assert_eq!(parse_primitive(PrimitiveType::I16, "-0x8001"), None);
+        assert_eq!(parse_primitive(PrimitiveType::Bool, "true"), Some(1));
+        assert_eq!(parse_primitive(PrimitiveType::U32, "seven"), None);
+    }
+
+    #[test]
+    fn writes_big_endian() {
+        let mut data = [0u8; 4];
+        write_value(VromMut(&mut data), I16_DESC, VromAddr(1), "-2").unwrap();
+        assert_eq!(data, [0x00, 0xff, 0xfe, 0x00]);
+        write_value(VromMut(&mut data), U8_DESC, VromAddr(3), "0x12").unwrap();
+        assert_eq!(data, [0x00, 0xff, 0xfe, 0x12]);
+        assert!(write_value(VromMut(&mut data), U8_DESC, VromAddr(4), "0").is_err());
+    }
+
+    #[test]
+    fn preserves_bits_outside_fixed_point_values() {
+        let mut data = [0xf1, 0x23];
+        let desc = TypeDescriptor::Primitive(PrimitiveType::Qu10_2);
+        write_value(VromMut(&mut data), desc, VromAddr(0), "2.25").unwrap();
+        assert_eq!(data, [0xf0, 0x09]);
+        let desc = TypeDescriptor::Primitive(PrimitiveType::Qu1_11);
+        write_value(VromMut(&mut data), desc, VromAddr(0), "1.5").unwrap();
+        assert_eq!(data, [0xfc, 0x00]);
+        assert!(write_value(VromMut(&mut data), desc, VromAddr(0), "2").is_err());
+        assert_eq!(data, [0xfc, 0x00]);
+    }
+
+    #[test]
+    fn parses_natural_units() {
+        assert_eq!(
+            parse_bytes(PrimitiveType::F32, "1.5"),
+            Some(vec![0x3f, 0xc0, 0x00, 0x00])
+        );
+        assert_eq!(
+            parse_bytes(PrimitiveType::Qu10_2, "2.25"),
+            Some(vec![0x00, 0x09])
+        );
+        assert_eq!(parse_bytes(PrimitiveType::Qu10_2, "1024"), None);
+        assert_eq!(parse_bytes(PrimitiveType::Vec3s, "1, 2"), None);
+        assert_eq!(
+            parse_bytes(PrimitiveType::Vec3s, "(1, -1, 0x2)"),
+            Some(vec![0x00, 0x01, 0xff, 0xff, 0x00, 0x02])
+        );
+    }
+}
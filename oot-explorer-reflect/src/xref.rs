@@ -0,0 +1,274 @@
+use oot_explorer_segment::SegmentTable;
+use oot_explorer_vrom::{Vrom, VromAddr};
+use std::ops::Range;
+
+use crate::walk::{walk, Visitor};
+use crate::{Query, QueryStep, QueryTarget, TypeDescriptor};
+
+/// A pointer or slice field that refers to a range of VROM.
+#[derive(Clone)]
+pub struct Reference {
+    /// A query that resolves to the pointer or slice field.
+    pub query: Query,
+    /// The name of the type that contains the field.
+    pub owner: &'static str,
+    /// The address of the field's segment address.
+    pub addr: VromAddr,
+    /// The type that the field refers to.
+    pub target_desc: TypeDescriptor,
+    /// The range the field refers to. Slices cover all of their elements. The range is empty when
+    /// the target type has no fixed size, and then only its start is considered referenced.
+    pub target: Range<VromAddr>,
+}
+
+impl Reference {
+    /// Whether the reference's target overlaps `range`.
+    pub fn overlaps(&self, range: &Range<VromAddr>) -> bool {
+        if self.target.start == self.target.end {
+            range.contains(&self.target.start)
+        } else {
+            self.target.start < range.end && range.start < self.target.end
+        }
+    }
+}
+
+/// An index of every pointer and slice reachable from some set of roots, by target address.
+///
+/// Display list commands are covered through their descriptors, so a `Vtx` command refers to the
+/// vertices it loads and a `SetTimg` command refers to the start of the texture it sets.
+#[derive(Clone, Default)]
+pub struct XrefIndex {
+    /// Sorted by target start.
+    references: Vec<Reference>,
+    /// The length of the longest target range.
+    max_len: u32,
+}
+
+impl XrefIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds every reference reachable from `root`, whose query is `root_query`.
+    pub fn add(
+        &mut self,
+        vrom: Vrom<'_>,
+        segment_table: &SegmentTable,
+        root_query: &Query,
+        root: &QueryTarget,
+    ) {
+        let mut collector = Collector { references: vec![] };
+        walk(vrom, segment_table, &root_query.steps, root, &mut collector);
+        let mut added = collector.references;
+        for reference in &added {
+            self.max_len = self
+                .max_len
+                .max(reference.target.end - reference.target.start);
+        }
+        added.sort_by_key(|reference| reference.target.start);
+
+        // Merge the new references into the sorted ones rather than sorting everything again.
+        let existing = std::mem::take(&mut self.references);
+        self.references.reserve(existing.len() + added.len());
+        let mut added = added.into_iter().peekable();
+        for reference in existing {
+            while let Some(next) = added.next_if(|next| next.target.start < reference.target.start)
+            {
+                self.references.push(next);
+            }
+            self.references.push(reference);
+        }
+        self.references.extend(added);
+    }
+
+    /// Lists the references whose target contains `addr`.
+    pub fn references_to(&self, addr: VromAddr) -> Vec<&Reference> {
+        self.references_in(addr..addr + 1)
+    }
+
+    /// Lists the references whose target overlaps `range`.
+    pub fn references_in(&self, range: Range<VromAddr>) -> Vec<&Reference> {
+        let first = self.references.partition_point(|reference| {
+            reference.target.start.0.saturating_add(self.max_len) < range.start.0
+        });
+        self.references[first..]
+            .iter()
+            .take_while(|reference| reference.target.start < range.end)
+            .filter(|reference| reference.overlaps(&range))
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.references.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.references.is_empty()
+    }
+
+    /// Iterates over all references in order of target address.
+    pub fn iter(&self) -> impl Iterator<Item = &Reference> {
+        self.references.iter()
+    }
+}
+
+struct Collector {
+    references: Vec<Reference>,
+}
+
+impl Visitor for Collector {
+    fn value(&mut self, _steps: &[QueryStep], _desc: TypeDescriptor, _addr: VromAddr) {}
+
+    fn reference(
+        &mut self,
+        steps: &[QueryStep],
+        owner: &'static str,
+        addr: VromAddr,
+        target_desc: TypeDescriptor,
+        target_addr: VromAddr,
+        count: Option<u32>,
+    ) {
+        let len = target_desc.size().unwrap_or(0) * count.unwrap_or(1);
+        self.references.push(Reference {
+            query: Query {
+                steps: steps.to_vec(),
+            },
+            owner,
+            addr,
+            target_desc,
+            target: target_addr..target_addr + len,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        FieldDescriptor, PointerDescriptor, PrimitiveType, StructDescriptor, StructFieldLocation,
+        U16_DESC,
+    };
+    use oot_explorer_segment::Segment;
+
+    const POINT: StructDescriptor = StructDescriptor {
+        name: "Point",
+        size: Some(4),
+        is_end: None,
+        fields: &[
+            FieldDescriptor {
+                name: "x",
+                location: StructFieldLocation::Simple { offset: 0 },
+                desc: U16_DESC,
+            },
+            FieldDescriptor {
+                name: "y",
+                location: StructFieldLocation::Simple { offset: 2 },
+                desc: U16_DESC,
+            },
+        ],
+    };
+
+    const POINT_PTR: PointerDescriptor = PointerDescriptor {
+        name: "Point*",
+        target: || TypeDescriptor::Struct(&POINT),
+        nullable: true,
+    };
+
+    const SHAPE: StructDescriptor = StructDescriptor {
+        name: "Shape",
+        size: Some(0xc),
+        is_end: None,
+        fields: &[
+            FieldDescriptor {
+                name: "points",
+                location: StructFieldLocation::Slice {
+                    count_offset: 0,
+                    count_desc: PrimitiveType::U8,
+                    count_span: None,
+                    ptr_offset: 4,
+                },
+                desc: TypeDescriptor::Struct(&POINT),
+            },
+            FieldDescriptor {
+                name: "origin",
+                location: StructFieldLocation::Simple { offset: 8 },
+                desc: TypeDescriptor::Pointer(&POINT_PTR),
+            },
+        ],
+    };
+
+    fn index() -> XrefIndex {
+        let data = [
+            0x02, 0x00, 0x00, 0x00, // count
+            0x02, 0x00, 0x00, 0x10, // points
+            0x02, 0x00, 0x00, 0x14, // origin
+            0x00, 0x00, 0x00, 0x00, // padding
+            0x00, 0x01, 0x00, 0x02, // points[0]
+            0x00, 0x03, 0x00, 0x04, // points[1]
+        ];
+        let segment_table = SegmentTable::new().with(Segment::SCENE, VromAddr(0));
+        let root_query = Query::parse("shape").unwrap();
+        let mut index = XrefIndex::new();
+        index.add(
+            Vrom(&data),
+            &segment_table,
+            &root_query,
+            &QueryTarget::root(TypeDescriptor::Struct(&SHAPE), VromAddr(0)),
+        );
+        index
+    }
+
+    fn queries(references: Vec<&Reference>) -> Vec<String> {
+        references
+            .into_iter()
+            .map(|reference| reference.query.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn finds_slices_and_pointers() {
+        let index = index();
+        assert_eq!(index.len(), 2);
+        assert_eq!(
+            queries(index.references_to(VromAddr(0x10))),
+            vec!["shape.points"]
+        );
+        assert_eq!(
+            queries(index.references_to(VromAddr(0x15))),
+            vec!["shape.points", "shape.origin"],
+        );
+        assert!(index.references_to(VromAddr(0x18)).is_empty());
+    }
+
+    #[test]
+    fn merges_later_roots_in_order() {
+        // Another shape whose one point lies between the first shape's targets.
+        let mut data = [0; 0x2c];
+        data[0x20] = 1;
+        data[0x24..0x28].copy_from_slice(&[0x02, 0x00, 0x00, 0x12]);
+        let segment_table = SegmentTable::new().with(Segment::SCENE, VromAddr(0));
+        let mut index = index();
+        index.add(
+            Vrom(&data),
+            &segment_table,
+            &Query::parse("other").unwrap(),
+            &QueryTarget::root(TypeDescriptor::Struct(&SHAPE), VromAddr(0x20)),
+        );
+        assert_eq!(
+            queries(index.iter().collect()),
+            vec!["shape.points", "other.points", "shape.origin"],
+        );
+    }
+
+    #[test]
+    fn finds_overlapping_ranges() {
+        let index = index();
+        assert_eq!(
+            queries(index.references_in(VromAddr(0x16)..VromAddr(0x20))),
+            vec!["shape.points", "shape.origin"],
+        );
+        let origin = &index.references_to(VromAddr(0x14))[1];
+        assert_eq!(origin.owner, "Shape");
+        assert_eq!(origin.addr, VromAddr(8));
+    }
+}
@@ -0,0 +1,30 @@
+use std::fmt::{self, Debug, Formatter};
+use std::ops::Range;
+
+use crate::{Vrom, VromAddr, VromError};
+
+/// A mutable slice representing all of VROM, for editing game data in place.
+pub struct VromMut<'a>(pub &'a mut [u8]);
+
+impl<'a> VromMut<'a> {
+    pub fn slice_mut(self, range: Range<VromAddr>) -> Result<&'a mut [u8], VromError> {
+        let vrom_size = self.0.len() as u32;
+        self.0
+            .get_mut(range.start.0 as usize..range.end.0 as usize)
+            .ok_or(VromError::OutOfRange {
+                from: Some(range.start),
+                to: Some(range.end),
+                vrom_size,
+            })
+    }
+
+    pub fn as_vrom(&self) -> Vrom<'_> {
+        Vrom(self.0)
+    }
+}
+
+impl<'a> Debug for VromMut<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "VromMut(_)")
+    }
+}
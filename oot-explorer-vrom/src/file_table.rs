@@ -13,6 +13,11 @@ pub struct FileTable {
 }
 
 impl FileTable {
+    /// Builds a file table from the VROM range of each file, in file index order.
+    pub fn new(file_ranges: Vec<Range<VromAddr>>) -> Self {
+        Self { file_ranges }
+    }
+
     pub fn file_vrom_range(&self, index: FileIndex) -> Result<Range<VromAddr>, GetFileError> {
         Ok(self
             .file_ranges
@@ -23,6 +28,14 @@ impl FileTable {
             })?
             .clone())
     }
+
+    /// Finds the file whose VROM range contains `addr`.
+    pub fn find_file(&self, addr: VromAddr) -> Option<FileIndex> {
+        self.file_ranges
+            .iter()
+            .position(|range| range.contains(&addr))
+            .map(|index| FileIndex(index as u32))
+    }
 }
 
 #[derive(Debug, Error)]
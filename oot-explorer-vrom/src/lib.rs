@@ -6,6 +6,7 @@ use crate::file_system_table_entry::FileSystemTableEntry;
 
 mod addr;
 mod borrowed;
+mod borrowed_mut;
 mod error;
 mod file_system_table_entry;
 mod file_table;
@@ -14,6 +15,7 @@ pub mod yaz;
 
 pub use addr::VromAddr;
 pub use borrowed::Vrom;
+pub use borrowed_mut::VromMut;
 pub use error::VromError;
 pub use file_table::{FileIndex, FileTable, GetFileError};
 pub use owned::OwnedVrom;
@@ -1,4 +1,4 @@
-use crate::Vrom;
+use crate::{Vrom, VromMut};
 
 /// A boxed slice representing all of VROM.
 ///
@@ -15,4 +15,8 @@ impl OwnedVrom {
     pub fn borrow(&self) -> Vrom<'_> {
         Vrom(&self.vrom)
     }
+
+    pub fn borrow_mut(&mut self) -> VromMut<'_> {
+        VromMut(&mut self.vrom)
+    }
 }
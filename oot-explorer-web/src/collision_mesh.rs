@@ -0,0 +1,106 @@
+//! Vertex data for drawing a scene's collision as an overlay.
+//!
+//! Each vertex is 16 bytes: a position as three little-endian f32s, then an RGBA color.
+
+use oot_explorer_game_data::collision::{
+    Collision, CollisionModel, ModelTriangle, TriangleKind, TriangleType, WallType,
+};
+use oot_explorer_read::{ReadError, Slice};
+use oot_explorer_segment::SegmentTable;
+use oot_explorer_vrom::Vrom;
+
+const EXIT_COLOR: [u8; 4] = [0xff, 0x00, 0xff, 0xc0];
+const FLOOR_PROPERTY_COLOR: [u8; 4] = [0xff, 0x40, 0x40, 0xc0];
+const FLOOR_TYPE_COLOR: [u8; 4] = [0xff, 0xa0, 0x00, 0xa0];
+const WALL_TYPE_COLOR: [u8; 4] = [0xff, 0xff, 0x40, 0xa0];
+const HOOKSHOTABLE_COLOR: [u8; 4] = [0x40, 0xff, 0x40, 0xa0];
+const FLOOR_COLOR: [u8; 4] = [0x60, 0x80, 0xff, 0x80];
+const WALL_COLOR: [u8; 4] = [0xc0, 0xc0, 0xc0, 0x80];
+const CEILING_COLOR: [u8; 4] = [0x80, 0x80, 0x80, 0x80];
+const WATER_BOX_COLOR: [u8; 4] = [0x20, 0x60, 0xff, 0x80];
+
+pub struct CollisionMesh {
+    pub triangle_vertex_data: Vec<u8>,
+    pub water_box_vertex_data: Vec<u8>,
+}
+
+impl CollisionMesh {
+    /// Builds vertex data for the triangles of `collision`, colored by their surface type, and for
+    /// the surfaces of its water boxes.
+    pub fn new(
+        vrom: Vrom<'_>,
+        segment_table: &SegmentTable,
+        collision: Collision,
+    ) -> Result<Self, ReadError> {
+        let model = CollisionModel::new(vrom, segment_table, collision)?;
+        let triangle_types = collision.triangle_types_auto_len(vrom, segment_table)?;
+
+        let mut triangle_vertex_data = vec![];
+        for triangle in model.triangles() {
+            let color = triangle_color(vrom, triangle_types, triangle)?;
+            for &vertex in &triangle.vertices {
+                push_vertex(&mut triangle_vertex_data, vertex, color);
+            }
+        }
+
+        let mut water_box_vertex_data = vec![];
+        for water_box in model.water_boxes() {
+            let y = water_box.y_surface;
+            let corners = [
+                [water_box.x_min, y, water_box.z_min],
+                [water_box.x_min, y, water_box.z_max],
+                [water_box.x_max, y, water_box.z_max],
+                [water_box.x_max, y, water_box.z_min],
+            ];
+            for &i in &[0, 1, 2, 0, 2, 3] {
+                push_vertex(&mut water_box_vertex_data, corners[i], WATER_BOX_COLOR);
+            }
+        }
+
+        Ok(CollisionMesh {
+            triangle_vertex_data,
+            water_box_vertex_data,
+        })
+    }
+}
+
+/// Picks a color for the most notable property of a triangle's surface type, falling back to
+/// whether the triangle is a floor, wall, or ceiling.
+fn triangle_color(
+    vrom: Vrom<'_>,
+    triangle_types: Slice<TriangleType>,
+    triangle: &ModelTriangle,
+) -> Result<[u8; 4], ReadError> {
+    if u32::from(triangle.type_index) < triangle_types.len() {
+        let triangle_type = triangle_types.get(vrom, u32::from(triangle.type_index))?;
+        let high = triangle_type.high_value(vrom);
+        let low = triangle_type.low_value(vrom);
+        if high.exit_index() != 0 {
+            return Ok(EXIT_COLOR);
+        }
+        if high.floor_property() != 0 {
+            return Ok(FLOOR_PROPERTY_COLOR);
+        }
+        if high.floor_type() != 0 {
+            return Ok(FLOOR_TYPE_COLOR);
+        }
+        if high.wall_type() != WallType::NONE {
+            return Ok(WALL_TYPE_COLOR);
+        }
+        if low.hookshotable() {
+            return Ok(HOOKSHOTABLE_COLOR);
+        }
+    }
+    Ok(match triangle.kind() {
+        TriangleKind::Floor => FLOOR_COLOR,
+        TriangleKind::Wall => WALL_COLOR,
+        TriangleKind::Ceiling => CEILING_COLOR,
+    })
+}
+
+fn push_vertex(data: &mut Vec<u8>, pos: [f32; 3], color: [u8; 4]) {
+    for x in &pos {
+        data.extend_from_slice(&x.to_le_bytes());
+    }
+    data.extend_from_slice(&color);
+}
@@ -1,18 +1,14 @@
-#![cfg_attr(feature = "trace_macros", feature(trace_macros))]
-
-#[cfg(feature = "trace_macros")]
-trace_macros!(true);
-
+use oot_explorer_game_data::cutscene::Cutscene;
 use oot_explorer_game_data::header_room::RoomHeaderVariant;
-use oot_explorer_game_data::header_scene::SceneHeaderVariant;
+use oot_explorer_game_data::header_scene::{Path, SceneHeaderVariant};
 use oot_explorer_game_data::mesh::{
-    Background, ClippedMeshEntry, JfifMeshVariant, MeshEntry, MeshVariant, SimpleMeshEntry,
+    Background, ClippedMeshEntry, JfifMeshVariant, MeshVariant, SimpleMeshEntry,
 };
 use oot_explorer_game_data::room::Room;
 use oot_explorer_game_data::scene::Scene;
 use oot_explorer_game_data::versions::oot_ntsc_10;
 use oot_explorer_gl::display_list_interpreter::{DisplayListInterpreter, DisplayListOpacity};
-use oot_explorer_read::VromProxy;
+use oot_explorer_read::{ReadError, VromProxy};
 use oot_explorer_rom::OwnedRom;
 use oot_explorer_segment::{Segment, SegmentTable};
 use oot_explorer_vrom::{decompress, FileIndex, FileTable, OwnedVrom, Vrom};
@@ -24,12 +20,15 @@ use web_sys::{WebGl2RenderingContext, WebGlSampler, WebGlTexture};
 #[macro_use]
 mod macros;
 
+mod collision_mesh;
 mod hexdump;
 mod reflect_root;
 mod reflect_value;
 mod sampler_cache;
 mod texture_cache;
+mod xref;
 
+use collision_mesh::CollisionMesh;
 use sampler_cache::SamplerCache;
 use texture_cache::TextureCache;
 
@@ -57,6 +56,18 @@ struct ProcessSceneResult<'a> {
     batches: Vec<ProcessSceneBatch<'a>>,
     backgrounds: Vec<String>,
     start_pos: Option<[f64; 5]>,
+    paths: Vec<Vec<[i16; 3]>>,
+    collision: Option<ProcessSceneCollision>,
+}
+
+/// Vertex data for the scene's collision, in the format described in [`collision_mesh`].
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ProcessSceneCollision {
+    #[serde(with = "serde_bytes")]
+    triangle_vertex_data: Vec<u8>,
+    #[serde(with = "serde_bytes")]
+    water_box_vertex_data: Vec<u8>,
 }
 
 #[derive(Serialize)]
@@ -80,6 +91,19 @@ struct ProcessSceneTexture {
     height: u32,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CutsceneCameraResult {
+    eye: [f32; 3],
+    at: [f32; 3],
+    /// In degrees, from the focus spline.
+    roll: f32,
+    /// In degrees, from the focus spline.
+    fovy: f32,
+    eye_relative_to_player: bool,
+    at_relative_to_player: bool,
+}
+
 #[wasm_bindgen]
 impl Context {
     #[wasm_bindgen(constructor)]
@@ -107,8 +131,9 @@ impl Context {
         }
     }
 
+    /// Builds render batches for a scene in one of its setups, where setup 0 is the default.
     #[wasm_bindgen(js_name = processScene)]
-    pub fn process_scene(&self, scene_index: u32) -> JsValue {
+    pub fn process_scene(&self, scene_index: u32, setup: u32) -> JsValue {
         let mut inner_mut = self.inner.lock().unwrap_throw();
         let InnerContext {
             ref gl,
@@ -122,8 +147,12 @@ impl Context {
         let vrom = vrom.as_ref().unwrap_throw().borrow();
 
         let mut dlist_interp = DisplayListInterpreter::new();
-        let mut backgrounds = vec![];
-        let start_pos = examine_scene(
+        let SceneContents {
+            start_pos,
+            backgrounds,
+            paths,
+            collision,
+        } = examine_scene(
             file_table,
             vrom,
             oot_ntsc_10::get_scene_table(file_table)
@@ -133,8 +162,8 @@ impl Context {
                 .scene(vrom)
                 .unwrap_throw()
                 .into_inner(),
+            setup,
             &mut dlist_interp,
-            &mut backgrounds,
         );
 
         // TODO: Set up a web-friendly logger of some kind.
@@ -178,10 +207,62 @@ impl Context {
             batches,
             backgrounds,
             start_pos,
+            paths,
+            collision: collision.map(|collision| ProcessSceneCollision {
+                triangle_vertex_data: collision.triangle_vertex_data,
+                water_box_vertex_data: collision.water_box_vertex_data,
+            }),
         })
         .unwrap_throw()
     }
 
+    /// The number of frames in a scene setup's cutscene, or `undefined` if it has none. Throws if
+    /// the scene can't be read.
+    #[wasm_bindgen(js_name = cutsceneFrameCount)]
+    pub fn cutscene_frame_count(
+        &self,
+        scene_index: u32,
+        setup: u32,
+    ) -> Result<Option<u32>, JsValue> {
+        let inner = self.inner.lock().unwrap_throw();
+        let file_table = inner.file_table.as_ref().unwrap_throw();
+        let vrom = inner.vrom.as_ref().unwrap_throw().borrow();
+
+        Ok(find_cutscene(file_table, vrom, scene_index, setup)?
+            .map(|cutscene| cutscene.frame_count(vrom)))
+    }
+
+    /// Evaluates the camera of a scene setup's cutscene at a frame, or returns `null` if the
+    /// cutscene hasn't positioned the camera by then. Throws if the scene or cutscene can't be
+    /// read.
+    #[wasm_bindgen(js_name = cutsceneCamera)]
+    pub fn cutscene_camera(
+        &self,
+        scene_index: u32,
+        setup: u32,
+        frame: u32,
+    ) -> Result<JsValue, JsValue> {
+        let inner = self.inner.lock().unwrap_throw();
+        let file_table = inner.file_table.as_ref().unwrap_throw();
+        let vrom = inner.vrom.as_ref().unwrap_throw().borrow();
+
+        let camera = match find_cutscene(file_table, vrom, scene_index, setup)? {
+            Some(cutscene) => cutscene.camera_at(vrom, frame).map_err(js_error)?,
+            None => None,
+        };
+        Ok(match camera {
+            Some(camera) => serde_wasm_bindgen::to_value(&CutsceneCameraResult {
+                eye: camera.eye.pos,
+                at: camera.at.pos,
+                roll: camera.at.roll,
+                fovy: camera.at.view_angle,
+                eye_relative_to_player: camera.eye.relative_to_player,
+                at_relative_to_player: camera.at.relative_to_player,
+            })?,
+            None => JsValue::NULL,
+        })
+    }
+
     #[wasm_bindgen(getter = sceneCount)]
     pub fn scene_count(&self) -> u32 {
         oot_ntsc_10::SCENE_TABLE_COUNT as u32
@@ -232,20 +313,65 @@ impl Context {
     }
 }
 
+fn find_cutscene(
+    file_table: &FileTable,
+    vrom: Vrom<'_>,
+    scene_index: u32,
+    setup: u32,
+) -> Result<Option<Cutscene>, JsValue> {
+    let scene = oot_ntsc_10::get_scene_table(file_table)
+        .map_err(js_error)?
+        .get(vrom, scene_index)
+        .and_then(|scene_table_entry| scene_table_entry.scene(vrom))
+        .map_err(js_error)?
+        .into_inner();
+    let segment_table = SegmentTable::new().with(Segment::SCENE, scene.addr());
+    let headers = scene
+        .setup(vrom, &segment_table, setup)
+        .map_err(js_error)?
+        .headers(vrom);
+    for header in headers {
+        if let SceneHeaderVariant::Cutscene(header) = header.map_err(js_error)?.variant(vrom) {
+            return header
+                .cutscene(vrom, &segment_table)
+                .map(Some)
+                .map_err(js_error);
+        }
+    }
+    Ok(None)
+}
+
+fn js_error(e: impl std::fmt::Display) -> JsValue {
+    JsValue::from_str(&e.to_string())
+}
+
+/// What a scene setup shows besides the display lists of its rooms.
+#[derive(Default)]
+struct SceneContents {
+    start_pos: Option<[f64; 5]>,
+    backgrounds: Vec<String>,
+    paths: Vec<Vec<[i16; 3]>>,
+    collision: Option<CollisionMesh>,
+}
+
 fn examine_scene(
     file_table: &FileTable,
     vrom: Vrom<'_>,
     scene: Scene,
+    setup: u32,
     dlist_interp: &mut DisplayListInterpreter,
-    backgrounds: &mut Vec<String>,
-) -> Option<[f64; 5]> {
+) -> SceneContents {
     let segment_table = SegmentTable::new().with(Segment::SCENE, scene.addr());
-    let mut start_pos = None;
-    for result in scene.headers(vrom) {
+    let mut contents = SceneContents::default();
+    for result in scene
+        .setup(vrom, &segment_table, setup)
+        .unwrap_throw()
+        .headers(vrom)
+    {
         let header = result.unwrap_throw();
         match header.variant(vrom) {
             SceneHeaderVariant::StartPositions(header) => {
-                start_pos = header
+                contents.start_pos = header
                     .start_positions(vrom, &segment_table)
                     .unwrap_throw()
                     .iter(vrom)
@@ -276,15 +402,47 @@ fn examine_scene(
                             .room(vrom)
                             .unwrap_throw()
                             .into_inner(),
+                        setup,
                         dlist_interp,
-                        backgrounds,
+                        &mut contents.backgrounds,
                     );
                 }
             }
+            SceneHeaderVariant::Collision(header) => {
+                // The collision is only an overlay, so leave it out if any of it doesn't read.
+                contents.collision = header
+                    .ptr(vrom, &segment_table)
+                    .ok()
+                    .and_then(|collision| CollisionMesh::new(vrom, &segment_table, collision).ok());
+            }
+            SceneHeaderVariant::Pathways(header) => {
+                // The path count is inferred, so skip anything that doesn't read as a path.
+                let path_list = header.paths_auto_len(vrom, &segment_table).ok();
+                for path in path_list.into_iter().flat_map(|paths| paths.iter(vrom)) {
+                    if let Ok(points) = path_points(vrom, &segment_table, path) {
+                        contents.paths.push(points);
+                    }
+                }
+            }
             _ => (),
         }
     }
-    start_pos
+    contents
+}
+
+fn path_points(
+    vrom: Vrom<'_>,
+    segment_table: &SegmentTable,
+    path: Result<Path, ReadError>,
+) -> Result<Vec<[i16; 3]>, ReadError> {
+    path?
+        .points(vrom, segment_table)?
+        .iter(vrom)
+        .map(|point| {
+            let point = point?;
+            Ok([point.x, point.y, point.z])
+        })
+        .collect()
 }
 
 fn examine_room(
@@ -292,12 +450,14 @@ fn examine_room(
     vrom: Vrom<'_>,
     scene: Scene,
     room: Room,
+    setup: u32,
     dlist_interp: &mut DisplayListInterpreter,
     backgrounds: &mut Vec<String>,
 ) {
     let cpu_ctx = SegmentTable::new()
         .with(Segment::SCENE, scene.addr())
         .with(Segment::ROOM, room.addr());
+    let room = room.setup(vrom, &cpu_ctx, setup).unwrap_throw();
     let rsp_ctx = {
         let ctx = cpu_ctx.clone();
 
@@ -3,12 +3,12 @@ use oot_explorer_game_data::room::ROOM_DESC;
 use oot_explorer_game_data::scene::SCENE_DESC;
 use oot_explorer_game_data::versions;
 use oot_explorer_read::VromProxy;
-use oot_explorer_reflect::{StructFieldLocation, TypeDescriptor};
+use oot_explorer_reflect::{Query, StructFieldLocation, TypeDescriptor};
 use oot_explorer_segment::{Segment, SegmentTable};
 use oot_explorer_vrom::VromAddr;
 use std::ops::Range;
 use wasm_bindgen::prelude::wasm_bindgen;
-use wasm_bindgen::UnwrapThrowExt;
+use wasm_bindgen::{JsValue, UnwrapThrowExt};
 
 use crate::reflect_value::{reflect_field, ReflectResult};
 use crate::Context;
@@ -22,6 +22,12 @@ pub struct ReflectRoot {
     #[wasm_bindgen(skip)]
     pub segment_table: SegmentTable,
 
+    #[wasm_bindgen(skip)]
+    pub base_addr: VromAddr,
+
+    #[wasm_bindgen(skip)]
+    pub location: StructFieldLocation,
+
     #[wasm_bindgen(skip)]
     pub desc: TypeDescriptor,
 
@@ -47,6 +53,8 @@ impl ReflectRoot {
         let segment_table = SegmentTable::new().with(Segment::SCENE, vrom_range.start);
 
         Self {
+            base_addr: vrom_range.start,
+            location: StructFieldLocation::Simple { offset: 0 },
             vrom_range,
             segment_table,
             desc: SCENE_DESC,
@@ -86,6 +94,8 @@ impl ReflectRoot {
         let segment_table = segment_table.with(Segment::ROOM, vrom_range.start);
 
         Self {
+            base_addr: vrom_range.start,
+            location: StructFieldLocation::Simple { offset: 0 },
             vrom_range,
             segment_table,
             desc: ROOM_DESC,
@@ -93,17 +103,39 @@ impl ReflectRoot {
         }
     }
 
+    /// Creates a root for the value at a query path like `scene[85].room[2].headers[ActorList]`.
+    #[wasm_bindgen(js_name = forQuery)]
+    pub fn for_query(ctx: &Context, query: &str) -> Result<ReflectRoot, JsValue> {
+        let inner = ctx.inner.lock().unwrap_throw();
+        let file_table = inner.file_table.as_ref().unwrap_throw();
+        let vrom = inner.vrom.as_ref().unwrap_throw().borrow();
+
+        let resolved = Query::parse(query)
+            .and_then(|query| versions::oot_ntsc_10::resolve_query(file_table, vrom, &query))
+            .map_err(|e| JsValue::from_str(&format!("{}: {}", query, e)))?;
+
+        Ok(Self {
+            vrom_range: resolved.file_range,
+            segment_table: resolved.segment_table,
+            base_addr: resolved.target.base_addr,
+            location: resolved.target.location,
+            desc: resolved.target.desc,
+            description: query.to_string(),
+        })
+    }
+
     #[wasm_bindgen]
     pub fn reflect(&self, ctx: &Context) -> ReflectResult {
         let ctx_ref = ctx.inner.lock().unwrap_throw();
         let vrom = ctx_ref.vrom.as_ref().unwrap_throw().borrow();
 
         reflect_field(
+            ctx_ref.file_table.as_ref().unwrap_throw(),
             vrom,
             &self.segment_table,
-            self.vrom_range.start,
+            self.base_addr,
             None,
-            &StructFieldLocation::Simple { offset: 0 },
+            &self.location,
             self.desc,
         )
     }
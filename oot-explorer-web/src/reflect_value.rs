@@ -1,7 +1,10 @@
-use oot_explorer_read::{FromVrom, Layout};
-use oot_explorer_reflect::{PrimitiveType, StructFieldLocation, TypeDescriptor};
+use oot_explorer_game_data::versions::oot_ntsc_10;
+use oot_explorer_read::{FromVrom, Layout, Qu0_16, Qu10_2, Qu1_11, Vec3f, Vec3s};
+use oot_explorer_reflect::{
+    is_editable, write_location, PrimitiveType, PseudoItem, StructFieldLocation, TypeDescriptor,
+};
 use oot_explorer_segment::{SegmentAddr, SegmentTable};
-use oot_explorer_vrom::{Vrom, VromAddr};
+use oot_explorer_vrom::{FileTable, Vrom, VromAddr};
 use serde::Serialize;
 use std::fmt::{Debug, Display};
 use std::ops::Range;
@@ -42,6 +45,7 @@ pub struct ReflectItemInfo {
     field_name: Option<String>,
     type_string: String,
     value_string: Option<String>,
+    editable: bool,
     vrom_start: u32,
     vrom_end: u32,
 }
@@ -53,6 +57,20 @@ pub struct ReflectFieldInfo {
     base_addr: VromAddr,
     location: StructFieldLocation,
     desc: TypeDescriptor,
+    /// If present, this item stands in for data that couldn't be interpreted as `desc`.
+    pseudo: Option<PseudoItem>,
+}
+
+impl ReflectFieldInfo {
+    fn pseudo(name: Option<String>, desc: TypeDescriptor, item: PseudoItem) -> Self {
+        ReflectFieldInfo {
+            name,
+            base_addr: item.addr(),
+            location: StructFieldLocation::Simple { offset: 0 },
+            desc,
+            pseudo: Some(item),
+        }
+    }
 }
 
 #[wasm_bindgen]
@@ -61,7 +79,23 @@ impl ReflectFieldInfo {
         let ctx_ref = ctx.inner.lock().unwrap_throw();
         let vrom = ctx_ref.vrom.as_ref().unwrap_throw().borrow();
 
+        if let Some(item) = &self.pseudo {
+            return ReflectResult {
+                info: ReflectItemInfo {
+                    base_addr: self.base_addr.0,
+                    field_name: self.name.clone(),
+                    type_string: self.desc.name().to_string(),
+                    value_string: Some(format!("({})", item)),
+                    editable: false,
+                    vrom_start: item.addr().0,
+                    vrom_end: item.addr().0 + item.size(),
+                },
+                fields: vec![],
+            };
+        }
+
         reflect_field(
+            ctx_ref.file_table.as_ref().unwrap_throw(),
             vrom,
             &root.segment_table,
             self.base_addr,
@@ -70,9 +104,24 @@ impl ReflectFieldInfo {
             self.desc,
         )
     }
+
+    /// Parses `text` as a new value for this field and writes it into VROM.
+    #[wasm_bindgen(js_name = setField)]
+    pub fn set_field(&self, ctx: &Context, text: &str) -> Result<(), JsValue> {
+        if let Some(item) = &self.pseudo {
+            return Err(JsValue::from_str(&format!("can't edit ({})", item)));
+        }
+
+        let mut ctx_mut = ctx.inner.lock().unwrap_throw();
+        let vrom = ctx_mut.vrom.as_mut().unwrap_throw().borrow_mut();
+
+        write_location(vrom, self.desc, &self.location, self.base_addr, text)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
 }
 
 pub fn reflect_field(
+    file_table: &FileTable,
     vrom: Vrom<'_>,
     segment_table: &SegmentTable,
     base_addr: VromAddr,
@@ -84,13 +133,22 @@ pub fn reflect_field(
     let type_string = desc.name().to_string()
         + match location {
             StructFieldLocation::Simple { .. } => "",
-            StructFieldLocation::Slice { .. } => "[]*",
+            StructFieldLocation::Slice { .. } | StructFieldLocation::InferredSlice { .. } => "[]*",
             StructFieldLocation::InlineDelimitedList { .. } => "[..]",
+            StructFieldLocation::InlineSlice { .. } => "[]",
         };
 
-    let vrom_range = get_field_vrom_range(base_addr, &location, desc);
-    let value_string = field_value_string(vrom, segment_table, base_addr, &location, desc);
-    let contents = contents(vrom, segment_table, base_addr, &location, desc);
+    let vrom_range = get_field_vrom_range(vrom, base_addr, location, desc);
+    let value_string =
+        field_value_string(vrom, segment_table, base_addr, location, desc).or_else(|| {
+            match *location {
+                StructFieldLocation::Simple { offset } => {
+                    oot_ntsc_10::summarize(file_table, vrom, desc, base_addr + offset)
+                }
+                _ => None,
+            }
+        });
+    let contents = contents(vrom, segment_table, base_addr, location, desc);
 
     ReflectResult {
         info: ReflectItemInfo {
@@ -98,6 +156,7 @@ pub fn reflect_field(
             field_name,
             type_string,
             value_string,
+            editable: is_editable(desc, location),
             vrom_start: vrom_range.start.0,
             vrom_end: vrom_range.end.0,
         },
@@ -125,16 +184,13 @@ fn contents(
             );
             field_infos
         }
-        StructFieldLocation::Slice {
-            count_offset,
-            count_desc,
-            ptr_offset,
-        } => {
+        StructFieldLocation::Slice { ptr_offset, .. }
+        | StructFieldLocation::InferredSlice { ptr_offset, .. } => {
             // This instance represents a slice field.
 
             // Retrieve the count.
-            let count = count_desc
-                .read_as_u32(vrom, base_addr + *count_offset)
+            let count = location
+                .slice_count(vrom, segment_table, base_addr)
                 .expect("not ready to make this robust yet");
 
             // Retrieve the initial pointer.
@@ -145,11 +201,21 @@ fn contents(
                 return vec![];
             }
 
-            // Resolve the segment address. If it's unmapped, we have no contents. The slice field's
-            // one-line value should display the error message.
+            // Resolve the segment address. If it's unmapped, the only content is a pseudo-item
+            // describing the failure.
             let mut vrom_ptr = match segment_table.resolve(segment_ptr) {
                 Ok(vrom_ptr) => vrom_ptr,
-                Err(_) => return vec![],
+                Err(e) => {
+                    return vec![ReflectFieldInfo::pseudo(
+                        None,
+                        desc,
+                        PseudoItem::UnresolvedPointer {
+                            addr: ptr_addr,
+                            segment_addr: segment_ptr,
+                            message: e.to_string(),
+                        },
+                    )]
+                }
             };
 
             // Add a field for each value in the slice.
@@ -160,6 +226,7 @@ fn contents(
                     base_addr: vrom_ptr,
                     location: StructFieldLocation::Simple { offset: 0 },
                     desc,
+                    pseudo: None,
                 });
 
                 vrom_ptr += match desc.size() {
@@ -169,53 +236,46 @@ fn contents(
             }
             field_infos
         }
-        StructFieldLocation::InlineDelimitedList { offset } => {
-            // This instance represents an inline delimited list field.
-
-            // Retrieve the is_end function.
-            let is_end = match desc.is_end() {
-                Some(is_end) => is_end,
-                None => panic!("delimited list element {} has no is_end", desc.name()),
-            };
-
-            // Add a field for each value in the list.
-            let mut field_infos = vec![];
-            let mut ptr = base_addr + *offset;
-            for index in 0.. {
-                field_infos.push(ReflectFieldInfo {
+        StructFieldLocation::InlineDelimitedList { .. }
+        | StructFieldLocation::InlineSlice { .. } => {
+            // This instance represents an inline list field. Add a field for each value in it.
+            let element_addrs = location
+                .inline_elements(vrom, desc, base_addr)
+                .expect("not ready to make this robust yet")
+                .0;
+            element_addrs
+                .into_iter()
+                .enumerate()
+                .map(|(index, addr)| ReflectFieldInfo {
                     name: Some(format!("{}", index)),
-                    base_addr: ptr,
+                    base_addr: addr,
                     location: StructFieldLocation::Simple { offset: 0 },
                     desc,
-                });
-
-                if is_end(vrom, ptr) {
-                    break;
-                }
-
-                ptr += match desc.size() {
-                    Some(size) => size,
-                    None => panic!("delimited list element {} has no size", desc.name()),
-                };
-            }
-            field_infos
+                    pseudo: None,
+                })
+                .collect()
         }
     }
 }
 
 fn get_field_vrom_range(
+    vrom: Vrom<'_>,
     base_addr: VromAddr,
     location: &StructFieldLocation,
     desc: TypeDescriptor,
 ) -> Range<VromAddr> {
     let (offset, known_size) = match location {
         StructFieldLocation::Simple { offset } => (*offset, None),
-        StructFieldLocation::Slice { ptr_offset, .. } => (*ptr_offset, Some(SegmentAddr::SIZE)),
-        StructFieldLocation::InlineDelimitedList { offset } => (*offset, None),
+        StructFieldLocation::Slice { ptr_offset, .. }
+        | StructFieldLocation::InferredSlice { ptr_offset, .. } => {
+            (*ptr_offset, Some(SegmentAddr::SIZE))
+        }
+        StructFieldLocation::InlineDelimitedList { offset }
+        | StructFieldLocation::InlineSlice { offset, .. } => (*offset, None),
     };
 
     let addr = base_addr + offset;
-    match known_size.or_else(|| desc.size()) {
+    match known_size.or_else(|| desc.size_at(vrom, addr)) {
         Some(size) => addr..addr + size,
         None => addr..addr + 1,
     }
@@ -235,6 +295,7 @@ fn add_field_infos_for_fields(
                 base_addr: addr,
                 location: field.location.clone(),
                 desc: field.desc,
+                pseudo: None,
             }))
         }
 
@@ -242,51 +303,37 @@ fn add_field_infos_for_fields(
             // Add an item for the discriminant.
             field_infos.push(ReflectFieldInfo {
                 name: Some("discriminant".to_string()),
-                base_addr: addr,
-                location: StructFieldLocation::Simple {
-                    offset: union_desc.discriminant_offset,
-                },
+                base_addr: union_desc.discriminant_addr(addr),
+                location: StructFieldLocation::Simple { offset: 0 },
                 desc: union_desc.discriminant_desc,
+                pseudo: None,
             });
 
-            // If the discriminant is accessible and known, recurse to add items for each field in
-            // the variant.
-            let discriminant = match union_desc
-                .discriminant_desc
-                .read_as_u32(vrom, addr + union_desc.discriminant_offset)
-                .expect("union discriminants must be readable as u32")
-            {
-                Ok(discriminant) => discriminant,
-                Err(_) => return,
-            };
-            if let Ok(index) = union_desc
-                .variants
-                .binary_search_by_key(&discriminant, |&(x, _)| x)
-            {
-                let variant_desc = union_desc.variants[index].1;
-                add_field_infos_for_fields(vrom, segment_table, variant_desc, addr, field_infos);
+            // If the discriminant is known, recurse to add items for each field in the variant.
+            // Otherwise, add a pseudo-item explaining why there are no fields.
+            match union_desc.variant_at(vrom, addr) {
+                Ok(variant_desc) => {
+                    add_field_infos_for_fields(vrom, segment_table, variant_desc, addr, field_infos)
+                }
+                Err(item) => field_infos.push(ReflectFieldInfo::pseudo(None, desc, item)),
             }
         }
 
         TypeDescriptor::Pointer(pointer_desc) => {
-            // TODO: Add pseudo-items for failure to dereference a pointer.
-
-            let segment_ptr = match SegmentAddr::from_vrom(vrom, addr) {
-                Ok(segment_ptr) => segment_ptr,
-                Err(_) => return,
-            };
-            let vrom_ptr = match segment_table.resolve(segment_ptr) {
-                Ok(vrom_ptr) => vrom_ptr,
-                Err(_) => return,
-            };
-
-            // Add an item for the pointed-to value.
-            field_infos.push(ReflectFieldInfo {
-                name: None,
-                base_addr: vrom_ptr,
-                location: StructFieldLocation::Simple { offset: 0 },
-                desc: pointer_desc.target,
-            });
+            match pointer_desc.target_at(vrom, segment_table, addr) {
+                // Add an item for the pointed-to value.
+                Ok(Some(vrom_ptr)) => field_infos.push(ReflectFieldInfo {
+                    name: None,
+                    base_addr: vrom_ptr,
+                    location: StructFieldLocation::Simple { offset: 0 },
+                    desc: pointer_desc.target(),
+                    pseudo: None,
+                }),
+                Ok(None) => {}
+                Err(item) => {
+                    field_infos.push(ReflectFieldInfo::pseudo(None, pointer_desc.target(), item))
+                }
+            }
         }
 
         // These types don't have fields.
@@ -296,7 +343,7 @@ fn add_field_infos_for_fields(
 
 fn field_value_string(
     vrom: Vrom<'_>,
-    _segment_table: &SegmentTable,
+    segment_table: &SegmentTable,
     base_addr: VromAddr,
     location: &StructFieldLocation,
     desc: TypeDescriptor,
@@ -319,7 +366,30 @@ fn field_value_string(
                     )))
                 }
 
-                TypeDescriptor::Bitfield(_) => Ok(Some(format!("(bitfields not implemented)"))),
+                TypeDescriptor::Bitfield(bitfield_desc) => {
+                    let value = bitfield_desc
+                        .underlying
+                        .read_as_u32(vrom, field_addr)
+                        .map_err(|_| format!("(inaccessible)"))?;
+                    let spans: Vec<String> = bitfield_desc
+                        .fields
+                        .iter()
+                        .map(|span| {
+                            let span_value = (value >> span.shift) & span.mask;
+                            match span
+                                .desc
+                                .values
+                                .binary_search_by_key(&span_value, |&(x, _)| x)
+                            {
+                                Ok(index) => {
+                                    format!("{}={}", span.desc.name, span.desc.values[index].1)
+                                }
+                                Err(_) => format!("{}={}", span.desc.name, span_value),
+                            }
+                        })
+                        .collect();
+                    Ok(Some(spans.join(" | ")))
+                }
 
                 TypeDescriptor::Primitive(primitive) => match primitive {
                     PrimitiveType::Bool => fetch_and_display::<bool>(vrom, field_addr),
@@ -329,12 +399,22 @@ fn field_value_string(
                     PrimitiveType::I16 => fetch_and_display::<i16>(vrom, field_addr),
                     PrimitiveType::U32 => fetch_and_display::<u32>(vrom, field_addr),
                     PrimitiveType::I32 => fetch_and_display::<i32>(vrom, field_addr),
+                    PrimitiveType::U64 => fetch_and_display::<u64>(vrom, field_addr),
+                    PrimitiveType::F32 => fetch_and_display::<f32>(vrom, field_addr),
+                    PrimitiveType::Qu10_2 => fetch_and_display::<Qu10_2>(vrom, field_addr),
+                    PrimitiveType::Qu1_11 => fetch_and_display::<Qu1_11>(vrom, field_addr),
+                    PrimitiveType::Qu0_16 => fetch_and_display::<Qu0_16>(vrom, field_addr),
+                    PrimitiveType::Vec3s => fetch_and_display::<Vec3s>(vrom, field_addr),
+                    PrimitiveType::Vec3f => fetch_and_display::<Vec3f>(vrom, field_addr),
                     PrimitiveType::VromAddr => fetch_and_debug::<VromAddr>(vrom, field_addr),
                     PrimitiveType::SegmentAddr => fetch_and_debug::<SegmentAddr>(vrom, field_addr),
                 },
 
                 TypeDescriptor::Pointer(pointer_desc) => {
                     match SegmentAddr::from_vrom(vrom, field_addr) {
+                        Ok(segment_ptr) if pointer_desc.nullable && segment_ptr.is_null() => {
+                            Ok(Some(format!("({}) NULL", pointer_desc.name)))
+                        }
                         Ok(segment_ptr) => {
                             Ok(Some(format!("({}) {:?}", pointer_desc.name, segment_ptr)))
                         }
@@ -345,8 +425,8 @@ fn field_value_string(
                 TypeDescriptor::Union(union_desc) => {
                     let discriminant_value = field_value_string(
                         vrom,
-                        _segment_table,
-                        base_addr + union_desc.discriminant_offset,
+                        segment_table,
+                        union_desc.discriminant_addr(base_addr),
                         &StructFieldLocation::Simple { offset: 0 },
                         union_desc.discriminant_desc,
                     )
@@ -360,12 +440,9 @@ fn field_value_string(
             }
         }
 
-        StructFieldLocation::Slice {
-            count_offset,
-            count_desc,
-            ptr_offset,
-        } => {
-            let count = match count_desc.read_as_u32(vrom, base_addr + *count_offset) {
+        StructFieldLocation::Slice { ptr_offset, .. }
+        | StructFieldLocation::InferredSlice { ptr_offset, .. } => {
+            let count = match location.slice_count(vrom, segment_table, base_addr) {
                 Ok(count) => format!("{}", count),
                 Err(_) => format!("(inaccessible)"),
             };
@@ -380,6 +457,13 @@ fn field_value_string(
             // TODO
             Ok(None)
         }
+        StructFieldLocation::InlineSlice { .. } => {
+            let count = match location.slice_count(vrom, segment_table, base_addr) {
+                Ok(count) => format!("{}", count),
+                Err(_) => format!("(inaccessible)"),
+            };
+            Ok(Some(format!("({}[{}])", desc.name(), count)))
+        }
     })();
     match fallible_result {
         Ok(result) => result,
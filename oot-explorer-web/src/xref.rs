@@ -0,0 +1,114 @@
+use oot_explorer_game_data::versions;
+use oot_explorer_reflect::Reference;
+use oot_explorer_vrom::VromAddr;
+use serde::Serialize;
+use wasm_bindgen::prelude::wasm_bindgen;
+use wasm_bindgen::{JsValue, UnwrapThrowExt};
+
+use crate::{js_error, Context};
+
+/// An index of the pointers and slices that refer to each address.
+#[wasm_bindgen]
+pub struct XrefIndex {
+    inner: oot_explorer_reflect::XrefIndex,
+    errors: Vec<XrefError>,
+}
+
+/// A scene that couldn't be fully indexed.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct XrefError {
+    scene_index: u32,
+    message: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct XrefReference {
+    query: String,
+    owner: &'static str,
+    addr: u32,
+    target_type: &'static str,
+    target_start: u32,
+    target_end: u32,
+}
+
+impl From<&Reference> for XrefReference {
+    fn from(reference: &Reference) -> Self {
+        Self {
+            query: reference.query.to_string(),
+            owner: reference.owner,
+            addr: reference.addr.0,
+            target_type: reference.target_desc.name(),
+            target_start: reference.target.start.0,
+            target_end: reference.target.end.0,
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl XrefIndex {
+    #[wasm_bindgen(js_name = forScene)]
+    pub fn for_scene(ctx: &Context, scene_index: u32) -> Result<XrefIndex, JsValue> {
+        let inner = ctx.inner.lock().unwrap_throw();
+        let file_table = inner.file_table.as_ref().unwrap_throw();
+        let vrom = inner.vrom.as_ref().unwrap_throw().borrow();
+
+        versions::oot_ntsc_10::xref_scene(file_table, vrom, scene_index)
+            .map(|inner| Self {
+                inner,
+                errors: vec![],
+            })
+            .map_err(js_error)
+    }
+
+    /// Indexes every scene and room. Scenes that fail to read are listed in `errors` instead of
+    /// throwing.
+    #[wasm_bindgen(js_name = forRom)]
+    pub fn for_rom(ctx: &Context) -> XrefIndex {
+        let inner = ctx.inner.lock().unwrap_throw();
+        let file_table = inner.file_table.as_ref().unwrap_throw();
+        let vrom = inner.vrom.as_ref().unwrap_throw().borrow();
+
+        let results = versions::oot_ntsc_10::xref_all(file_table, vrom);
+        Self {
+            inner: results.index,
+            errors: results
+                .errors
+                .into_iter()
+                .map(|(scene_index, e)| XrefError {
+                    scene_index,
+                    message: e.to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Lists the references whose target contains `addr`.
+    #[wasm_bindgen(js_name = referencesTo)]
+    pub fn references_to(&self, addr: u32) -> JsValue {
+        to_value(self.inner.references_to(VromAddr(addr)))
+    }
+
+    /// Lists the references whose target overlaps `start..end`.
+    #[wasm_bindgen(js_name = referencesIn)]
+    pub fn references_in(&self, start: u32, end: u32) -> JsValue {
+        to_value(self.inner.references_in(VromAddr(start)..VromAddr(end)))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// The scenes that couldn't be fully indexed, as `{ sceneIndex, message }` objects.
+    #[wasm_bindgen(getter)]
+    pub fn errors(&self) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.errors).unwrap_throw()
+    }
+}
+
+fn to_value(references: Vec<&Reference>) -> JsValue {
+    let references: Vec<XrefReference> = references.into_iter().map(Into::into).collect();
+    serde_wasm_bindgen::to_value(&references).unwrap_throw()
+}
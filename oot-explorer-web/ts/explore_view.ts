@@ -1,7 +1,7 @@
 import type * as Wasm from '../pkg';
 
 import { $t } from './dollar_t';
-import { ItemView } from './item_view';
+import { EditCallback, ItemView } from './item_view';
 import { ReflectView } from './reflect_view';
 import { WasmModule } from './wasm';
 import { WMWindow } from './window_manager';
@@ -27,6 +27,8 @@ export class ExploreView extends WMWindow {
     private highlight?: Highlight;
     private refreshMarkingsScheduled: boolean = false;
 
+    public onedit?: EditCallback;
+
     constructor(wasm: WasmModule, ctx: Wasm.Context, root: Wasm.ReflectRoot) {
         // TODO: Don't hard-code the title.
         super({ title: 'Explore ' + root.description, width: 464 });
@@ -55,6 +57,12 @@ export class ExploreView extends WMWindow {
             this.scheduleRefreshMarkings();
             this.reflect.setSelection(item);
         };
+        this.reflect.onedit = () => {
+            this.hexdump.regenerateChildren();
+            if (this.onedit) {
+                this.onedit();
+            }
+        };
     }
 
     private scheduleRefreshMarkings() {
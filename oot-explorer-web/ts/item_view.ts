@@ -8,6 +8,7 @@ export interface ReflectItemInfo {
     fieldName?: string;
     typeString: string;
     valueString?: string;
+    editable: boolean;
     expandable: boolean;
     vromStart: number;
     vromEnd: number;
@@ -17,11 +18,13 @@ export type SetHighlightCallback = (start: number, end: number) => void;
 export type ClearHighlightCallback = () => void;
 export type ShowAddrCallback = (addr: number) => void;
 export type SelectCallback = (item: ItemView, start: number, end: number) => void;
+export type EditCallback = () => void;
 
 export class ItemView {
     private info: ReflectItemInfo;
     private fields: Wasm.ReflectFieldInfo[];
     private header: HTMLElement;
+    private valueElement?: HTMLElement;
     private indicator?: HTMLElement;
     private contents?: HTMLElement;
     private expanded?: boolean;
@@ -32,12 +35,14 @@ export class ItemView {
     public onclearhighlight?: ClearHighlightCallback;
     public onshowaddr?: ShowAddrCallback;
     public onselect?: SelectCallback;
+    public onedit?: EditCallback;
 
     constructor(
         private readonly ctx: Wasm.Context,
         private readonly root: Wasm.ReflectRoot,
         reflect: Wasm.ReflectResult,
         private readonly oddNesting: boolean,
+        private readonly field?: Wasm.ReflectFieldInfo,
     ) {
         this.oddNesting = oddNesting;
 
@@ -72,6 +77,11 @@ export class ItemView {
             this.handleElementClick();
         });
 
+        this.header.addEventListener('dblclick', e => {
+            e.stopPropagation();
+            this.handleHeaderDoubleClick();
+        });
+
         this.header.addEventListener('mouseenter', () => this.handleHeaderMouseEnter());
         this.header.addEventListener('mouseleave', () => this.handleHeaderMouseLeave());
 
@@ -84,7 +94,8 @@ export class ItemView {
 
         // Add a span element for the item's value.
         if (this.info.valueString !== undefined) {
-            this.header.appendChild($t('span', { textContent: ' = ' + this.info.valueString }));
+            this.header.appendChild(this.valueElement =
+                $t('span', { textContent: ' = ' + this.info.valueString }));
         }
 
         // Add elements for expandable content.
@@ -109,6 +120,35 @@ export class ItemView {
         }
     }
 
+    handleHeaderDoubleClick() {
+        if (!this.info.editable || this.field === undefined) {
+            return;
+        }
+
+        let text = window.prompt(
+            'New value for ' + (this.info.fieldName ?? this.info.typeString),
+            this.info.valueString);
+        if (text === null) {
+            return;
+        }
+        try {
+            this.field.setField(this.ctx, text);
+        } catch (e) {
+            window.alert(String(e));
+            return;
+        }
+
+        // Show the new value.
+        this.info = this.field.reflect(this.ctx, this.root).info;
+        if (this.valueElement !== undefined && this.info.valueString !== undefined) {
+            this.valueElement.textContent = ' = ' + this.info.valueString;
+        }
+
+        if (this.onedit) {
+            this.onedit();
+        }
+    }
+
     handleHeaderMouseEnter() {
         if (this.onsethighlight) {
             this.onsethighlight(this.info.vromStart, this.info.vromEnd);
@@ -144,7 +184,7 @@ export class ItemView {
     private expandImpl() {
         for (let field of this.fields) {
             let fieldView = new ItemView(
-                this.ctx, this.root, field.reflect(this.ctx, this.root), !this.oddNesting);
+                this.ctx, this.root, field.reflect(this.ctx, this.root), !this.oddNesting, field);
             this.contents!.appendChild(fieldView.element);
             fieldView.onsethighlight = (start, end) => {
                 if (this.onsethighlight) {
@@ -166,6 +206,11 @@ export class ItemView {
                     this.onselect(item, start, end);
                 }
             };
+            fieldView.onedit = () => {
+                if (this.onedit) {
+                    this.onedit();
+                }
+            };
             this.expandedFields.push(fieldView);
         }
         this.expanded = true;
@@ -73,37 +73,92 @@ void main() {
 }
 `;
 
+const COLLISION_VERTEX_SHADER_SOURCE = `#version 300 es
+
+precision highp float;
+precision highp int;
+
+layout(location = 0) in vec3 vertexPosition;
+layout(location = 1) in vec4 vertexColor;
+
+uniform mat4 u_projectionMatrix;
+uniform mat4 u_modelViewMatrix;
+
+out vec4 v_color;
+
+void main() {
+  gl_Position = u_projectionMatrix * u_modelViewMatrix * vec4(vertexPosition, 1.0);
+  v_color = vertexColor;
+}
+`;
+
+const COLLISION_FRAGMENT_SHADER_SOURCE = `#version 300 es
+
+precision highp float;
+precision highp int;
+
+in vec4 v_color;
+
+layout(location = 0) out vec4 fragColor;
+
+void main() {
+  fragColor = v_color;
+}
+`;
+
+// Bytes per collision vertex: a float position, then an RGBA color.
+const COLLISION_VERTEX_STRIDE = 16;
+
 function addLineNumbers(text: string) {
     return text.split('\n').map((line, i) => (i + 1) + ' | ' + line).join('\n');
 }
 
 function parseUrlFragment(): UrlFragmentParams {
-    let matches = /^#scene=([0-9]+)(&room=([0-9]+))?$/.exec(window.location.hash);
+    let matches = /^#scene=([0-9]+)(&setup=([0-9]+))?(&room=([0-9]+))?(&explore=([^&]*))?$/
+        .exec(window.location.hash);
     if (matches === null) {
         return {
             sceneIndex: 0,
+            setup: 0,
             roomIndex: undefined,
         };
     }
 
     let sceneIndex = parseInt(matches[1], 10);
-    let roomIndex = matches[3] !== undefined
+    let setup = matches[3] !== undefined
         ? parseInt(matches[3], 10)
+        : 0;
+    let roomIndex = matches[5] !== undefined
+        ? parseInt(matches[5], 10)
+        : undefined;
+    let explore = matches[7] !== undefined
+        ? decodeURIComponent(matches[7])
         : undefined;
 
     return {
         sceneIndex,
+        setup,
         roomIndex,
+        explore,
     };
 }
 
 interface UrlFragmentParams {
     sceneIndex: number;
+    // Which of the scene's setups to show. 0 is the default, 1 to 3 are child night, adult day, and
+    // adult night, and later setups are used by cutscenes.
+    setup: number;
     roomIndex?: number;
+    // A reflection query path, like "scene[85].room[2].headers[ActorList]", to open in an
+    // ExploreView.
+    explore?: string;
 }
 
-function updateUrlFragment({ sceneIndex, roomIndex }: UrlFragmentParams) {
+function updateUrlFragment({ sceneIndex, setup, roomIndex }: UrlFragmentParams) {
     let hash = '#scene=' + sceneIndex;
+    if (setup !== 0) {
+        hash += '&setup=' + setup;
+    }
     if (roomIndex !== undefined) {
         hash += '&room=' + roomIndex;
     }
@@ -119,6 +174,16 @@ interface View {
     pitch: number;
 }
 
+// Playback of the current scene setup's cutscene camera.
+interface CutscenePlayback {
+    frameCount: number;
+    // Fractional, since the game runs cutscenes at 20 frames per second.
+    frame: number;
+}
+
+// Cutscene frames per second.
+const CUTSCENE_FPS = 20;
+
 interface TouchState {
     x: number;
     y: number;
@@ -140,6 +205,14 @@ interface Batch {
     decal: boolean;
 }
 
+// Collision triangles and water box surfaces, drawn over the scene.
+interface CollisionOverlay {
+    triangleBuffer: WebGLBuffer;
+    triangleCount: number;
+    waterBoxBuffer: WebGLBuffer;
+    waterBoxCount: number;
+}
+
 interface BatchTexture {
     texture: WebGLTexture;
     sampler: WebGLSampler;
@@ -170,16 +243,23 @@ export class MainView {
         }
     };
     sceneIndex?: number;
+    setup: number = 0;
     roomIndex?: number;
     view: View;
+    cutscene?: CutscenePlayback;
     backgroundProgram: WebGLProgram;
     backgroundVertexBuffer: WebGLBuffer;
     batches: Batch[];
     currentResolves: (() => void)[];
     nextResolves: (() => void)[];
     backgrounds: WebGLTexture[];
+    collisionProgram: WebGLProgram;
+    collision?: CollisionOverlay;
+    showCollision: boolean = false;
     prevTimestamp?: number;
+    private readonly wasm: WasmModule;
     private readonly exploreButton: HTMLButtonElement;
+    private readonly collisionButton: HTMLButtonElement;
     private readonly prevSceneButton: HTMLButtonElement;
     private readonly nextSceneButton: HTMLButtonElement;
     private readonly prevRoomButton: HTMLButtonElement;
@@ -187,6 +267,7 @@ export class MainView {
 
     constructor({ wasm, rom }: MainViewCtorArgs) {
         Toolbar.show();
+        this.wasm = wasm;
 
         this.canvas = $t('canvas');
         let gl = this.gl = this.canvas.getContext(
@@ -200,15 +281,28 @@ export class MainView {
         this.exploreButton = <HTMLButtonElement>document.getElementById('explore')!;
         this.exploreButton.addEventListener('click', () => {
             if (this.sceneIndex !== undefined) {
-                let root = this.roomIndex !== undefined
-                    ? wasm.ReflectRoot.forRoom(this.ctx, this.sceneIndex, this.roomIndex)
-                    : wasm.ReflectRoot.forScene(this.ctx, this.sceneIndex);
-                let exploreView = new ExploreView(wasm, this.ctx, root);
-                this.canvas.parentElement!.appendChild(exploreView.element);
-                WindowManager.add(exploreView);
+                let root;
+                if (this.setup !== 0) {
+                    let query = 'scene[' + this.sceneIndex + '].setup[' + this.setup + ']';
+                    if (this.roomIndex !== undefined) {
+                        query += '.room[' + this.roomIndex + ']';
+                    }
+                    root = wasm.ReflectRoot.forQuery(this.ctx, query);
+                } else {
+                    root = this.roomIndex !== undefined
+                        ? wasm.ReflectRoot.forRoom(this.ctx, this.sceneIndex, this.roomIndex)
+                        : wasm.ReflectRoot.forScene(this.ctx, this.sceneIndex);
+                }
+                this.openExploreView(root);
             }
         });
 
+        this.collisionButton = <HTMLButtonElement>document.getElementById('collision')!;
+        this.collisionButton.addEventListener('click', () => {
+            this.showCollision = !this.showCollision;
+            this.collisionButton.classList.toggle('toggled', this.showCollision);
+        });
+
         this.canvas.addEventListener('mousedown', e => {
             if (e.button === 0) {
                 e.preventDefault();
@@ -264,6 +358,7 @@ export class MainView {
 
             if (key === 'PageDown') { this.nextScene(); }
             else if (key === 'PageUp') { this.prevScene(); }
+            else if (key === 'KeyC') { this.toggleCutscene(); }
         });
         window.addEventListener('keyup', e => {
             let key = e.code;
@@ -278,14 +373,17 @@ export class MainView {
             }
         });
         window.addEventListener('hashchange', e => {
-            let { sceneIndex, roomIndex } = parseUrlFragment();
-            if (sceneIndex !== this.sceneIndex) {
-                this.changeScene(sceneIndex, roomIndex);
+            let { sceneIndex, setup, roomIndex, explore } = parseUrlFragment();
+            if (explore !== undefined) {
+                this.explore(explore);
+            }
+            if (sceneIndex !== this.sceneIndex || setup !== this.setup) {
+                this.changeScene(sceneIndex, roomIndex, setup);
             } else if (roomIndex !== this.roomIndex) {
                 this.changeRoom(roomIndex);
             } else {
                 // Canonicalize it.
-                updateUrlFragment({ sceneIndex, roomIndex });
+                updateUrlFragment({ sceneIndex, setup, roomIndex });
             }
         });
 
@@ -314,6 +412,8 @@ export class MainView {
 
         this.backgroundProgram = glInitProgram(gl, BACKGROUND_VERTEX_SHADER_SOURCE,
             BACKGROUND_FRAGMENT_SHADER_SOURCE);
+        this.collisionProgram = glInitProgram(gl, COLLISION_VERTEX_SHADER_SOURCE,
+            COLLISION_FRAGMENT_SHADER_SOURCE);
         this.backgroundVertexBuffer = (() => {
             let data = new Float32Array(12);
             data.set([
@@ -339,12 +439,34 @@ export class MainView {
         this.nextResolves = [];
         this.backgrounds = [];
 
-        let { sceneIndex, roomIndex } = parseUrlFragment();
-        this.changeScene(sceneIndex, roomIndex);
+        let { sceneIndex, setup, roomIndex, explore } = parseUrlFragment();
+        this.changeScene(sceneIndex, roomIndex, setup);
+        if (explore !== undefined) {
+            this.explore(explore);
+        }
 
         window.requestAnimationFrame(timestamp => this.step(timestamp));
     }
 
+    private openExploreView(root: wasm.ReflectRoot) {
+        let exploreView = new ExploreView(this.wasm, this.ctx, root);
+        exploreView.onedit = () => this.reloadScene();
+        this.canvas.parentElement!.appendChild(exploreView.element);
+        WindowManager.add(exploreView);
+    }
+
+    // Opens an ExploreView for a reflection query path, as given in a deep link.
+    private explore(query: string) {
+        let root;
+        try {
+            root = this.wasm.ReflectRoot.forQuery(this.ctx, query);
+        } catch (e) {
+            console.log('WARNING: failed to resolve query: ' + e);
+            return;
+        }
+        this.openExploreView(root);
+    }
+
     nextStep() {
         return new Promise<void>(resolve => this.nextResolves.push(resolve));
     }
@@ -361,26 +483,29 @@ export class MainView {
         }
     }
 
-    async changeScene(sceneIndex: number, roomIndex?: number) {
+    async changeScene(sceneIndex: number, roomIndex?: number, setup: number = 0) {
         const sceneCount = this.ctx.sceneCount;
         if (sceneIndex >= sceneCount) {
             sceneIndex = sceneCount - 1;
         }
-        if (sceneIndex === this.sceneIndex) {
+        if (sceneIndex === this.sceneIndex && setup === this.setup) {
             this.changeRoom(roomIndex);
             return;
         }
 
         this.sceneIndex = undefined;
+        this.cutscene = undefined;
         let gl = this.gl;
-        document.getElementById('scene')!.textContent = 'Scene ' + sceneIndex;
+        document.getElementById('scene')!.textContent = setup === 0
+            ? 'Scene ' + sceneIndex
+            : 'Scene ' + sceneIndex + ', Setup ' + setup;
         this.prevSceneButton.disabled = sceneIndex === 0;
         this.nextSceneButton.disabled = sceneIndex === sceneCount - 1;
 
         Status.show('Processing scene...');
         await this.nextStep();
 
-        let processedScene = <WasmInterface.ProcessSceneResult>this.ctx.processScene(sceneIndex);
+        let processedScene = <WasmInterface.ProcessSceneResult>this.ctx.processScene(sceneIndex, setup);
 
         // Compile shaders for all batches.
         let vertexShader = glInitShader(gl, gl.VERTEX_SHADER, VERTEX_SHADER_SOURCE);
@@ -467,6 +592,26 @@ export class MainView {
             gl.generateMipmap(gl.TEXTURE_2D);
             backgrounds.push(texture);
         }
+        let collision;
+        if (processedScene.collision) {
+            let triangleBuffer = gl.createBuffer()!;
+            gl.bindBuffer(gl.ARRAY_BUFFER, triangleBuffer);
+            gl.bufferData(gl.ARRAY_BUFFER, processedScene.collision.triangleVertexData,
+                gl.STATIC_DRAW);
+            let waterBoxBuffer = gl.createBuffer()!;
+            gl.bindBuffer(gl.ARRAY_BUFFER, waterBoxBuffer);
+            gl.bufferData(gl.ARRAY_BUFFER, processedScene.collision.waterBoxVertexData,
+                gl.STATIC_DRAW);
+            collision = {
+                triangleBuffer,
+                triangleCount: processedScene.collision.triangleVertexData.byteLength
+                    / COLLISION_VERTEX_STRIDE,
+                waterBoxBuffer,
+                waterBoxCount: processedScene.collision.waterBoxVertexData.byteLength
+                    / COLLISION_VERTEX_STRIDE,
+            };
+        }
+
         if (processedScene.backgrounds.length > 1) {
             console.log('WARNING: found ' + processedScene.backgrounds.length
                 + ' backgrounds in this scene!');
@@ -474,8 +619,14 @@ export class MainView {
 
         // Publish all new data.
         this.sceneIndex = sceneIndex;
+        this.setup = setup;
         this.batches = (<Batch[]>[]).concat(opaqueBatches, translucentBatches);
         this.backgrounds = backgrounds;
+        if (this.collision) {
+            gl.deleteBuffer(this.collision.triangleBuffer);
+            gl.deleteBuffer(this.collision.waterBoxBuffer);
+        }
+        this.collision = collision;
         if (processedScene.startPos) {
             this.view = {
                 pos: vec3.clone([
@@ -492,6 +643,15 @@ export class MainView {
         this.changeRoom(roomIndex);
     }
 
+    // Reprocesses the current scene, such as after its data was edited.
+    private reloadScene() {
+        if (this.sceneIndex !== undefined) {
+            let { sceneIndex, roomIndex, setup } = this;
+            this.sceneIndex = undefined;
+            this.changeScene(sceneIndex, roomIndex, setup);
+        }
+    }
+
     private prevRoom() {
         if (this.sceneIndex === undefined) {
             return;
@@ -532,7 +692,55 @@ export class MainView {
         this.nextRoomButton.disabled = roomIndex === roomCount - 1;
         this.exploreButton.textContent = roomIndex === undefined ? 'Explore Scene' : 'Explore Room';
         this.exploreButton.disabled = false;
-        updateUrlFragment({ sceneIndex: this.sceneIndex, roomIndex: this.roomIndex });
+        updateUrlFragment({
+            sceneIndex: this.sceneIndex,
+            setup: this.setup,
+            roomIndex: this.roomIndex,
+        });
+    }
+
+    // Starts or stops playing the current scene setup's cutscene camera.
+    private toggleCutscene() {
+        if (this.cutscene !== undefined) {
+            this.cutscene = undefined;
+        } else if (this.sceneIndex !== undefined) {
+            let frameCount;
+            try {
+                frameCount = this.ctx.cutsceneFrameCount(this.sceneIndex, this.setup);
+            } catch (e) {
+                console.log('WARNING: failed to read cutscene: ' + e);
+                return;
+            }
+            if (frameCount !== undefined) {
+                this.cutscene = { frameCount, frame: 0 };
+            }
+        }
+    }
+
+    // Advances cutscene playback, returning the camera to draw with, if any.
+    private stepCutscene(dt: number): WasmInterface.CutsceneCameraResult | null {
+        if (this.cutscene === undefined || this.sceneIndex === undefined) {
+            return null;
+        }
+        this.cutscene.frame += dt * CUTSCENE_FPS;
+        if (this.cutscene.frame >= this.cutscene.frameCount) {
+            this.cutscene = undefined;
+            return null;
+        }
+        let camera: WasmInterface.CutsceneCameraResult | null;
+        try {
+            camera = this.ctx.cutsceneCamera(
+                this.sceneIndex, this.setup, Math.floor(this.cutscene.frame));
+        } catch (e) {
+            console.log('WARNING: failed to read cutscene camera: ' + e);
+            this.cutscene = undefined;
+            return null;
+        }
+        // Cameras relative to the player need a player to follow, which the viewer doesn't have.
+        if (camera === null || camera.eyeRelativeToPlayer || camera.atRelativeToPlayer) {
+            return null;
+        }
+        return camera;
     }
 
     updateDimensions(): [number, number] {
@@ -550,6 +758,50 @@ export class MainView {
         return [this.w, this.h];
     }
 
+    private drawCollision(collision: CollisionOverlay, projectionMatrix: mat4,
+        modelViewMatrix: mat4) {
+        let gl = this.gl;
+        let program = this.collisionProgram;
+        gl.useProgram(program);
+        gl.enable(gl.DEPTH_TEST);
+        gl.disable(gl.CULL_FACE);
+        gl.enable(gl.BLEND);
+        gl.blendFunc(gl.SRC_ALPHA, gl.ONE_MINUS_SRC_ALPHA);
+        gl.depthMask(false);
+        // Draw in front of the coplanar scene geometry.
+        gl.enable(gl.POLYGON_OFFSET_FILL);
+        gl.polygonOffset(-2, -2);
+
+        gl.uniformMatrix4fv(
+            gl.getUniformLocation(program, 'u_projectionMatrix'),
+            false,
+            projectionMatrix);
+        gl.uniformMatrix4fv(
+            gl.getUniformLocation(program, 'u_modelViewMatrix'),
+            false,
+            modelViewMatrix);
+
+        for (let i = 2; i < 5; ++i) {
+            gl.disableVertexAttribArray(i);
+        }
+        for (let [buffer, count] of <[WebGLBuffer, number][]>[
+            [collision.triangleBuffer, collision.triangleCount],
+            [collision.waterBoxBuffer, collision.waterBoxCount],
+        ]) {
+            gl.bindBuffer(gl.ARRAY_BUFFER, buffer);
+            // Position
+            gl.vertexAttribPointer(0, 3, gl.FLOAT, false, COLLISION_VERTEX_STRIDE, 0);
+            gl.enableVertexAttribArray(0);
+            // Color
+            gl.vertexAttribPointer(1, 4, gl.UNSIGNED_BYTE, true, COLLISION_VERTEX_STRIDE, 12);
+            gl.enableVertexAttribArray(1);
+
+            gl.drawArrays(gl.TRIANGLES, 0, count);
+        }
+
+        gl.disable(gl.POLYGON_OFFSET_FILL);
+    }
+
     step(timestamp: number) {
         // Trigger anything that was waiting for a new frame.
         for (let resolve of this.currentResolves) {
@@ -560,8 +812,10 @@ export class MainView {
 
         let [w, h] = this.updateDimensions();
 
+        let cutsceneCamera = null;
         if (this.prevTimestamp !== undefined) {
             let dt = (timestamp - this.prevTimestamp) / 1000;
+            cutsceneCamera = this.stepCutscene(dt);
             let motion = vec3.create();
             if (this.keys.get('KeyW')) {
                 vec3.add(motion, motion, [0, 0, -1]);
@@ -631,12 +885,18 @@ export class MainView {
         gl.frontFace(gl.CCW);
 
         let projectionMatrix = mat4.create();
-        mat4.perspective(projectionMatrix, 0.5 * Math.PI, w / h, 1, 20000.0);
-
         let modelViewMatrix = mat4.create();
-        mat4.rotateX(modelViewMatrix, modelViewMatrix, -this.view.pitch);
-        mat4.rotateY(modelViewMatrix, modelViewMatrix, -this.view.yaw);
-        {
+        if (cutsceneCamera !== null) {
+            mat4.perspective(projectionMatrix, cutsceneCamera.fovy * Math.PI / 180, w / h, 1,
+                20000.0);
+            mat4.fromZRotation(modelViewMatrix, -cutsceneCamera.roll * Math.PI / 180);
+            let lookAt = mat4.create();
+            mat4.lookAt(lookAt, cutsceneCamera.eye, cutsceneCamera.at, [0, 1, 0]);
+            mat4.multiply(modelViewMatrix, modelViewMatrix, lookAt);
+        } else {
+            mat4.perspective(projectionMatrix, 0.5 * Math.PI, w / h, 1, 20000.0);
+            mat4.rotateX(modelViewMatrix, modelViewMatrix, -this.view.pitch);
+            mat4.rotateY(modelViewMatrix, modelViewMatrix, -this.view.yaw);
             let npos = vec3.create();
             vec3.negate(npos, this.view.pos);
             mat4.translate(modelViewMatrix, modelViewMatrix, npos);
@@ -706,6 +966,10 @@ export class MainView {
             gl.drawArrays(batch.mode, 0, batch.count);
         }
 
+        if (this.showCollision && this.collision) {
+            this.drawCollision(this.collision, projectionMatrix, modelViewMatrix);
+        }
+
         let error = gl.getError();
         if (error !== gl.NO_ERROR) {
             console.log('gl error: ' + error);
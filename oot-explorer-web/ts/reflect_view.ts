@@ -2,7 +2,8 @@ import type * as Wasm from '../pkg';
 
 import { $t } from './dollar_t';
 import {
-    ClearHighlightCallback, ItemView, SelectCallback, SetHighlightCallback, ShowAddrCallback,
+    ClearHighlightCallback, EditCallback, ItemView, SelectCallback, SetHighlightCallback,
+    ShowAddrCallback,
 } from './item_view';
 import { WasmModule } from './wasm';
 
@@ -13,6 +14,7 @@ export class ReflectView {
     onclearhighlight?: ClearHighlightCallback;
     onshowaddr?: ShowAddrCallback;
     onselect?: SelectCallback;
+    onedit?: EditCallback;
 
     // TODO: addr, desc parameters? Or should roots be retrieve from wasm endpoints?
     constructor(wasm: WasmModule, ctx: Wasm.Context, root: Wasm.ReflectRoot) {
@@ -40,6 +42,11 @@ export class ReflectView {
                 this.onselect(item, start, end);
             }
         };
+        this.rootItem.onedit = () => {
+            if (this.onedit) {
+                this.onedit();
+            }
+        };
         this.rootItem.expand();
     }
 
@@ -11,6 +11,14 @@ export namespace WasmInterface {
         batches: ProcessSceneBatch[],
         backgrounds: string[],
         startPos?: [number, number, number, number, number],
+        paths: [number, number, number][][],
+        collision?: ProcessSceneCollision,
+    }
+
+    // Vertices are 16 bytes: a float position, then an RGBA color.
+    export interface ProcessSceneCollision {
+        triangleVertexData: ArrayBuffer;
+        waterBoxVertexData: ArrayBuffer;
     }
 
     export interface ProcessSceneBatch {
@@ -28,4 +36,22 @@ export namespace WasmInterface {
         width: number;
         height: number;
     }
+
+    export interface CutsceneCameraResult {
+        eye: [number, number, number];
+        at: [number, number, number];
+        roll: number;
+        fovy: number;
+        eyeRelativeToPlayer: boolean;
+        atRelativeToPlayer: boolean;
+    }
+
+    export interface XrefReference {
+        query: string;
+        owner: string;
+        addr: number;
+        targetType: string;
+        targetStart: number;
+        targetEnd: number;
+    }
 }